@@ -0,0 +1,368 @@
+//! SQL array values and conditions.
+//!
+//! `SqlArray` renders values as `ARRAY[...]` constructor or as `'{...}'`
+//! literal with quoted elements. Array conditions use `= ANY(...)`, `@>`,
+//! `<@` and `&&` operators of Postgres, dialects without arrays get `IN`
+//! list for `= ANY(...)` and an error for other operators and `unnest`.
+//! `ARRAY['a']` of strings is `text[]`, compare columns of other types like
+//! `varchar[]` with [`cast`](SqlArray::cast) array or untyped
+//! [`literal`](SqlArray::literal) one.
+//!
+//! ```
+//! # use anyhow::Result;
+//! use sql_builder::{array::SqlArray, SqlBuilder};
+//!
+//! # fn main() -> Result<()> {
+//! let tags = SqlArray::new(&["rust", "big data"]);
+//! let sql = SqlBuilder::select_from("posts")
+//!     .and_where_array_contains("tags", &tags)
+//!     .and_where_eq_any("author_id", &SqlArray::new(&[1, 2]))
+//!     .sql()?;
+//!
+//! assert_eq!("SELECT * FROM posts WHERE (tags @> ARRAY['rust', 'big data']) AND (author_id = ANY(ARRAY[1, 2]));", &sql);
+//!
+//! let sql = SqlBuilder::insert_into("posts")
+//!     .field("tags")
+//!     .values(&[tags.literal()])
+//!     .sql()?;
+//!
+//! assert_eq!("INSERT INTO posts (tags) VALUES ('{rust,\"big data\"}');", &sql);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::arg::SqlArg;
+use crate::dialect::Dialect;
+use crate::error::SqlBuilderError;
+use crate::page::is_null;
+use crate::SqlBuilder;
+use std::fmt;
+
+/// Array of SQL values
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SqlArray {
+    items: Vec<String>,
+    literal: bool,
+    cast: Option<String>,
+}
+
+impl SqlArray {
+    /// Make array of the values.
+    pub fn new<T: SqlArg>(values: &[T]) -> Self {
+        Self {
+            items: values.iter().map(|v| v.sql_arg()).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Render as `'{...}'` literal instead of `ARRAY[...]`.
+    pub fn literal(mut self) -> Self {
+        self.literal = true;
+        self
+    }
+
+    /// Cast to array of the element type, needed for empty arrays.
+    ///
+    /// ```
+    /// use sql_builder::array::SqlArray;
+    ///
+    /// assert_eq!("ARRAY[]::int[]", SqlArray::new::<i32>(&[]).cast("int").to_string());
+    /// assert_eq!("'{}'::text[]", SqlArray::new::<&str>(&[]).literal().cast("text").to_string());
+    /// ```
+    pub fn cast<S: ToString>(mut self, element_type: S) -> Self {
+        self.cast = Some(element_type.to_string());
+        self
+    }
+
+    /// Check if the array has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Make table function `unnest` for FROM or JOIN part.
+    /// Dialects without arrays have no `unnest`.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{array::SqlArray, Dialect, SqlBuilder};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let ids = SqlArray::new(&[3, 1, 2]);
+    /// let sql = SqlBuilder::select_from(ids.unnest("t(id)", Dialect::Postgres)?)
+    ///     .field("t.id")
+    ///     .field("b.title")
+    ///     .join("books AS b")
+    ///     .on("b.id = t.id")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT t.id, b.title FROM unnest(ARRAY[3, 1, 2]) AS t(id) JOIN books AS b ON b.id = t.id;", &sql);
+    ///
+    /// let res = ids.unnest("t(id)", Dialect::MySql);
+    /// assert_eq!("Function \"unnest\" is not supported by dialect", &res.unwrap_err().to_string());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn unnest<S: ToString>(
+        &self,
+        alias: S,
+        dialect: Dialect,
+    ) -> Result<String, SqlBuilderError> {
+        if !dialect.arrays() {
+            return Err(SqlBuilderError::UnsupportedFunction("unnest".to_string()));
+        }
+        Ok(format!("unnest({}) AS {}", self, alias.to_string()))
+    }
+
+    /// Make element of array literal
+    fn literal_item(item: &str) -> String {
+        if is_null(item) {
+            return "NULL".to_string();
+        }
+        let text = match item.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')) {
+            Some(text) => text.replace("''", "'"),
+            None => return item.to_string(),
+        };
+        let plain = !text.is_empty()
+            && !is_null(&text)
+            && !text
+                .chars()
+                .any(|c| c.is_whitespace() || matches!(c, '{' | '}' | ',' | '"' | '\\'));
+        if plain {
+            text
+        } else {
+            format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+        }
+    }
+}
+
+impl fmt::Display for SqlArray {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.literal {
+            let items: Vec<String> = self.items.iter().map(|i| Self::literal_item(i)).collect();
+            write!(f, "'{{{}}}'", items.join(",").replace('\'', "''"))?;
+        } else {
+            write!(f, "ARRAY[{}]", self.items.join(", "))?;
+        }
+        match &self.cast {
+            Some(element_type) => write!(f, "::{}[]", element_type),
+            None => Ok(()),
+        }
+    }
+}
+
+impl SqlArg for SqlArray {
+    fn sql_arg(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl SqlArg for &SqlArray {
+    fn sql_arg(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl<T: SqlArg> From<Vec<T>> for SqlArray {
+    fn from(values: Vec<T>) -> Self {
+        Self::new(&values)
+    }
+}
+
+impl SqlBuilder {
+    /// Add WHERE field = ANY(array), or field IN (list) if arrays are not supported.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{array::SqlArray, Dialect, SqlBuilder};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .dialect(Dialect::MySql)
+    ///     .and_where_eq_any("id", &SqlArray::from(vec![1, 2]))
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM books WHERE id IN (1, 2);", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_where_eq_any<S: ToString>(&mut self, field: S, array: &SqlArray) -> &mut Self {
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+
+        // Change
+        if self.dialect.arrays() {
+            return self.and_where(format!("{} = ANY({})", field, array));
+        }
+        if array.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereList(field));
+        }
+        self.and_where(format!("{} IN ({})", field, array.items.join(", ")))
+    }
+
+    /// Add WHERE array field contains all elements of the array: `@>`.
+    /// Cast the array to the element type of non-text columns.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{array::SqlArray, SqlBuilder};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("posts")
+    ///     .and_where_array_contains("tags", &SqlArray::new(&["rust"]).cast("varchar"))
+    ///     .and_where_array_contains("codes", &SqlArray::new(&["a1"]).literal())
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM posts WHERE (tags @> ARRAY['rust']::varchar[]) AND (codes @> '{a1}');", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_where_array_contains<S: ToString>(
+        &mut self,
+        field: S,
+        array: &SqlArray,
+    ) -> &mut Self {
+        self.and_where_array_op(field, "@>", array)
+    }
+
+    /// Add WHERE array field elements are all in the array: `<@`.
+    pub fn and_where_array_contained_by<S: ToString>(
+        &mut self,
+        field: S,
+        array: &SqlArray,
+    ) -> &mut Self {
+        self.and_where_array_op(field, "<@", array)
+    }
+
+    /// Add WHERE array field has common elements with the array: `&&`.
+    pub fn and_where_array_overlaps<S: ToString>(
+        &mut self,
+        field: S,
+        array: &SqlArray,
+    ) -> &mut Self {
+        self.and_where_array_op(field, "&&", array)
+    }
+
+    /// Add WHERE comparison of array field length with the number.
+    /// Length of empty array is NULL, use `coalesce` in raw condition to match it.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("posts")
+    ///     .and_where_array_length("tags", ">=", 2)
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM posts WHERE array_length(tags, 1) >= 2;", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_where_array_length<S: ToString>(
+        &mut self,
+        field: S,
+        op: &str,
+        length: usize,
+    ) -> &mut Self {
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+        if !self.dialect.arrays() || !["=", "<>", "!=", "<", "<=", ">", ">="].contains(&op) {
+            return self.set_error(&SqlBuilderError::UnknownOperator(op.to_string()));
+        }
+
+        // Change
+        self.and_where(format!("array_length({}, 1) {} {}", field, op, length))
+    }
+
+    /// Add WHERE array operator condition
+    fn and_where_array_op<S: ToString>(
+        &mut self,
+        field: S,
+        op: &str,
+        array: &SqlArray,
+    ) -> &mut Self {
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+        if !self.dialect.arrays() {
+            return self.set_error(&SqlBuilderError::UnknownOperator(op.to_string()));
+        }
+
+        // Change
+        self.and_where(format!("{} {} {}", field, op, array))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Dialect;
+    use anyhow::Result;
+
+    #[test]
+    fn test_array() -> Result<()> {
+        let array = SqlArray::new(&[Some("a b"), None, Some(r#"q"\"#), Some(""), Some("null")]);
+        assert_eq!(
+            array.clone().to_string(),
+            r#"ARRAY['a b', NULL, 'q"\', '', 'null']"#
+        );
+        assert_eq!(
+            array.literal().to_string(),
+            r#"'{"a b",NULL,"q\"\\","","null"}'"#
+        );
+        assert_eq!(
+            SqlArray::new(&[1.5, -2.0])
+                .literal()
+                .cast("float8")
+                .to_string(),
+            "'{1.5,-2}'::float8[]"
+        );
+
+        let tags = SqlArray::new(&["a"]);
+        let sql = SqlBuilder::select_from("posts")
+            .dialect(Dialect::Postgres)
+            .and_where_array_contained_by("tags", &tags)
+            .and_where_array_overlaps("tags", &tags.clone().literal())
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT * FROM posts WHERE (tags <@ ARRAY['a']) AND (tags && '{a}');"
+        );
+
+        let res = SqlBuilder::select_from("posts")
+            .dialect(Dialect::Sqlite)
+            .and_where_array_overlaps("tags", &tags)
+            .sql();
+        assert_eq!(
+            &res.unwrap_err().to_string(),
+            "Comparison operator \"&&\" is not supported"
+        );
+
+        assert_eq!(
+            &tags
+                .unnest("t(tag)", Dialect::Sqlite)
+                .unwrap_err()
+                .to_string(),
+            "Function \"unnest\" is not supported by dialect"
+        );
+
+        let res = SqlBuilder::select_from("posts")
+            .dialect(Dialect::MsSql)
+            .and_where_eq_any("id", &SqlArray::new::<i32>(&[]))
+            .sql();
+        assert_eq!(
+            &res.unwrap_err().to_string(),
+            "WHERE list for field \"id\" not defined"
+        );
+
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use crate::arg::{SqlArg, SqlArgs};
 
 pub trait Bind {
-    /// Replace first ? with a value.
+    /// Replace first ? with a value, ? inside quoted literals is kept.
     ///
     /// ```
     /// # use std::error::Error;
@@ -165,7 +165,14 @@ impl Bind for String {
     where
         S: SqlArg,
     {
-        self.replacen('?', &arg.sql_arg(), 1)
+        match placeholders(self).first() {
+            Some(&pos) => {
+                let mut res = self.clone();
+                res.replace_range(pos..pos + 1, &arg.sql_arg());
+                res
+            }
+            None => self.clone(),
+        }
     }
 
     /// Cyclic bindings of values.
@@ -193,14 +200,14 @@ impl Bind for String {
         let mut offset = 0;
         let mut res = String::new();
         let len = args.len();
-        for ch in self.chars() {
-            if ch == '?' {
-                res.push_str(&args[offset]);
-                offset = (offset + 1) % len;
-            } else {
-                res.push(ch);
-            }
+        let mut last = 0;
+        for pos in placeholders(self) {
+            res.push_str(&self[last..pos]);
+            res.push_str(&args[offset]);
+            offset = (offset + 1) % len;
+            last = pos + 1;
         }
+        res.push_str(&self[last..]);
         res
     }
 
@@ -232,6 +239,36 @@ impl Bind for String {
     }
 }
 
+/// Find positions of ? placeholders outside of quoted literals.
+/// Postgres operators `?`, `?|` and `?&` followed by a literal or ARRAY are kept,
+/// because a placeholder is never followed by a value.
+fn placeholders(sql: &str) -> Vec<usize> {
+    let mut quoted = false;
+    sql.char_indices()
+        .filter_map(|(pos, ch)| {
+            match ch {
+                '\'' => quoted = !quoted,
+                '?' if !quoted && !is_key_operator(&sql[pos + 1..]) => return Some(pos),
+                _ => {}
+            }
+            None
+        })
+        .collect()
+}
+
+/// Check if text after ? is the right operand of a JSON key operator
+fn is_key_operator(rest: &str) -> bool {
+    let rest = match rest.strip_prefix(['|', '&']) {
+        Some(tail) if !tail.starts_with(['|', '&']) => tail,
+        _ => rest,
+    };
+    let operand = rest.trim_start();
+    operand.starts_with('\'')
+        || operand
+            .get(..6)
+            .is_some_and(|word| word.eq_ignore_ascii_case("ARRAY["))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -254,6 +291,12 @@ mod tests {
         assert_eq!("foTRUEo", &"fo?o".bind(&true));
         assert_eq!("foFALSEo", &"fo?o".bind(&false));
         assert_eq!("f'lol'oo:def:", &"f:abc:oo:def:".bind_name(&"abc", &"lol"));
+        assert_eq!("'?' = 'a?'", &"? = ?".bind("?").bind("a?"));
+        assert_eq!("'it''s ?' 1", &"'it''s ?' ?".binds((1,)));
+        assert_eq!(
+            "attrs ? 'a' AND attrs ?| ARRAY['b'] AND 1|| 'c'",
+            &"attrs ? 'a' AND attrs ?| ARRAY['b'] AND ?|| 'c'".bind(1)
+        );
 
         Ok(())
     }
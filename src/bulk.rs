@@ -0,0 +1,398 @@
+//! Bulk UPDATE of many rows with different values.
+//!
+//! Rows are identified by the key column. Postgres updates rows joined with
+//! `FROM (VALUES ...)`, other dialects pick the values with `CASE key WHEN ...`.
+//! Postgres infers types of VALUES columns from literals, so columns of
+//! dates, numerics or only NULLs need [`cast`](BulkUpdateBuilder::cast).
+//! Rows may be split into chunks to keep statements under size limits.
+//! Every statement gets tenant and scope conditions of the table.
+//!
+//! ```
+//! # use anyhow::Result;
+//! use sql_builder::{bulk::BulkUpdateBuilder, Dialect};
+//!
+//! # fn main() -> Result<()> {
+//! let mut prices = BulkUpdateBuilder::new("books", "id", &["price", "title"]);
+//! prices
+//!     .row(1, &[&100, &"Dune"])
+//!     .row(2, &[&150, &"Emma"]);
+//!
+//! assert_eq!(vec!["UPDATE books SET price = CASE id WHEN 1 THEN 100 WHEN 2 THEN 150 END, title = CASE id WHEN 1 THEN 'Dune' WHEN 2 THEN 'Emma' END WHERE id IN (1, 2);"], prices.sql()?);
+//!
+//! prices.dialect(Dialect::Postgres);
+//! assert_eq!(vec!["UPDATE books SET price = v.price, title = v.title FROM (VALUES (1, 100, 'Dune'), (2, 150, 'Emma')) AS v(id, price, title) WHERE books.id = v.id;"], prices.sql()?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::arg::{RawSql, SqlArg};
+use crate::case::CaseExpr;
+use crate::dialect::Dialect;
+use crate::error::SqlBuilderError;
+use crate::{scope, tenant, SqlBuilder};
+use anyhow::Result;
+
+/// Builder of bulk UPDATE
+#[derive(Clone)]
+pub struct BulkUpdateBuilder {
+    table: String,
+    key: String,
+    columns: Vec<String>,
+    rows: Vec<(String, Vec<String>)>,
+    casts: Vec<(String, String)>,
+    wheres: Vec<String>,
+    chunk_size: Option<usize>,
+    dialect: Dialect,
+    tenant: Option<String>,
+    error: Option<SqlBuilderError>,
+}
+
+impl BulkUpdateBuilder {
+    /// Create bulk UPDATE of the table columns for rows identified by the key column.
+    pub fn new<S, K, C>(table: S, key: K, columns: &[C]) -> Self
+    where
+        S: ToString,
+        K: ToString,
+        C: ToString,
+    {
+        Self {
+            table: table.to_string(),
+            key: key.to_string(),
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            rows: Vec::new(),
+            casts: Vec::new(),
+            wheres: Vec::new(),
+            chunk_size: None,
+            dialect: Dialect::default(),
+            tenant: None,
+            error: None,
+        }
+    }
+
+    /// Add row with key and values of the columns.
+    pub fn row<K: SqlArg>(&mut self, key: K, values: &[&dyn SqlArg]) -> &mut Self {
+        if values.len() != self.columns.len() {
+            self.error = Some(SqlBuilderError::InvalidRowLength(
+                self.columns.len(),
+                values.len(),
+            ));
+            return self;
+        }
+        let values = values.iter().map(|v| v.sql_arg()).collect();
+        self.rows.push((key.sql_arg(), values));
+        self
+    }
+
+    /// Set SQL type of the key or column for VALUES list of Postgres.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{bulk::BulkUpdateBuilder, Dialect};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = BulkUpdateBuilder::new("books", "id", &["price", "published"])
+    ///     .row(1, &[&"9.99", &"2024-01-01"])
+    ///     .row(2, &[&"5.00", &None::<&str>])
+    ///     .cast("price", "numeric")
+    ///     .cast("published", "date")
+    ///     .dialect(Dialect::Postgres)
+    ///     .sql()?;
+    ///
+    /// assert_eq!(vec!["UPDATE books SET price = v.price, published = v.published FROM (VALUES (1, '9.99'::numeric, '2024-01-01'::date), (2, '5.00', NULL)) AS v(id, price, published) WHERE books.id = v.id;"], sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn cast<S: ToString, T: ToString>(&mut self, column: S, sql_type: T) -> &mut Self {
+        let column = column.to_string();
+        if column != self.key && !self.columns.contains(&column) {
+            self.error = Some(SqlBuilderError::InvalidQuery(format!(
+                "no column {} to cast",
+                column
+            )));
+            return self;
+        }
+        self.casts.push((column, sql_type.to_string()));
+        self
+    }
+
+    /// Add WHERE condition applied to every statement.
+    pub fn and_where<S: ToString>(&mut self, cond: S) -> &mut Self {
+        let cond = cond.to_string();
+        if cond.is_empty() {
+            self.error = Some(SqlBuilderError::NoWhereCond);
+            return self;
+        }
+        self.wheres.push(cond);
+        self
+    }
+
+    /// Set maximal number of rows updated by one statement.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::bulk::BulkUpdateBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = BulkUpdateBuilder::new("books", "id", &["price"])
+    ///     .row(1, &[&100])
+    ///     .row(2, &[&150])
+    ///     .row(3, &[&200])
+    ///     .and_where("NOT archived")
+    ///     .chunk_size(2)
+    ///     .sql()?;
+    ///
+    /// assert_eq!(vec![
+    ///     "UPDATE books SET price = CASE id WHEN 1 THEN 100 WHEN 2 THEN 150 END WHERE (id IN (1, 2)) AND (NOT archived);",
+    ///     "UPDATE books SET price = CASE id WHEN 3 THEN 200 END WHERE (id IN (3)) AND (NOT archived);",
+    /// ], sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn chunk_size(&mut self, rows: usize) -> &mut Self {
+        self.chunk_size = Some(rows.max(1));
+        self
+    }
+
+    /// Set target SQL dialect.
+    pub fn dialect(&mut self, dialect: Dialect) -> &mut Self {
+        self.dialect = dialect;
+        self
+    }
+
+    /// Set tenant for tenant-scoped table.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{bulk::BulkUpdateBuilder, tenant, Dialect};
+    ///
+    /// # fn main() -> Result<()> {
+    /// tenant::register_tenant_table("ledgers", "tenant_id");
+    ///
+    /// let mut bulk = BulkUpdateBuilder::new("ledgers", "id", &["total"]);
+    /// bulk.row(1, &[&100]).tenant(7);
+    ///
+    /// assert_eq!(vec!["UPDATE ledgers SET total = CASE id WHEN 1 THEN 100 END WHERE (id IN (1)) AND (tenant_id = 7);"], bulk.sql()?);
+    ///
+    /// bulk.dialect(Dialect::Postgres);
+    /// assert_eq!(vec!["UPDATE ledgers SET total = v.total FROM (VALUES (1, 100)) AS v(id, total) WHERE (ledgers.id = v.id) AND (ledgers.tenant_id = 7);"], bulk.sql()?);
+    ///
+    /// let res = BulkUpdateBuilder::new("ledgers", "id", &["total"])
+    ///     .row(1, &[&100])
+    ///     .sql();
+    ///
+    /// assert_eq!("Tenant is not set for table \"ledgers\"", &res.unwrap_err().to_string());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn tenant<T: SqlArg>(&mut self, id: T) -> &mut Self {
+        self.tenant = Some(id.sql_arg());
+        self
+    }
+
+    /// Build UPDATE commands, one for every chunk of rows.
+    pub fn sql(&self) -> Result<Vec<String>> {
+        if let Some(err) = &self.error {
+            return Err(err.clone().into());
+        }
+        if self.table.is_empty() {
+            return Err(SqlBuilderError::NoTableName.into());
+        }
+        if self.key.is_empty() {
+            return Err(SqlBuilderError::NoWhereField.into());
+        }
+        if self.columns.is_empty() {
+            return Err(SqlBuilderError::NoSetFields.into());
+        }
+        if self.rows.is_empty() {
+            return Err(SqlBuilderError::NoValues.into());
+        }
+
+        // Conditions are qualified by table name next to VALUES list
+        let joined = self.dialect.update_from_values();
+        let from = if joined {
+            format!("{} AS {}", self.table, self.table)
+        } else {
+            self.table.clone()
+        };
+        let mut wheres = self.wheres.clone();
+        wheres.append(&mut scope::scope_conds(&from, &[]));
+        let (mut tenant_wheres, _) = tenant::tenant_filter(&from, &[], self.tenant.as_deref())?;
+        wheres.append(&mut tenant_wheres);
+
+        let size = self.chunk_size.unwrap_or(self.rows.len());
+        Ok(self
+            .rows
+            .chunks(size)
+            .map(|rows| {
+                if joined {
+                    self.values_join(rows, &wheres)
+                } else {
+                    self.case(rows, &wheres)
+                }
+            })
+            .collect())
+    }
+
+    /// Make UPDATE with CASE for every column
+    fn case(&self, rows: &[(String, Vec<String>)], conds: &[String]) -> String {
+        let sets = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                let mut case = CaseExpr::simple(&self.key);
+                for (key, values) in rows {
+                    case.when_value(RawSql(key), RawSql(&values[i]));
+                }
+                format!("{} = {}", column, case)
+            })
+            .collect::<Vec<String>>();
+        let keys = rows
+            .iter()
+            .map(|(key, _)| key.as_str())
+            .collect::<Vec<&str>>();
+
+        let mut wheres = vec![format!("{} IN ({})", self.key, keys.join(", "))];
+        wheres.extend(conds.iter().cloned());
+        format!(
+            "UPDATE {} SET {}{};",
+            self.table,
+            sets.join(", "),
+            SqlBuilder::make_wheres(&wheres)
+        )
+    }
+
+    /// Make UPDATE joined with VALUES list
+    fn values_join(&self, rows: &[(String, Vec<String>)], conds: &[String]) -> String {
+        let sets = self
+            .columns
+            .iter()
+            .map(|column| format!("{} = v.{}", column, column))
+            .collect::<Vec<String>>();
+        // Types of VALUES columns follow the first row, so only it is cast
+        let mut values = rows
+            .iter()
+            .map(|(key, values)| format!("({}, {})", key, values.join(", ")))
+            .collect::<Vec<String>>();
+        if !self.casts.is_empty() {
+            let (key, row) = &rows[0];
+            let row = std::iter::once((&self.key, key))
+                .chain(self.columns.iter().zip(row))
+                .map(
+                    |(column, value)| match self.casts.iter().rev().find(|(c, _)| c == column) {
+                        Some((_, sql_type)) => format!("{}::{}", value, sql_type),
+                        None => value.to_string(),
+                    },
+                )
+                .collect::<Vec<String>>();
+            values[0] = format!("({})", row.join(", "));
+        }
+
+        let mut wheres = vec![format!("{}.{} = v.{}", self.table, self.key, self.key)];
+        wheres.extend(conds.iter().cloned());
+        format!(
+            "UPDATE {} SET {} FROM (VALUES {}) AS v({}, {}){};",
+            self.table,
+            sets.join(", "),
+            values.join(", "),
+            self.key,
+            self.columns.join(", "),
+            SqlBuilder::make_wheres(&wheres)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bulk_update() -> Result<()> {
+        let mut bulk = BulkUpdateBuilder::new("users", "id", &["name", "age"]);
+        bulk.row(7, &[&"Ann", &Some(30)])
+            .row(8, &[&"Bob", &None::<i32>])
+            .row(9, &[&"Eve", &41])
+            .and_where("active")
+            .chunk_size(2)
+            .dialect(Dialect::Postgres);
+        assert_eq!(
+            bulk.sql()?,
+            vec![
+                "UPDATE users SET name = v.name, age = v.age FROM (VALUES (7, 'Ann', 30), (8, 'Bob', NULL)) AS v(id, name, age) WHERE (users.id = v.id) AND (active);",
+                "UPDATE users SET name = v.name, age = v.age FROM (VALUES (9, 'Eve', 41)) AS v(id, name, age) WHERE (users.id = v.id) AND (active);",
+            ]
+        );
+
+        let res = BulkUpdateBuilder::new("users", "id", &["name", "age"])
+            .row(1, &[&"Ann"])
+            .sql();
+        assert_eq!(
+            &res.unwrap_err().to_string(),
+            "Row has 1 values instead of 2"
+        );
+
+        let res = BulkUpdateBuilder::new("users", "id", &["name"]).sql();
+        assert_eq!(&res.unwrap_err().to_string(), "No values");
+
+        let mut bulk = BulkUpdateBuilder::new("users", "uid", &["name", "age"]);
+        bulk.row("a1", &[&"Ann", &None::<i32>])
+            .cast("uid", "uuid")
+            .cast("age", "int");
+        assert_eq!(
+            bulk.sql()?,
+            vec!["UPDATE users SET name = CASE uid WHEN 'a1' THEN 'Ann' END, age = CASE uid WHEN 'a1' THEN NULL END WHERE uid IN ('a1');"]
+        );
+        assert_eq!(
+            bulk.dialect(Dialect::Postgres).sql()?,
+            vec!["UPDATE users SET name = v.name, age = v.age FROM (VALUES ('a1'::uuid, 'Ann', NULL::int)) AS v(uid, name, age) WHERE users.uid = v.uid;"]
+        );
+
+        let res = BulkUpdateBuilder::new("users", "id", &["name"])
+            .cast("age", "int")
+            .sql();
+        assert_eq!(
+            &res.unwrap_err().to_string(),
+            "Query is invalid: no column age to cast"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bulk_update_scoped() -> Result<()> {
+        crate::tenant::register_tenant_table("bulk_accounts", "tenant_id");
+        crate::scope::register_soft_delete("bulk_accounts", "deleted_at");
+
+        let mut bulk = BulkUpdateBuilder::new("bulk_accounts", "id", &["name"]);
+        bulk.row(1, &[&"Ann"])
+            .row(2, &[&"Bob"])
+            .chunk_size(1)
+            .tenant(5);
+        assert_eq!(
+            bulk.sql()?,
+            vec![
+                "UPDATE bulk_accounts SET name = CASE id WHEN 1 THEN 'Ann' END WHERE (id IN (1)) AND (deleted_at IS NULL) AND (tenant_id = 5);",
+                "UPDATE bulk_accounts SET name = CASE id WHEN 2 THEN 'Bob' END WHERE (id IN (2)) AND (deleted_at IS NULL) AND (tenant_id = 5);",
+            ]
+        );
+        assert_eq!(
+            bulk.dialect(Dialect::Postgres).sql()?,
+            vec![
+                "UPDATE bulk_accounts SET name = v.name FROM (VALUES (1, 'Ann')) AS v(id, name) WHERE (bulk_accounts.id = v.id) AND (bulk_accounts.deleted_at IS NULL) AND (bulk_accounts.tenant_id = 5);",
+                "UPDATE bulk_accounts SET name = v.name FROM (VALUES (2, 'Bob')) AS v(id, name) WHERE (bulk_accounts.id = v.id) AND (bulk_accounts.deleted_at IS NULL) AND (bulk_accounts.tenant_id = 5);",
+            ]
+        );
+
+        let res = BulkUpdateBuilder::new("bulk_accounts", "id", &["name"])
+            .row(1, &[&"Ann"])
+            .sql();
+        assert_eq!(
+            &res.unwrap_err().to_string(),
+            "Tenant is not set for table \"bulk_accounts\""
+        );
+
+        crate::tenant::remove_tenant_table("bulk_accounts");
+        crate::scope::remove_scopes("bulk_accounts");
+        Ok(())
+    }
+}
@@ -0,0 +1,288 @@
+//! CASE expressions.
+//!
+//! Searched form takes WHEN conditions as text or builds them with the
+//! `and_where_*` methods of [`SqlBuilder`]. Simple form compares the
+//! expression with values. THEN and ELSE values are rendered as [`SqlArg`].
+//! Display and [`SqlArg`] render the expression as is, builder methods like
+//! [`field_case`](SqlBuilder::field_case) report its errors instead.
+//!
+//! ```
+//! # use anyhow::Result;
+//! use sql_builder::{case::CaseExpr, SqlBuilder};
+//!
+//! # fn main() -> Result<()> {
+//! let category = CaseExpr::new()
+//!     .when("price < 100", "cheap")
+//!     .when_where(|b| { b.and_where_ge("price", 100).and_where_lt("price", 1000); }, "regular")
+//!     .otherwise("expensive")
+//!     .clone();
+//!
+//! let sql = SqlBuilder::select_from("books")
+//!     .field("title")
+//!     .field_case(&category.alias("category"))
+//!     .order_by_case(&category)
+//!     .sql()?;
+//!
+//! assert_eq!("SELECT title, CASE WHEN price < 100 THEN 'cheap' WHEN (price >= 100) AND (price < 1000) THEN 'regular' ELSE 'expensive' END AS category FROM books ORDER BY CASE WHEN price < 100 THEN 'cheap' WHEN (price >= 100) AND (price < 1000) THEN 'regular' ELSE 'expensive' END;", &sql);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::arg::SqlArg;
+use crate::error::SqlBuilderError;
+use crate::SqlBuilder;
+use std::fmt;
+
+/// Builder of CASE expression
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CaseExpr {
+    expr: Option<String>,
+    whens: Vec<(String, String)>,
+    otherwise: Option<String>,
+    alias: Option<String>,
+    error: Option<SqlBuilderError>,
+}
+
+impl CaseExpr {
+    /// Create searched CASE expression.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create simple CASE expression comparing the expression with WHEN values.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{case::CaseExpr, update::UpdateBuilder};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = UpdateBuilder::update_table("orders")
+    ///     .set("status", CaseExpr::simple("status").when_value("new", "pending").when_value("pending", "done").otherwise_raw("status"))
+    ///     .build()?;
+    ///
+    /// assert_eq!("UPDATE orders SET status = CASE status WHEN 'new' THEN 'pending' WHEN 'pending' THEN 'done' ELSE status END;", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn simple<S: ToString>(expr: S) -> Self {
+        Self {
+            expr: Some(expr.to_string()),
+            ..Self::default()
+        }
+    }
+
+    /// Add WHEN condition THEN value.
+    pub fn when<S: ToString, T: SqlArg>(&mut self, cond: S, value: T) -> &mut Self {
+        let cond = cond.to_string();
+        if cond.is_empty() {
+            self.error = Some(SqlBuilderError::NoWhereCond);
+            return self;
+        }
+        self.whens.push((cond, value.sql_arg()));
+        self
+    }
+
+    /// Add WHEN condition built with WHERE methods THEN value.
+    pub fn when_where<F, T>(&mut self, f: F, value: T) -> &mut Self
+    where
+        F: FnOnce(&mut SqlBuilder),
+        T: SqlArg,
+    {
+        let mut builder = SqlBuilder::select_from("");
+        f(&mut builder);
+        if let Some(err) = builder.error {
+            self.error = Some(err);
+            return self;
+        }
+        let cond = match builder.wheres.len() {
+            0 => String::new(),
+            1 => builder.wheres.remove(0),
+            _ => builder
+                .wheres
+                .iter()
+                .map(|w| format!("({})", w))
+                .collect::<Vec<String>>()
+                .join(" AND "),
+        };
+        self.when(cond, value)
+    }
+
+    /// Add WHEN value THEN value of simple CASE expression.
+    pub fn when_value<V: SqlArg, T: SqlArg>(&mut self, value: V, result: T) -> &mut Self {
+        self.whens.push((value.sql_arg(), result.sql_arg()));
+        self
+    }
+
+    /// Set ELSE value.
+    pub fn otherwise<T: SqlArg>(&mut self, value: T) -> &mut Self {
+        self.otherwise = Some(value.sql_arg());
+        self
+    }
+
+    /// Set ELSE expression.
+    pub fn otherwise_raw<S: ToString>(&mut self, expr: S) -> &mut Self {
+        self.otherwise = Some(expr.to_string());
+        self
+    }
+
+    /// Make field with alias.
+    pub fn alias<S: ToString>(&self, alias: S) -> Self {
+        Self {
+            alias: Some(alias.to_string()),
+            ..self.clone()
+        }
+    }
+
+    /// Build CASE expression checking errors of WHEN conditions.
+    pub fn build(&self) -> Result<String, SqlBuilderError> {
+        self.check()?;
+        Ok(self.to_string())
+    }
+
+    /// Check errors of WHEN conditions
+    fn check(&self) -> Result<(), SqlBuilderError> {
+        if let Some(err) = &self.error {
+            return Err(err.clone());
+        }
+        if self.whens.is_empty() {
+            return Err(SqlBuilderError::NoWhereCond);
+        }
+        Ok(())
+    }
+
+    /// Make expression without alias
+    fn expr(&self) -> String {
+        let mut text = String::from("CASE");
+        if let Some(expr) = &self.expr {
+            text.push(' ');
+            text.push_str(expr);
+        }
+        for (cond, value) in &self.whens {
+            text.push_str(&format!(" WHEN {} THEN {}", cond, value));
+        }
+        if let Some(otherwise) = &self.otherwise {
+            text.push_str(" ELSE ");
+            text.push_str(otherwise);
+        }
+        text.push_str(" END");
+        text
+    }
+}
+
+impl SqlBuilder {
+    /// Add CASE expression field, errors of the expression are reported by the builder.
+    pub fn field_case(&mut self, case: &CaseExpr) -> &mut Self {
+        match case.build() {
+            Ok(field) => self.field(field),
+            Err(err) => self.set_error(&err),
+        }
+    }
+
+    /// Add SET part with CASE expression, errors of the expression are reported by the builder.
+    pub fn set_case<S: ToString>(&mut self, field: S, case: &CaseExpr) -> &mut Self {
+        match case.check() {
+            Ok(()) => self.set(field, case.expr()),
+            Err(err) => self.set_error(&err),
+        }
+    }
+
+    /// Add ORDER BY CASE expression, errors of the expression are reported by the builder.
+    pub fn order_by_case(&mut self, case: &CaseExpr) -> &mut Self {
+        match case.check() {
+            Ok(()) => self.order_by(case.expr()),
+            Err(err) => self.set_error(&err),
+        }
+    }
+}
+
+impl fmt::Display for CaseExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.alias {
+            Some(alias) => write!(f, "{} AS {}", self.expr(), alias),
+            None => write!(f, "{}", self.expr()),
+        }
+    }
+}
+
+impl SqlArg for CaseExpr {
+    fn sql_arg(&self) -> String {
+        self.expr()
+    }
+}
+
+impl SqlArg for &CaseExpr {
+    fn sql_arg(&self) -> String {
+        self.expr()
+    }
+}
+
+impl SqlArg for &mut CaseExpr {
+    fn sql_arg(&self) -> String {
+        self.expr()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    #[test]
+    fn test_case() -> Result<()> {
+        let sql = SqlBuilder::update_table("books")
+            .set(
+                "price",
+                CaseExpr::new()
+                    .when_where(
+                        |b| {
+                            b.and_where_eq("author", "'Tolkien'")
+                                .or_where_eq("author", "'Lewis'");
+                        },
+                        10,
+                    )
+                    .otherwise_raw("price")
+                    .sql_arg(),
+            )
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "UPDATE books SET price = CASE WHEN author = 'Tolkien' OR author = 'Lewis' THEN 10 ELSE price END;"
+        );
+
+        let sql = SqlBuilder::select_from("books")
+            .order_desc(CaseExpr::simple("lang").when_value("en", 1).otherwise(0))
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT * FROM books ORDER BY CASE lang WHEN 'en' THEN 1 ELSE 0 END DESC;"
+        );
+
+        assert_eq!(
+            CaseExpr::new()
+                .when_where(
+                    |b| {
+                        b.and_where_gt("", 1);
+                    },
+                    1
+                )
+                .build(),
+            Err(SqlBuilderError::NoWhereField)
+        );
+        assert_eq!(
+            CaseExpr::new().otherwise(1).build(),
+            Err(SqlBuilderError::NoWhereCond)
+        );
+
+        let res = SqlBuilder::update_table("books")
+            .set_case("price", CaseExpr::new().when("", 1))
+            .sql();
+        assert_eq!(&res.unwrap_err().to_string(), "WHERE condition is empty");
+
+        let res = SqlBuilder::select_from("books")
+            .order_by_case(&CaseExpr::simple("lang"))
+            .sql();
+        assert_eq!(&res.unwrap_err().to_string(), "WHERE condition is empty");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,423 @@
+//! Splitting of large statements into chunks.
+//!
+//! Multi-row INSERT and the largest IN list of WHERE part may be split into
+//! several statements limited by rows, parameters or size in bytes.
+//! Parameters are counted as values of rows and items of the list,
+//! as they become parameters when values are bound.
+//!
+//! ```
+//! # use anyhow::Result;
+//! use sql_builder::{chunk::ChunkLimit, SqlBuilder};
+//!
+//! # fn main() -> Result<()> {
+//! let sql = SqlBuilder::insert_into("books")
+//!     .field("title")
+//!     .field("price")
+//!     .values(&["'Dune'", "100"])
+//!     .values(&["'Emma'", "150"])
+//!     .values(&["'Ulysses'", "200"])
+//!     .sql_chunks(ChunkLimit::new().max_params(4))?;
+//!
+//! assert_eq!(vec![
+//!     "INSERT INTO books (title, price) VALUES ('Dune', 100), ('Emma', 150);",
+//!     "INSERT INTO books (title, price) VALUES ('Ulysses', 200);",
+//! ], sql);
+//!
+//! let sql = SqlBuilder::delete_from("books")
+//!     .and_where_in("id", &[1, 2, 3, 4, 5])
+//!     .sql_chunks(ChunkLimit::new().max_rows(2))?;
+//!
+//! assert_eq!(vec![
+//!     "DELETE FROM books WHERE id IN (1, 2);",
+//!     "DELETE FROM books WHERE id IN (3, 4);",
+//!     "DELETE FROM books WHERE id IN (5);",
+//! ], sql);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::dialect::Dialect;
+use crate::error::SqlBuilderError;
+use crate::{SqlBuilder, Statement, Values};
+use anyhow::Result;
+
+/// Limits of one statement
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ChunkLimit {
+    rows: Option<usize>,
+    params: Option<usize>,
+    bytes: Option<usize>,
+}
+
+impl ChunkLimit {
+    /// Create limits without restrictions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create default limits of the dialect:
+    /// 65535 parameters of Postgres, 999 parameters of SQLite before 3.32,
+    /// 4 MiB `max_allowed_packet` of MySQL 5.7 and 2100 parameters and
+    /// 1000 rows of VALUES in SQL Server.
+    pub fn for_dialect(dialect: Dialect) -> Self {
+        match dialect {
+            Dialect::Generic => Self::new(),
+            Dialect::Postgres => Self::new().max_params(65535),
+            Dialect::MySql => Self::new().max_params(65535).max_bytes(4 * 1024 * 1024),
+            Dialect::Sqlite => Self::new().max_params(999),
+            Dialect::MsSql => Self::new().max_params(2100).max_rows(1000),
+        }
+    }
+
+    /// Set maximal number of rows or list items.
+    pub fn max_rows(mut self, rows: usize) -> Self {
+        self.rows = Some(rows);
+        self
+    }
+
+    /// Set maximal number of parameters.
+    pub fn max_params(mut self, params: usize) -> Self {
+        self.params = Some(params);
+        self
+    }
+
+    /// Set maximal size of statement in bytes.
+    pub fn max_bytes(mut self, bytes: usize) -> Self {
+        self.bytes = Some(bytes);
+        self
+    }
+
+    /// Split items into chunks, every chunk has at least one item.
+    /// Size of item is its number of parameters and length in bytes.
+    fn split<'a, T, F>(&self, items: &'a [T], size: F, base: usize) -> Vec<&'a [T]>
+    where
+        F: Fn(&T) -> (usize, usize),
+    {
+        let mut chunks = Vec::new();
+        let mut start = 0;
+        let (mut rows, mut count, mut bytes) = (0, 0, base);
+        for (i, item) in items.iter().enumerate() {
+            let (item_params, item_len) = size(item);
+            let item_bytes = if rows > 0 { item_len + 2 } else { item_len };
+            let over = self.rows.is_some_and(|max| rows + 1 > max)
+                || self.params.is_some_and(|max| count + item_params > max)
+                || self.bytes.is_some_and(|max| bytes + item_bytes > max);
+            if rows > 0 && over {
+                chunks.push(&items[start..i]);
+                start = i;
+                rows = 0;
+                count = 0;
+                bytes = base;
+            }
+            rows += 1;
+            count += item_params;
+            bytes += if rows > 1 { item_len + 2 } else { item_len };
+        }
+        chunks.push(&items[start..]);
+        chunks
+    }
+}
+
+/// IN list of WHERE part kept for splitting
+#[derive(Clone)]
+pub(crate) struct InList {
+    cond: String,
+    field: String,
+    list: Vec<String>,
+}
+
+/// Size of list joined with ", "
+fn joined_len(items: &[String]) -> usize {
+    items
+        .iter()
+        .map(|i| i.len() + 2)
+        .sum::<usize>()
+        .saturating_sub(2)
+}
+
+impl SqlBuilder {
+    /// Build SQL commands splitting rows of INSERT or the largest IN list
+    /// added with [`and_where_in`](SqlBuilder::and_where_in) into chunks.
+    /// Other queries are returned as one command.
+    ///
+    /// SELECT with DISTINCT, GROUP BY, LIMIT or OFFSET cannot be split,
+    /// ORDER BY applies to every chunk separately.
+    pub fn sql_chunks(&self, limit: ChunkLimit) -> Result<Vec<String>> {
+        if let Some(err) = &self.error {
+            return Err(err.clone().into());
+        }
+
+        if let (Statement::InsertInto, Values::List(rows)) = (&self.statement, &self.values) {
+            // Tenant value is added to every row
+            let tenant = match self.insert_tenant()? {
+                Some((_, tenant)) => vec![tenant],
+                None => Vec::new(),
+            };
+            let rendered: Vec<String> = rows
+                .iter()
+                .map(|row| format!("({})", [row.as_slice(), &tenant].concat().join(", ")))
+                .collect();
+            let base = self.sql()?.len() - joined_len(&rendered);
+            return limit
+                .split(
+                    rows,
+                    |row| {
+                        let row = [row.as_slice(), &tenant].concat();
+                        (row.len(), joined_len(&row) + 2)
+                    },
+                    base,
+                )
+                .into_iter()
+                .map(|chunk| {
+                    let mut builder = self.clone();
+                    builder.values = Values::List(chunk.to_vec());
+                    builder.sql()
+                })
+                .collect();
+        }
+
+        let Some(in_list) = self
+            .in_lists
+            .iter()
+            .filter(|l| self.wheres.contains(&l.cond))
+            .max_by_key(|l| l.list.len())
+        else {
+            return Ok(vec![self.sql()?]);
+        };
+
+        if matches!(
+            self.statement,
+            Statement::SelectFrom | Statement::SelectValues
+        ) && (self.distinct
+            || !self.group_by.is_empty()
+            || !self.groupings.is_empty()
+            || self.limit.is_some()
+            || self.offset.is_some())
+        {
+            return Err(SqlBuilderError::InvalidQuery(
+                "SELECT with DISTINCT, GROUP BY, LIMIT or OFFSET cannot be split".to_string(),
+            )
+            .into());
+        }
+
+        let base = self.sql()?.len() - joined_len(&in_list.list);
+        let pos = self.wheres.iter().position(|w| w == &in_list.cond);
+        limit
+            .split(&in_list.list, |item| (1, item.len()), base)
+            .into_iter()
+            .map(|chunk| {
+                let mut builder = self.clone();
+                if let Some(pos) = pos {
+                    builder.wheres[pos] = format!("{} IN ({})", in_list.field, chunk.join(", "));
+                }
+                builder.sql()
+            })
+            .collect()
+    }
+
+    /// Add WHERE field = ANY(ARRAY[list]) in Postgres, so the statement text
+    /// does not depend on the list length. Values are rendered inline,
+    /// bind the whole array as one parameter with `field = ANY(?)` instead.
+    /// Other dialects get WHERE field IN (list), set dialect before the condition.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{Dialect, SqlBuilder};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .dialect(Dialect::Postgres)
+    ///     .and_where_in_array("id", &[1, 2, 3])
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM books WHERE id = ANY(ARRAY[1, 2, 3]);", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_where_in_array<S, T>(&mut self, field: S, list: &[T]) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        if self.dialect != Dialect::Postgres {
+            return self.and_where_in(field, list);
+        }
+
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+        if list.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereList(field));
+        }
+
+        // Change
+        let list: Vec<String> = list.iter().map(|v| v.to_string()).collect();
+        self.and_where(format!("{} = ANY(ARRAY[{}])", field, list.join(", ")))
+    }
+
+    /// Add WHERE field IN (VALUES list), which is planned as a join in Postgres.
+    /// MySQL gets `VALUES ROW(...)` and SQL Server a derived table,
+    /// set dialect before the condition.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{Dialect, SqlBuilder};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .and_where_in_values("id", &[1, 2, 3])
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM books WHERE id IN (VALUES (1), (2), (3));", &sql);
+    ///
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .dialect(Dialect::MsSql)
+    ///     .and_where_in_values("id", &[1, 2])
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM books WHERE id IN (SELECT v FROM (VALUES (1), (2)) AS t(v));", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_where_in_values<S, T>(&mut self, field: S, list: &[T]) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+        if list.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereList(field));
+        }
+
+        // Change
+        let row = if self.dialect == Dialect::MySql {
+            "ROW"
+        } else {
+            ""
+        };
+        let rows = list
+            .iter()
+            .map(|v| format!("{}({})", row, v.to_string()))
+            .collect::<Vec<String>>()
+            .join(", ");
+        let cond = match self.dialect {
+            Dialect::MsSql => format!("{} IN (SELECT v FROM (VALUES {}) AS t(v))", field, rows),
+            _ => format!("{} IN (VALUES {})", field, rows),
+        };
+        self.and_where(cond)
+    }
+
+    /// Add WHERE field IN (list) keeping the list for splitting
+    pub(crate) fn add_in_list(&mut self, field: String, list: Vec<String>) -> &mut Self {
+        let cond = format!("{} IN ({})", field, list.join(", "));
+        self.in_lists.push(InList {
+            cond: cond.clone(),
+            field,
+            list,
+        });
+        self.and_where(cond)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_row_params() -> Result<()> {
+        let sql = SqlBuilder::insert_into("t")
+            .fields(["a", "b"])
+            .values(&["'x, y'", "f(1, 2)"])
+            .values(&["'it''s'", "2"])
+            .sql_chunks(ChunkLimit::new().max_params(4))?;
+        assert_eq!(
+            sql,
+            vec!["INSERT INTO t (a, b) VALUES ('x, y', f(1, 2)), ('it''s', 2);"]
+        );
+
+        crate::tenant::register_tenant_table("chunk_orders", "tenant_id");
+        let sql = SqlBuilder::insert_into("chunk_orders")
+            .field("total")
+            .values(&[1])
+            .values(&[2])
+            .values(&[3])
+            .tenant(7)
+            .sql_chunks(ChunkLimit::new().max_params(4))?;
+        assert_eq!(
+            sql,
+            vec![
+                "INSERT INTO chunk_orders (total, tenant_id) VALUES (1, 7), (2, 7);",
+                "INSERT INTO chunk_orders (total, tenant_id) VALUES (3, 7);",
+            ]
+        );
+        let sql = SqlBuilder::insert_into("chunk_orders")
+            .field("total")
+            .values(&[1])
+            .values(&[2])
+            .tenant(7)
+            .sql_chunks(ChunkLimit::new().max_bytes(64))?;
+        assert_eq!(
+            sql,
+            vec![
+                "INSERT INTO chunk_orders (total, tenant_id) VALUES (1, 7);",
+                "INSERT INTO chunk_orders (total, tenant_id) VALUES (2, 7);",
+            ]
+        );
+
+        crate::tenant::remove_tenant_table("chunk_orders");
+        Ok(())
+    }
+
+    #[test]
+    fn test_sql_chunks() -> Result<()> {
+        let sql = SqlBuilder::update_table("books")
+            .set("price", 0)
+            .and_where_in_quoted("title", &["Dune", "Emma", "Ulysses"])
+            .and_where("price > 0")
+            .sql_chunks(ChunkLimit::new().max_bytes(80))?;
+        assert_eq!(
+            sql,
+            vec![
+                "UPDATE books SET price = 0 WHERE (title IN ('Dune', 'Emma')) AND (price > 0);",
+                "UPDATE books SET price = 0 WHERE (title IN ('Ulysses')) AND (price > 0);",
+            ]
+        );
+
+        let sql = SqlBuilder::select_from("books")
+            .and_where_in("id", &[1, 2, 3])
+            .sql_chunks(ChunkLimit::for_dialect(Dialect::Postgres))?;
+        assert_eq!(sql, vec!["SELECT * FROM books WHERE id IN (1, 2, 3);"]);
+
+        let sql = SqlBuilder::select_from("books")
+            .and_where("price > 0")
+            .sql_chunks(ChunkLimit::new().max_rows(1))?;
+        assert_eq!(sql, vec!["SELECT * FROM books WHERE price > 0;"]);
+
+        let res = SqlBuilder::select_from("books")
+            .and_where_in("id", &[1, 2, 3])
+            .limit(10)
+            .sql_chunks(ChunkLimit::new().max_rows(1));
+        assert_eq!(
+            &res.unwrap_err().to_string(),
+            "Query is invalid: SELECT with DISTINCT, GROUP BY, LIMIT or OFFSET cannot be split"
+        );
+
+        let sql = SqlBuilder::select_from("books")
+            .dialect(Dialect::MySql)
+            .and_where_in_values("id", &[1, 2])
+            .and_where_in_array("author_id", &[3])
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT * FROM books WHERE (id IN (VALUES ROW(1), ROW(2))) AND (author_id IN (3));"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,349 @@
+//! Compound queries combined with UNION, INTERSECT and EXCEPT.
+//!
+//! Every arm is parenthesized, so arms may have their own ORDER BY and LIMIT.
+//! ORDER BY, LIMIT and OFFSET of the compound query apply to the whole result.
+//! Operators are applied in order of calls, the left side is grouped when
+//! the operator changes.
+//!
+//! ```
+//! # use anyhow::Result;
+//! use sql_builder::{compound::CompoundBuilder, SqlBuilder};
+//!
+//! # fn main() -> Result<()> {
+//! let sql = CompoundBuilder::new(SqlBuilder::select_from("books").field("title").order_desc("price").limit(3))
+//!     .union_all(SqlBuilder::select_from("drafts").field("title"))
+//!     .except("SELECT title FROM banned")
+//!     .order_asc("title")
+//!     .limit(10)
+//!     .sql()?;
+//!
+//! assert_eq!("((SELECT title FROM books ORDER BY price DESC LIMIT 3) UNION ALL (SELECT title FROM drafts)) EXCEPT (SELECT title FROM banned) ORDER BY title LIMIT 10;", &sql);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::dialect::Dialect;
+use crate::error::SqlBuilderError;
+use crate::query::SqlQuery;
+use anyhow::Result;
+
+/// Builder of compound query
+#[derive(Clone)]
+pub struct CompoundBuilder {
+    arms: Vec<(&'static str, String)>,
+    order_by: Vec<String>,
+    limit: Option<String>,
+    offset: Option<String>,
+    dialect: Dialect,
+    error: Option<SqlBuilderError>,
+}
+
+impl CompoundBuilder {
+    /// Create compound query starting with the query.
+    pub fn new<S: SqlQuery>(query: S) -> Self {
+        let mut builder = Self {
+            arms: Vec::new(),
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+            dialect: Dialect::default(),
+            error: None,
+        };
+        builder.add_arm("", query);
+        builder
+    }
+
+    /// Add UNION query.
+    pub fn union<S: SqlQuery>(&mut self, query: S) -> &mut Self {
+        self.add_arm("UNION", query)
+    }
+
+    /// Add UNION ALL query.
+    pub fn union_all<S: SqlQuery>(&mut self, query: S) -> &mut Self {
+        self.add_arm("UNION ALL", query)
+    }
+
+    /// Add INTERSECT query.
+    pub fn intersect<S: SqlQuery>(&mut self, query: S) -> &mut Self {
+        self.add_arm("INTERSECT", query)
+    }
+
+    /// Add INTERSECT ALL query.
+    pub fn intersect_all<S: SqlQuery>(&mut self, query: S) -> &mut Self {
+        self.add_arm("INTERSECT ALL", query)
+    }
+
+    /// Add EXCEPT query.
+    pub fn except<S: SqlQuery>(&mut self, query: S) -> &mut Self {
+        self.add_arm("EXCEPT", query)
+    }
+
+    /// Add EXCEPT ALL query.
+    pub fn except_all<S: SqlQuery>(&mut self, query: S) -> &mut Self {
+        self.add_arm("EXCEPT ALL", query)
+    }
+
+    /// Set target SQL dialect.
+    /// SQLite does not accept parenthesized arms, so they are wrapped into `SELECT * FROM (...)`.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{compound::CompoundBuilder, Dialect};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = CompoundBuilder::new("SELECT id FROM a")
+    ///     .intersect("SELECT id FROM b")
+    ///     .dialect(Dialect::Sqlite)
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM (SELECT id FROM a) INTERSECT SELECT * FROM (SELECT id FROM b);", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn dialect(&mut self, dialect: Dialect) -> &mut Self {
+        self.dialect = dialect;
+        self
+    }
+
+    /// Add ORDER BY of the whole result.
+    pub fn order_by<S: ToString>(&mut self, field: S) -> &mut Self {
+        self.order_by.push(field.to_string());
+        self
+    }
+
+    /// Add ORDER BY ASC of the whole result.
+    pub fn order_asc<S: ToString>(&mut self, field: S) -> &mut Self {
+        self.order_by(field)
+    }
+
+    /// Add ORDER BY DESC of the whole result.
+    pub fn order_desc<S: ToString>(&mut self, field: S) -> &mut Self {
+        self.order_by(format!("{} DESC", field.to_string()))
+    }
+
+    /// Set LIMIT of the whole result.
+    pub fn limit<S: ToString>(&mut self, limit: S) -> &mut Self {
+        self.limit = Some(limit.to_string());
+        self
+    }
+
+    /// Set OFFSET of the whole result.
+    pub fn offset<S: ToString>(&mut self, offset: S) -> &mut Self {
+        self.offset = Some(offset.to_string());
+        self
+    }
+
+    /// Build complete SQL command.
+    pub fn sql(&self) -> Result<String> {
+        let mut text = self.query()?;
+        text.push(';');
+        Ok(text)
+    }
+
+    /// Build named subquery SQL command.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{compound::CompoundBuilder, SqlBuilder};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let titles = CompoundBuilder::new("SELECT title FROM books")
+    ///     .union("SELECT title FROM drafts")
+    ///     .subquery_as("t")?;
+    ///
+    /// let sql = SqlBuilder::select_from(&titles)
+    ///     .field("COUNT(*)")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT COUNT(*) FROM ((SELECT title FROM books) UNION (SELECT title FROM drafts)) AS t;", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn subquery_as<S: ToString>(&self, name: S) -> Result<String> {
+        Ok(format!("({}) AS {}", self.query()?, name.to_string()))
+    }
+
+    /// SQL command generator for query or subquery.
+    pub fn query(&self) -> Result<String> {
+        if let Some(err) = &self.error {
+            return Err(err.clone().into());
+        }
+        if self.arms.len() < 2 {
+            return Err(SqlBuilderError::NoValues.into());
+        }
+
+        let mut text = String::new();
+        let mut last_op = "";
+        for (op, query) in &self.arms {
+            if !op.is_empty() {
+                // Mixed operators are applied in order of calls:
+                // INTERSECT binds tighter, so the left side is grouped
+                if !last_op.is_empty() && op_kind(op) != op_kind(last_op) {
+                    text = self.arm(&text);
+                }
+                last_op = op;
+                text.push(' ');
+                text.push_str(op);
+                text.push(' ');
+            }
+            text.push_str(&self.arm(query));
+        }
+
+        let order_by = if self.order_by.is_empty() {
+            None
+        } else {
+            Some(self.order_by.join(", "))
+        };
+        match self.dialect {
+            // MS SQL has no LIMIT and needs ORDER BY for OFFSET and FETCH
+            Dialect::MsSql if self.limit.is_some() || self.offset.is_some() => {
+                text.push_str(" ORDER BY ");
+                text.push_str(order_by.as_deref().unwrap_or("(SELECT NULL)"));
+                text.push_str(&format!(
+                    " OFFSET {} ROWS",
+                    self.offset.as_deref().unwrap_or("0")
+                ));
+                if let Some(limit) = &self.limit {
+                    text.push_str(&format!(" FETCH NEXT {} ROWS ONLY", limit));
+                }
+            }
+            _ => {
+                if let Some(order_by) = order_by {
+                    text.push_str(" ORDER BY ");
+                    text.push_str(&order_by);
+                }
+                if let Some(limit) = &self.limit {
+                    text.push_str(" LIMIT ");
+                    text.push_str(limit);
+                }
+                if let Some(offset) = &self.offset {
+                    text.push_str(" OFFSET ");
+                    text.push_str(offset);
+                }
+            }
+        }
+        Ok(text)
+    }
+
+    /// Make compound query of legacy UNION parts of the builder,
+    /// ORDER BY, LIMIT and OFFSET of the builder apply to the whole result
+    pub(crate) fn from_unions(
+        first: String,
+        unions: &[(&'static str, String)],
+        order_by: &[String],
+        limit: &Option<String>,
+        offset: &Option<String>,
+        dialect: Dialect,
+    ) -> Self {
+        let mut arms = vec![("", first)];
+        arms.extend(unions.iter().cloned());
+        Self {
+            arms,
+            order_by: order_by.to_vec(),
+            limit: limit.clone(),
+            offset: offset.clone(),
+            dialect,
+            error: None,
+        }
+    }
+
+    /// Make parenthesized arm of the query
+    fn arm(&self, query: &str) -> String {
+        match self.dialect {
+            Dialect::Sqlite => format!("SELECT * FROM ({})", query),
+            _ => format!("({})", query),
+        }
+    }
+
+    /// Add query arm combined with the operator
+    fn add_arm<S: SqlQuery>(&mut self, op: &'static str, query: S) -> &mut Self {
+        match query.sql_query() {
+            Ok(query) if query.is_empty() => self.error = Some(SqlBuilderError::NoValues),
+            Ok(query) => self.arms.push((op, query)),
+            Err(err) => {
+                self.error = Some(match err.downcast::<SqlBuilderError>() {
+                    Ok(err) => err,
+                    Err(err) => SqlBuilderError::InvalidQuery(err.to_string()),
+                });
+            }
+        }
+        self
+    }
+}
+
+/// Operator without ALL modifier
+fn op_kind(op: &str) -> &str {
+    op.trim_end_matches(" ALL")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SqlBuilder;
+
+    #[test]
+    fn test_compound() -> Result<()> {
+        let recent = SqlBuilder::select_from("books")
+            .field("id")
+            .order_desc("year")
+            .limit(5)
+            .clone();
+        let sql = CompoundBuilder::new(&recent)
+            .intersect_all(SqlBuilder::select_from("sales").field("book_id"))
+            .except_all("SELECT book_id FROM returns")
+            .order_desc("id")
+            .limit(2)
+            .offset(1)
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "((SELECT id FROM books ORDER BY year DESC LIMIT 5) INTERSECT ALL (SELECT book_id FROM sales)) EXCEPT ALL (SELECT book_id FROM returns) ORDER BY id DESC LIMIT 2 OFFSET 1;"
+        );
+
+        let mut query = CompoundBuilder::new("SELECT id FROM a")
+            .union("SELECT id FROM b")
+            .union_all("SELECT id FROM c")
+            .intersect("SELECT id FROM d")
+            .limit(10)
+            .clone();
+        assert_eq!(
+            &query.sql()?,
+            "((SELECT id FROM a) UNION (SELECT id FROM b) UNION ALL (SELECT id FROM c)) INTERSECT (SELECT id FROM d) LIMIT 10;"
+        );
+        assert_eq!(
+            &query.dialect(Dialect::MsSql).sql()?,
+            "((SELECT id FROM a) UNION (SELECT id FROM b) UNION ALL (SELECT id FROM c)) INTERSECT (SELECT id FROM d) ORDER BY (SELECT NULL) OFFSET 0 ROWS FETCH NEXT 10 ROWS ONLY;"
+        );
+        assert_eq!(
+            &query.offset(20).order_by("id").sql()?,
+            "((SELECT id FROM a) UNION (SELECT id FROM b) UNION ALL (SELECT id FROM c)) INTERSECT (SELECT id FROM d) ORDER BY id OFFSET 20 ROWS FETCH NEXT 10 ROWS ONLY;"
+        );
+        assert_eq!(
+            &query.dialect(Dialect::Sqlite).sql()?,
+            "SELECT * FROM (SELECT * FROM (SELECT id FROM a) UNION SELECT * FROM (SELECT id FROM b) UNION ALL SELECT * FROM (SELECT id FROM c)) INTERSECT SELECT * FROM (SELECT id FROM d) ORDER BY id LIMIT 10 OFFSET 20;"
+        );
+
+        let sql = SqlBuilder::select_from("authors")
+            .field("name")
+            .and_where_in_query(
+                "id",
+                CompoundBuilder::new("SELECT author_id FROM books")
+                    .union("SELECT author_id FROM drafts"),
+            )
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT name FROM authors WHERE id IN ((SELECT author_id FROM books) UNION (SELECT author_id FROM drafts));"
+        );
+
+        let res = CompoundBuilder::new("SELECT 1").sql();
+        assert_eq!(&res.unwrap_err().to_string(), "No values");
+
+        let res = CompoundBuilder::new("SELECT 1")
+            .union(SqlBuilder::select_from("books").and_where(""))
+            .sql();
+        assert_eq!(&res.unwrap_err().to_string(), "WHERE condition is empty");
+
+        Ok(())
+    }
+}
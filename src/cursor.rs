@@ -0,0 +1,384 @@
+//! Opaque cursors for keyset pagination.
+//!
+//! Cursor keeps typed values of the last row, so a decoded cursor
+//! is rendered with proper quoting and never injects raw input.
+//!
+//! ```
+//! # use anyhow::Result;
+//! use sql_builder::{cursor::Cursor, page::{Direction, SortKey}, SqlBuilder};
+//!
+//! # fn main() -> Result<()> {
+//! let sort = [SortKey::asc("title"), SortKey::asc("id")];
+//!
+//! // Last row of the previous page
+//! let token = Cursor::new().push("Dune").push(42).encode()?;
+//!
+//! let sql = SqlBuilder::select_from("books")
+//!     .seek(&sort, &Cursor::decode(&token)?, Direction::After)
+//!     .limit(10)
+//!     .sql()?;
+//!
+//! assert_eq!("SELECT * FROM books WHERE (title > 'Dune') OR (title = 'Dune' AND id > 42) ORDER BY title, id LIMIT 10;", &sql);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::arg::{SqlArg, SqlArgs};
+use crate::error::SqlBuilderError;
+use crate::quote;
+use std::convert::{TryFrom, TryInto};
+
+/// Typed value of cursor
+#[derive(Clone, Debug, PartialEq)]
+pub enum CursorValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+}
+
+impl SqlArg for CursorValue {
+    fn sql_arg(&self) -> String {
+        match self {
+            CursorValue::Null => String::from("NULL"),
+            CursorValue::Bool(value) => value.sql_arg(),
+            CursorValue::Int(value) => value.to_string(),
+            CursorValue::Float(value) => value.to_string(),
+            CursorValue::Text(value) => quote(value),
+        }
+    }
+}
+
+macro_rules! impl_cursor_value_from {
+    ($variant:ident : $($type:ty),*) => {
+        $(
+            impl From<$type> for CursorValue {
+                fn from(value: $type) -> Self {
+                    CursorValue::$variant(value.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_cursor_value_from!(Bool: bool);
+impl_cursor_value_from!(Int: i8, u8, i16, u16, i32, u32, i64);
+impl_cursor_value_from!(Text: &str, String);
+
+macro_rules! impl_cursor_value_try_from {
+    ($($type:ty),*) => {
+        $(
+            impl TryFrom<$type> for CursorValue {
+                type Error = SqlBuilderError;
+
+                /// Make float value, NaN and infinity are rejected
+                fn try_from(value: $type) -> Result<Self, Self::Error> {
+                    if value.is_finite() {
+                        Ok(CursorValue::Float(value.into()))
+                    } else {
+                        Err(SqlBuilderError::InvalidCursor)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_cursor_value_try_from!(f32, f64);
+
+impl<T: Into<CursorValue>> From<Option<T>> for CursorValue {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => value.into(),
+            None => CursorValue::Null,
+        }
+    }
+}
+
+impl TryFrom<Option<f64>> for CursorValue {
+    type Error = SqlBuilderError;
+
+    fn try_from(value: Option<f64>) -> Result<Self, Self::Error> {
+        match value {
+            Some(value) => value.try_into(),
+            None => Ok(CursorValue::Null),
+        }
+    }
+}
+
+impl CursorValue {
+    /// Check if value can be rendered as SQL literal
+    fn is_valid(&self) -> bool {
+        match self {
+            CursorValue::Float(value) => value.is_finite(),
+            _ => true,
+        }
+    }
+}
+
+/// Values of the last row encoded into opaque token
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Cursor {
+    values: Vec<CursorValue>,
+    error: Option<SqlBuilderError>,
+}
+
+impl Cursor {
+    /// Create empty cursor
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add value of the next sort key.
+    /// Float NaN and infinity make encoding fail.
+    pub fn push<V>(&mut self, value: V) -> &mut Self
+    where
+        V: TryInto<CursorValue>,
+        SqlBuilderError: From<V::Error>,
+    {
+        match value.try_into() {
+            Ok(value) if value.is_valid() => self.values.push(value),
+            Ok(_) => self.error = Some(SqlBuilderError::InvalidCursor),
+            Err(err) => self.error = Some(err.into()),
+        }
+        self
+    }
+
+    /// Values of cursor
+    pub fn values(&self) -> &[CursorValue] {
+        &self.values
+    }
+
+    /// Encode cursor into URL-safe token
+    pub fn encode(&self) -> Result<String, SqlBuilderError> {
+        if let Some(err) = &self.error {
+            return Err(err.clone());
+        }
+        let mut data = String::new();
+        for value in &self.values {
+            match value {
+                CursorValue::Null => data.push('n'),
+                CursorValue::Bool(true) => data.push('t'),
+                CursorValue::Bool(false) => data.push('f'),
+                CursorValue::Int(value) => data.push_str(&format!("i{};", value)),
+                CursorValue::Float(value) => data.push_str(&format!("d{};", value)),
+                CursorValue::Text(value) => data.push_str(&format!("s{}:{}", value.len(), value)),
+            }
+        }
+        Ok(base64_encode(data.as_bytes()))
+    }
+
+    /// Decode cursor from token.
+    ///
+    /// ```
+    /// use sql_builder::{cursor::Cursor, SqlBuilderError};
+    ///
+    /// let cursor = Cursor::new().push("O'Brien").push(None::<i32>).push(1.5).clone();
+    ///
+    /// assert_eq!(Ok(cursor.clone()), Cursor::decode(&cursor.encode()?));
+    /// assert_eq!(Err(SqlBuilderError::InvalidCursor), Cursor::decode("' OR 1 = 1 --"));
+    /// assert_eq!(Err(SqlBuilderError::InvalidCursor), Cursor::new().push(f64::NAN).encode());
+    /// # Ok::<(), SqlBuilderError>(())
+    /// ```
+    pub fn decode(token: &str) -> Result<Self, SqlBuilderError> {
+        let data = base64_decode(token).ok_or(SqlBuilderError::InvalidCursor)?;
+        let data = String::from_utf8(data).map_err(|_| SqlBuilderError::InvalidCursor)?;
+
+        let mut values = Vec::new();
+        let mut rest = data.as_str();
+        while let Some(tag) = rest.chars().next() {
+            rest = &rest[tag.len_utf8()..];
+            let value = match tag {
+                'n' => CursorValue::Null,
+                't' => CursorValue::Bool(true),
+                'f' => CursorValue::Bool(false),
+                'i' | 'd' => {
+                    let (number, tail) =
+                        rest.split_once(';').ok_or(SqlBuilderError::InvalidCursor)?;
+                    rest = tail;
+                    if tag == 'i' {
+                        CursorValue::Int(
+                            number.parse().map_err(|_| SqlBuilderError::InvalidCursor)?,
+                        )
+                    } else {
+                        match number.parse::<f64>() {
+                            Ok(number) if number.is_finite() => CursorValue::Float(number),
+                            _ => return Err(SqlBuilderError::InvalidCursor),
+                        }
+                    }
+                }
+                's' => {
+                    let (len, tail) = rest.split_once(':').ok_or(SqlBuilderError::InvalidCursor)?;
+                    let len: usize = len.parse().map_err(|_| SqlBuilderError::InvalidCursor)?;
+                    let text = tail.get(..len).ok_or(SqlBuilderError::InvalidCursor)?;
+                    rest = &tail[len..];
+                    CursorValue::Text(text.to_string())
+                }
+                _ => return Err(SqlBuilderError::InvalidCursor),
+            };
+            values.push(value);
+        }
+        Ok(Self {
+            values,
+            error: None,
+        })
+    }
+}
+
+impl SqlArgs for Cursor {
+    fn sql_args(&self) -> Vec<String> {
+        self.values.iter().map(|value| value.sql_arg()).collect()
+    }
+}
+
+impl SqlArgs for &Cursor {
+    fn sql_args(&self) -> Vec<String> {
+        (*self).sql_args()
+    }
+}
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Encode bytes into URL-safe base64 without padding
+fn base64_encode(data: &[u8]) -> String {
+    let mut res = String::with_capacity(data.len() * 4 / 3 + 3);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..=chunk.len() {
+            res.push(BASE64[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+    res
+}
+
+/// Decode URL-safe base64 without padding
+fn base64_decode(text: &str) -> Option<Vec<u8>> {
+    let mut res = Vec::with_capacity(text.len() * 3 / 4);
+    for chunk in text.as_bytes().chunks(4) {
+        if chunk.len() == 1 {
+            return None;
+        }
+        let mut n = 0u32;
+        for (i, c) in chunk.iter().enumerate() {
+            let v = BASE64.iter().position(|b| b == c)? as u32;
+            n |= v << (18 - 6 * i);
+        }
+        for i in 0..chunk.len() - 1 {
+            res.push((n >> (16 - 8 * i)) as u8);
+        }
+    }
+    Some(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::page::{Direction, SortKey};
+    use crate::SqlBuilder;
+    use anyhow::Result;
+
+    #[test]
+    fn test_base64() {
+        for text in ["", "f", "fo", "foo", "foob", "fooba", "foobar"] {
+            let encoded = base64_encode(text.as_bytes());
+            assert_eq!(base64_decode(&encoded), Some(text.as_bytes().to_vec()));
+        }
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64_decode("Zm9vYmE"), Some(b"fooba".to_vec()));
+        assert_eq!(base64_decode("Zm9=vYmE"), None);
+    }
+
+    #[test]
+    fn test_cursor() -> Result<()> {
+        let cursor = Cursor::new()
+            .push(true)
+            .push(-7)
+            .push("a;b:c")
+            .push("")
+            .push(None::<&str>)
+            .clone();
+        assert_eq!(Cursor::decode(&cursor.encode()?), Ok(cursor.clone()));
+        assert_eq!(
+            cursor.sql_args(),
+            vec!["TRUE", "-7", "'a;b:c'", "''", "NULL"]
+        );
+
+        assert_eq!(
+            Cursor::decode(&base64_encode(b"s10:abc")),
+            Err(SqlBuilderError::InvalidCursor)
+        );
+        assert_eq!(
+            Cursor::decode(&base64_encode(b"dinf;")),
+            Err(SqlBuilderError::InvalidCursor)
+        );
+        assert_eq!(Cursor::decode("w6k"), Err(SqlBuilderError::InvalidCursor));
+        assert_eq!(
+            Cursor::decode(&base64_encode("s1:é".as_bytes())),
+            Err(SqlBuilderError::InvalidCursor)
+        );
+        let cursor = Cursor::new().push("Émile Zola").clone();
+        assert_eq!(Cursor::decode(&cursor.encode()?), Ok(cursor));
+        assert_eq!(
+            Cursor::new().push(f32::INFINITY).push(1).encode(),
+            Err(SqlBuilderError::InvalidCursor)
+        );
+        assert_eq!(
+            Cursor::new().push(CursorValue::Float(f64::NAN)).encode(),
+            Err(SqlBuilderError::InvalidCursor)
+        );
+        assert_eq!(
+            CursorValue::try_from(Some(f64::NEG_INFINITY)),
+            Err(SqlBuilderError::InvalidCursor)
+        );
+
+        let sort = [SortKey::asc("author").nulls_last(), SortKey::desc("id")];
+        let sql = SqlBuilder::select_from("books")
+            .seek(
+                &sort,
+                &*Cursor::new().push(None::<&str>).push(42),
+                Direction::After,
+            )
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT * FROM books WHERE author IS NULL AND id < 42 ORDER BY author NULLS LAST, id DESC;"
+        );
+
+        let sql = SqlBuilder::select_from("books")
+            .seek(
+                &sort,
+                &*Cursor::new().push("Le Guin").push(42),
+                Direction::Before,
+            )
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT * FROM books WHERE (author < 'Le Guin') OR (author = 'Le Guin' AND id > 42) ORDER BY author DESC NULLS FIRST, id;"
+        );
+
+        let res = SqlBuilder::select_from("books")
+            .seek(
+                &sort,
+                &*Cursor::new().push("Le Guin").push(None::<i32>),
+                Direction::After,
+            )
+            .sql();
+        assert_eq!(
+            &res.unwrap_err().to_string(),
+            "Sort value for field \"id\" is NULL"
+        );
+
+        let res = SqlBuilder::select_from("books")
+            .seek(&sort, ("Le Guin", f64::NAN), Direction::After)
+            .sql();
+        assert_eq!(&res.unwrap_err().to_string(), "Cursor is invalid");
+
+        Ok(())
+    }
+}
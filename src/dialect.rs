@@ -0,0 +1,33 @@
+/// Target SQL dialect
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Dialect {
+    /// Portable SQL without dialect-specific features
+    #[default]
+    Generic,
+    Postgres,
+    MySql,
+    Sqlite,
+    MsSql,
+}
+
+impl Dialect {
+    /// Check if row values like `(a, b) > (1, 2)` are supported
+    pub fn row_values(&self) -> bool {
+        matches!(self, Dialect::Postgres | Dialect::MySql | Dialect::Sqlite)
+    }
+
+    /// Check if `NULLS FIRST` and `NULLS LAST` are supported in ORDER BY
+    pub fn nulls_order(&self) -> bool {
+        matches!(self, Dialect::Generic | Dialect::Postgres | Dialect::Sqlite)
+    }
+
+    /// Check if `UPDATE ... FROM (VALUES ...) AS v(...)` is supported
+    pub fn update_from_values(&self) -> bool {
+        matches!(self, Dialect::Postgres)
+    }
+
+    /// Check if array values and operators like `@>` are supported
+    pub fn arrays(&self) -> bool {
+        matches!(self, Dialect::Generic | Dialect::Postgres)
+    }
+}
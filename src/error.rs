@@ -1,3 +1,4 @@
+use std::convert::Infallible;
 use thiserror::Error;
 
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
@@ -18,4 +19,44 @@ pub enum SqlBuilderError {
     NoWhereList(String),
     #[error("WHERE query for field \"{0}\" not defined")]
     NoWhereQuery(String),
+    #[error("Tenant is not set for table \"{0}\"")]
+    NoTenant(String),
+    #[error("Tenant field \"{0}\" not defined")]
+    NoTenantField(String),
+    #[error("Filter is invalid: {0}")]
+    InvalidFilter(String),
+    #[error("Filter field \"{0}\" is not allowed")]
+    UnknownFilterField(String),
+    #[error("Filter operator \"{0}\" is not supported")]
+    UnknownFilterOperator(String),
+    #[error("Filter value for \"{0}\" is invalid")]
+    InvalidFilterValue(String),
+    #[error("Sort field \"{0}\" is not allowed")]
+    UnknownSortField(String),
+    #[error("Sort field \"{0}\" is repeated")]
+    DuplicateSortField(String),
+    #[error("Page parameter \"{0}\" is invalid")]
+    InvalidPageParam(String),
+    #[error("Sort value for field \"{0}\" is NULL")]
+    NullSortValue(String),
+    #[error("Cursor is invalid")]
+    InvalidCursor,
+    #[error("Query is invalid: {0}")]
+    InvalidQuery(String),
+    #[error("Comparison operator \"{0}\" is not supported")]
+    UnknownOperator(String),
+    #[error("Relation to table \"{0}\" not defined")]
+    NoRelation(String),
+    #[error("Relation to table \"{0}\" is ambiguous")]
+    AmbiguousRelation(String),
+    #[error("Function \"{0}\" is not supported by dialect")]
+    UnsupportedFunction(String),
+    #[error("Row has {1} values instead of {0}")]
+    InvalidRowLength(usize, usize),
+}
+
+impl From<Infallible> for SqlBuilderError {
+    fn from(err: Infallible) -> Self {
+        match err {}
+    }
 }
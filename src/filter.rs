@@ -0,0 +1,329 @@
+//! Translation of JSON filter documents into WHERE conditions.
+//!
+//! Only fields from the allow-list are accepted, so the document never
+//! injects raw input into the query.
+//!
+//! ```
+//! # use anyhow::Result;
+//! use serde_json::json;
+//! use sql_builder::{filter::JsonFilter, SqlBuilder};
+//!
+//! # fn main() -> Result<()> {
+//! let filter = JsonFilter::new()
+//!     .field("price")
+//!     .field_as("title", "b.title")
+//!     .clone();
+//!
+//! let sql = SqlBuilder::select_from("books AS b")
+//!     .field("b.title")
+//!     .and_where_filter(&filter, &json!({
+//!         "price": {"$gt": 100},
+//!         "$or": [{"title": {"$like": "Harry%"}}, {"title": "Dune"}],
+//!     }))
+//!     .sql()?;
+//!
+//! assert_eq!("SELECT b.title FROM books AS b WHERE ((b.title LIKE 'Harry%') OR (b.title = 'Dune')) AND (price > 100);", &sql);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::SqlBuilderError;
+use crate::{quote, SqlBuilder};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Allow-list of filter fields with their columns
+#[derive(Clone, Default)]
+pub struct JsonFilter {
+    fields: HashMap<String, String>,
+}
+
+impl JsonFilter {
+    /// Create empty allow-list
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow field with the same column name
+    pub fn field<S: ToString>(&mut self, name: S) -> &mut Self {
+        let name = name.to_string();
+        self.fields.insert(name.clone(), name);
+        self
+    }
+
+    /// Allow field mapped to the column
+    pub fn field_as<S, C>(&mut self, name: S, column: C) -> &mut Self
+    where
+        S: ToString,
+        C: ToString,
+    {
+        self.fields.insert(name.to_string(), column.to_string());
+        self
+    }
+
+    /// Parse filter document into WHERE conditions joined with AND.
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use sql_builder::{filter::JsonFilter, SqlBuilderError};
+    ///
+    /// let filter = JsonFilter::new().field("price").clone();
+    ///
+    /// let conds = filter.parse(&json!({"price": {"$between": [10, 20]}}));
+    /// assert_eq!(Ok(vec!["price BETWEEN 10 AND 20".to_string()]), conds);
+    ///
+    /// let conds = filter.parse(&json!({"password": "secret"}));
+    /// assert_eq!(Err(SqlBuilderError::UnknownFilterField("password".to_string())), conds);
+    /// ```
+    pub fn parse(&self, doc: &Value) -> Result<Vec<String>, SqlBuilderError> {
+        match doc {
+            Value::Object(doc) => self.doc_conds(doc),
+            _ => Err(SqlBuilderError::InvalidFilter(
+                "document is not an object".to_string(),
+            )),
+        }
+    }
+
+    /// Parse filter document from JSON text.
+    pub fn parse_str(&self, doc: &str) -> Result<Vec<String>, SqlBuilderError> {
+        let doc: Value =
+            serde_json::from_str(doc).map_err(|e| SqlBuilderError::InvalidFilter(e.to_string()))?;
+        self.parse(&doc)
+    }
+
+    /// Make conditions of document
+    fn doc_conds(&self, doc: &Map<String, Value>) -> Result<Vec<String>, SqlBuilderError> {
+        let mut conds = Vec::new();
+        for (key, value) in doc {
+            match key.as_str() {
+                "$and" => conds.push(self.logic_cond(key, value, " AND ")?),
+                "$or" => conds.push(self.logic_cond(key, value, " OR ")?),
+                "$not" => match value {
+                    Value::Object(doc) if !doc.is_empty() => {
+                        conds.push(format!("NOT ({})", and_all(self.doc_conds(doc)?)))
+                    }
+                    _ => return Err(SqlBuilderError::InvalidFilterValue(key.clone())),
+                },
+                op if op.starts_with('$') => {
+                    return Err(SqlBuilderError::UnknownFilterOperator(key.clone()))
+                }
+                _ => {
+                    let column = self
+                        .fields
+                        .get(key)
+                        .ok_or_else(|| SqlBuilderError::UnknownFilterField(key.clone()))?;
+                    conds.append(&mut field_conds(key, column, value)?);
+                }
+            }
+        }
+        Ok(conds)
+    }
+
+    /// Make condition of $and or $or list
+    fn logic_cond(&self, key: &str, value: &Value, sep: &str) -> Result<String, SqlBuilderError> {
+        let docs = match value {
+            Value::Array(docs) if !docs.is_empty() => docs,
+            _ => return Err(SqlBuilderError::InvalidFilterValue(key.to_string())),
+        };
+        let mut conds = Vec::with_capacity(docs.len());
+        for doc in docs {
+            match doc {
+                Value::Object(doc) if !doc.is_empty() => conds.push(and_all(self.doc_conds(doc)?)),
+                _ => return Err(SqlBuilderError::InvalidFilterValue(key.to_string())),
+            }
+        }
+        Ok(join_all(conds, sep))
+    }
+}
+
+/// Make conditions of field with WHERE helpers of builder
+fn field_conds(field: &str, column: &str, value: &Value) -> Result<Vec<String>, SqlBuilderError> {
+    let ops: Vec<(&str, &Value)> = match value {
+        Value::Object(ops) => ops.iter().map(|(op, v)| (op.as_str(), v)).collect(),
+        value => vec![("$eq", value)],
+    };
+    if ops.is_empty() {
+        return Err(SqlBuilderError::InvalidFilterValue(field.to_string()));
+    }
+
+    let mut builder = SqlBuilder::select_from("");
+    for (op, value) in ops {
+        match (op, value) {
+            ("$eq", Value::Null) | ("$null", Value::Bool(true)) => {
+                builder.and_where_is_null(column)
+            }
+            ("$ne", Value::Null) | ("$null", Value::Bool(false)) => {
+                builder.and_where_is_not_null(column)
+            }
+            ("$eq", value) => builder.and_where_eq(column, scalar(field, value)?),
+            ("$ne", value) => builder.and_where_ne(column, scalar(field, value)?),
+            ("$gt", value) => builder.and_where_gt(column, scalar(field, value)?),
+            ("$gte", value) => builder.and_where_ge(column, scalar(field, value)?),
+            ("$lt", value) => builder.and_where_lt(column, scalar(field, value)?),
+            ("$lte", value) => builder.and_where_le(column, scalar(field, value)?),
+            ("$like", Value::String(mask)) => builder.and_where_like(column, mask),
+            ("$nlike", Value::String(mask)) => builder.and_where_not_like(column, mask),
+            ("$in", value) => builder.and_where_in(column, &list(field, value)?),
+            ("$nin", value) => builder.and_where_not_in(column, &list(field, value)?),
+            ("$between", Value::Array(range)) if range.len() == 2 => builder.and_where_between(
+                column,
+                scalar(field, &range[0])?,
+                scalar(field, &range[1])?,
+            ),
+            ("$like" | "$nlike" | "$between" | "$null", _) => {
+                return Err(SqlBuilderError::InvalidFilterValue(field.to_string()))
+            }
+            _ => return Err(SqlBuilderError::UnknownFilterOperator(op.to_string())),
+        };
+    }
+    match builder.error {
+        Some(err) => Err(err),
+        None => Ok(builder.wheres),
+    }
+}
+
+/// Render scalar value
+fn scalar(field: &str, value: &Value) -> Result<String, SqlBuilderError> {
+    match value {
+        Value::String(s) => Ok(quote(s)),
+        Value::Number(n) => Ok(n.to_string()),
+        Value::Bool(true) => Ok("TRUE".to_string()),
+        Value::Bool(false) => Ok("FALSE".to_string()),
+        _ => Err(SqlBuilderError::InvalidFilterValue(field.to_string())),
+    }
+}
+
+/// Render list of scalar values
+fn list(field: &str, value: &Value) -> Result<Vec<String>, SqlBuilderError> {
+    match value {
+        Value::Array(items) if !items.is_empty() => {
+            items.iter().map(|item| scalar(field, item)).collect()
+        }
+        _ => Err(SqlBuilderError::InvalidFilterValue(field.to_string())),
+    }
+}
+
+/// Join conditions with AND
+fn and_all(conds: Vec<String>) -> String {
+    join_all(conds, " AND ")
+}
+
+/// Join conditions with separator
+fn join_all(conds: Vec<String>, sep: &str) -> String {
+    if conds.len() == 1 {
+        conds.into_iter().next().unwrap_or_default()
+    } else {
+        conds
+            .iter()
+            .map(|c| format!("({})", c))
+            .collect::<Vec<String>>()
+            .join(sep)
+    }
+}
+
+impl SqlBuilder {
+    /// Add WHERE conditions parsed from JSON filter document.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use serde_json::json;
+    /// use sql_builder::{filter::JsonFilter, SqlBuilder};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let filter = JsonFilter::new().field("price").field("title").clone();
+    ///
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .and_where_filter(&filter, &json!({"price": {"$gte": 100, "$lt": 200}, "title": null}))
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title FROM books WHERE (price >= 100) AND (price < 200) AND (title IS NULL);", &sql);
+    ///
+    /// let res = SqlBuilder::select_from("books")
+    ///     .and_where_filter(&filter, &json!({"price": {"$regex": ".*"}}))
+    ///     .sql();
+    ///
+    /// assert_eq!("Filter operator \"$regex\" is not supported", &res.unwrap_err().to_string());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_where_filter(&mut self, filter: &JsonFilter, doc: &Value) -> &mut Self {
+        match filter.parse(doc) {
+            Ok(conds) => {
+                for cond in conds {
+                    self.and_where(cond);
+                }
+                self
+            }
+            Err(err) => self.set_error(&err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_filter() {
+        let filter = JsonFilter::new()
+            .field("price")
+            .field_as("author", "a.name")
+            .clone();
+
+        assert_eq!(
+            filter.parse(&json!({
+                "$not": {"author": {"$in": ["Tolkien", "O'Brien"]}},
+                "price": {"$null": false, "$ne": 0},
+            })),
+            Ok(vec![
+                "NOT (a.name IN ('Tolkien', 'O''Brien'))".to_string(),
+                "price <> 0".to_string(),
+                "price IS NOT NULL".to_string(),
+            ])
+        );
+
+        assert_eq!(
+            filter.parse(&json!({"price": {"$in": []}})),
+            Err(SqlBuilderError::InvalidFilterValue("price".to_string()))
+        );
+        assert_eq!(
+            filter.parse(&json!({"price": {"$gt": [1]}})),
+            Err(SqlBuilderError::InvalidFilterValue("price".to_string()))
+        );
+        assert_eq!(
+            filter.parse(&json!({"$or": []})),
+            Err(SqlBuilderError::InvalidFilterValue("$or".to_string()))
+        );
+        assert_eq!(
+            filter.parse(&json!({"$and": [{}]})),
+            Err(SqlBuilderError::InvalidFilterValue("$and".to_string()))
+        );
+        assert_eq!(
+            filter.parse(&json!({"$not": {}})),
+            Err(SqlBuilderError::InvalidFilterValue("$not".to_string()))
+        );
+        assert_eq!(
+            filter.parse(&json!({"author": {"$like": "O'B%", "$eq": true}})),
+            Ok(vec![
+                "a.name = TRUE".to_string(),
+                "a.name LIKE 'O''B%'".to_string(),
+            ])
+        );
+        assert_eq!(
+            filter.parse(&json!({"$where": "1 = 1"})),
+            Err(SqlBuilderError::UnknownFilterOperator("$where".to_string()))
+        );
+        assert_eq!(
+            filter.parse(&json!({"price; DROP TABLE books": 1})),
+            Err(SqlBuilderError::UnknownFilterField(
+                "price; DROP TABLE books".to_string()
+            ))
+        );
+        assert!(matches!(
+            filter.parse_str("{"),
+            Err(SqlBuilderError::InvalidFilter(_))
+        ));
+    }
+}
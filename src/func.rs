@@ -0,0 +1,427 @@
+//! Aggregate and scalar SQL functions.
+//!
+//! Functions render as expressions usable in fields, ORDER BY, HAVING
+//! and SET parts. Functions differing between databases are rendered
+//! for the dialect, `FILTER (WHERE ...)` of aggregates is emulated with
+//! `CASE` where it is unsupported. Builder methods like
+//! [`field_func`](SqlBuilder::field_func) use the dialect of the builder
+//! unless the function has its own one, [`SqlFunc::sql`] renders
+//! the function for other parts.
+//!
+//! ```
+//! # use anyhow::Result;
+//! use sql_builder::{func::{count, string_agg, sum}, Dialect, SqlBuilder};
+//!
+//! # fn main() -> Result<()> {
+//! let sql = SqlBuilder::select_from("books")
+//!     .field("author")
+//!     .field_func(sum("price").alias("total"))
+//!     .field_func(count("*").filter("price > 100").alias("expensive"))
+//!     .field_func(string_agg("title", ", ").alias("titles"))
+//!     .group_by("author")
+//!     .having(format!("{} > 1", count("*").sql(Dialect::Generic)?))
+//!     .order_by_func(sum("price").desc())
+//!     .sql()?;
+//!
+//! assert_eq!("SELECT author, SUM(price) AS total, COUNT(*) FILTER (WHERE price > 100) AS expensive, STRING_AGG(title, ', ') AS titles FROM books GROUP BY author HAVING COUNT(*) > 1 ORDER BY SUM(price) DESC;", &sql);
+//!
+//! let sql = SqlBuilder::select_from("books")
+//!     .dialect(Dialect::MySql)
+//!     .field_func(count("*").filter("price > 100"))
+//!     .field_func(string_agg("title", ", "))
+//!     .sql()?;
+//!
+//! assert_eq!("SELECT COUNT(CASE WHEN price > 100 THEN 1 END), GROUP_CONCAT(title SEPARATOR ', ') FROM books;", &sql);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::dialect::Dialect;
+use crate::error::SqlBuilderError;
+use crate::quote;
+use crate::SqlBuilder;
+
+/// Kind of function
+#[derive(Clone, Debug, PartialEq)]
+enum Kind {
+    Aggregate(&'static str),
+    CountDistinct,
+    StringAgg(String),
+    ArrayAgg,
+    Scalar(&'static str),
+    Greatest,
+    Least,
+}
+
+/// SQL function call
+#[derive(Clone, Debug, PartialEq)]
+pub struct SqlFunc {
+    kind: Kind,
+    args: Vec<String>,
+    filter: Option<String>,
+    dialect: Option<Dialect>,
+}
+
+impl SqlFunc {
+    fn new(kind: Kind, args: Vec<String>) -> Self {
+        Self {
+            kind,
+            args,
+            filter: None,
+            dialect: None,
+        }
+    }
+
+    /// Aggregate only rows matching the condition.
+    /// Condition is ignored by scalar functions.
+    pub fn filter<S: ToString>(mut self, cond: S) -> Self {
+        self.filter = Some(cond.to_string());
+        self
+    }
+
+    /// Add alias for field.
+    /// Function with alias is usable in fields only.
+    pub fn alias<S: ToString>(self, alias: S) -> FuncTerm {
+        FuncTerm {
+            func: self,
+            suffix: format!(" AS {}", alias.to_string()),
+        }
+    }
+
+    /// Use descending order for ORDER BY.
+    /// Function with order is usable in ORDER BY only.
+    pub fn desc(self) -> FuncTerm {
+        FuncTerm {
+            func: self,
+            suffix: " DESC".to_string(),
+        }
+    }
+
+    /// Render for the target SQL dialect instead of the dialect of builder.
+    pub fn dialect(mut self, dialect: Dialect) -> Self {
+        self.dialect = Some(dialect);
+        self
+    }
+
+    /// Make argument of aggregate, filter is emulated with CASE if needed
+    fn aggregated(&self, native_filter: bool) -> String {
+        let arg = self.args.join(", ");
+        match &self.filter {
+            Some(cond) if !native_filter => {
+                let value = if arg == "*" { "1" } else { arg.as_str() };
+                format!("CASE WHEN {} THEN {} END", cond, value)
+            }
+            _ => arg,
+        }
+    }
+
+    /// Render function call for the dialect unless the function has its own.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{func::{array_agg, greatest}, Dialect};
+    ///
+    /// # fn main() -> Result<()> {
+    /// assert_eq!("MAX(a, b)", &greatest(["a", "b"]).sql(Dialect::Sqlite)?);
+    /// assert_eq!("GREATEST(a, b)", &greatest(["a", "b"]).sql(Dialect::MySql)?);
+    ///
+    /// let res = array_agg("id").sql(Dialect::MsSql);
+    /// assert_eq!("Function \"ARRAY_AGG\" is not supported by dialect", &res.unwrap_err().to_string());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn sql(&self, dialect: Dialect) -> Result<String, SqlBuilderError> {
+        let dialect = self.dialect.unwrap_or(dialect);
+        let native_filter = matches!(
+            dialect,
+            Dialect::Generic | Dialect::Postgres | Dialect::Sqlite
+        );
+        let call = match &self.kind {
+            Kind::Aggregate(name) => format!("{}({})", name, self.aggregated(native_filter)),
+            Kind::CountDistinct => format!("COUNT(DISTINCT {})", self.aggregated(native_filter)),
+            Kind::StringAgg(sep) => match dialect {
+                Dialect::MySql => {
+                    format!(
+                        "GROUP_CONCAT({} SEPARATOR {})",
+                        self.aggregated(native_filter),
+                        sep
+                    )
+                }
+                Dialect::Sqlite => {
+                    format!("GROUP_CONCAT({}, {})", self.aggregated(native_filter), sep)
+                }
+                _ => format!("STRING_AGG({}, {})", self.aggregated(native_filter), sep),
+            },
+            Kind::ArrayAgg => match dialect {
+                Dialect::MySql => format!("JSON_ARRAYAGG({})", self.aggregated(native_filter)),
+                Dialect::Sqlite => format!("JSON_GROUP_ARRAY({})", self.aggregated(native_filter)),
+                Dialect::MsSql => {
+                    return Err(SqlBuilderError::UnsupportedFunction(
+                        "ARRAY_AGG".to_string(),
+                    ))
+                }
+                _ => format!("ARRAY_AGG({})", self.aggregated(native_filter)),
+            },
+            Kind::Scalar(name) => return Ok(format!("{}({})", name, self.args.join(", "))),
+            Kind::Greatest => match dialect {
+                Dialect::Sqlite => return Ok(format!("MAX({})", self.args.join(", "))),
+                _ => return Ok(format!("GREATEST({})", self.args.join(", "))),
+            },
+            Kind::Least => match dialect {
+                Dialect::Sqlite => return Ok(format!("MIN({})", self.args.join(", "))),
+                _ => return Ok(format!("LEAST({})", self.args.join(", "))),
+            },
+        };
+        match &self.filter {
+            Some(cond) if native_filter => Ok(format!("{} FILTER (WHERE {})", call, cond)),
+            _ => Ok(call),
+        }
+    }
+}
+
+/// SQL function with alias for field or with order for ORDER BY
+#[derive(Clone, Debug, PartialEq)]
+pub struct FuncTerm {
+    func: SqlFunc,
+    suffix: String,
+}
+
+impl FuncTerm {
+    /// Render term for the dialect unless the function has its own
+    pub fn sql(&self, dialect: Dialect) -> Result<String, SqlBuilderError> {
+        Ok(format!("{}{}", self.func.sql(dialect)?, self.suffix))
+    }
+}
+
+impl From<SqlFunc> for FuncTerm {
+    fn from(func: SqlFunc) -> Self {
+        Self {
+            func,
+            suffix: String::new(),
+        }
+    }
+}
+
+impl SqlBuilder {
+    /// Add function field rendered for the dialect of builder.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{func::{greatest, max}, Dialect, SqlBuilder};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .dialect(Dialect::Sqlite)
+    ///     .field_func(greatest(["price", "discount"]).alias("top"))
+    ///     .order_by_func(max("price").desc())
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT MAX(price, discount) AS top FROM books ORDER BY MAX(price) DESC;", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn field_func<F: Into<FuncTerm>>(&mut self, func: F) -> &mut Self {
+        match func.into().sql(self.dialect) {
+            Ok(field) => self.field(field),
+            Err(err) => self.set_error(&err),
+        }
+    }
+
+    /// Add ORDER BY function rendered for the dialect of builder.
+    pub fn order_by_func<F: Into<FuncTerm>>(&mut self, func: F) -> &mut Self {
+        match func.into().sql(self.dialect) {
+            Ok(order) => self.order_by(order),
+            Err(err) => self.set_error(&err),
+        }
+    }
+
+    /// Add SET part with function rendered for the dialect of builder.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{func::{coalesce, nullif}, SqlBuilder};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::update_table("books")
+    ///     .set_func("price", coalesce(["discount_price", "price", "0"]))
+    ///     .set_func("isbn", nullif("isbn", "''"))
+    ///     .sql()?;
+    ///
+    /// assert_eq!("UPDATE books SET price = COALESCE(discount_price, price, 0), isbn = NULLIF(isbn, '');", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_func<S: ToString>(&mut self, field: S, func: SqlFunc) -> &mut Self {
+        match func.sql(self.dialect) {
+            Ok(value) => self.set(field, value),
+            Err(err) => self.set_error(&err),
+        }
+    }
+}
+
+/// Make list of arguments
+fn arg_list<S, I>(args: I) -> Vec<String>
+where
+    S: ToString,
+    I: IntoIterator<Item = S>,
+{
+    args.into_iter().map(|a| a.to_string()).collect()
+}
+
+/// COUNT(expr)
+pub fn count<S: ToString>(expr: S) -> SqlFunc {
+    SqlFunc::new(Kind::Aggregate("COUNT"), vec![expr.to_string()])
+}
+
+/// COUNT(DISTINCT expr)
+pub fn count_distinct<S: ToString>(expr: S) -> SqlFunc {
+    SqlFunc::new(Kind::CountDistinct, vec![expr.to_string()])
+}
+
+/// SUM(expr)
+pub fn sum<S: ToString>(expr: S) -> SqlFunc {
+    SqlFunc::new(Kind::Aggregate("SUM"), vec![expr.to_string()])
+}
+
+/// AVG(expr)
+pub fn avg<S: ToString>(expr: S) -> SqlFunc {
+    SqlFunc::new(Kind::Aggregate("AVG"), vec![expr.to_string()])
+}
+
+/// MIN(expr)
+pub fn min<S: ToString>(expr: S) -> SqlFunc {
+    SqlFunc::new(Kind::Aggregate("MIN"), vec![expr.to_string()])
+}
+
+/// MAX(expr)
+pub fn max<S: ToString>(expr: S) -> SqlFunc {
+    SqlFunc::new(Kind::Aggregate("MAX"), vec![expr.to_string()])
+}
+
+/// Concatenation of values with the separator:
+/// `STRING_AGG` or `GROUP_CONCAT` depending on dialect.
+pub fn string_agg<S: ToString, T: ToString>(expr: S, sep: T) -> SqlFunc {
+    SqlFunc::new(Kind::StringAgg(quote(sep)), vec![expr.to_string()])
+}
+
+/// Concatenation of values with the separator, the same as [`string_agg`].
+pub fn group_concat<S: ToString, T: ToString>(expr: S, sep: T) -> SqlFunc {
+    string_agg(expr, sep)
+}
+
+/// Array of values: `ARRAY_AGG`, JSON array in MySQL and SQLite.
+/// MS SQL has no array aggregate. Emulated FILTER keeps NULLs for filtered out rows in MySQL.
+pub fn array_agg<S: ToString>(expr: S) -> SqlFunc {
+    SqlFunc::new(Kind::ArrayAgg, vec![expr.to_string()])
+}
+
+/// COALESCE(args)
+pub fn coalesce<S, I>(args: I) -> SqlFunc
+where
+    S: ToString,
+    I: IntoIterator<Item = S>,
+{
+    SqlFunc::new(Kind::Scalar("COALESCE"), arg_list(args))
+}
+
+/// NULLIF(expr, value)
+pub fn nullif<S: ToString, T: ToString>(expr: S, value: T) -> SqlFunc {
+    SqlFunc::new(
+        Kind::Scalar("NULLIF"),
+        vec![expr.to_string(), value.to_string()],
+    )
+}
+
+/// Greatest of values: `GREATEST` or `MAX` in SQLite.
+pub fn greatest<S, I>(args: I) -> SqlFunc
+where
+    S: ToString,
+    I: IntoIterator<Item = S>,
+{
+    SqlFunc::new(Kind::Greatest, arg_list(args))
+}
+
+/// Least of values: `LEAST` or `MIN` in SQLite.
+pub fn least<S, I>(args: I) -> SqlFunc
+where
+    S: ToString,
+    I: IntoIterator<Item = S>,
+{
+    SqlFunc::new(Kind::Least, arg_list(args))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    #[test]
+    fn test_func_dialects() -> Result<()> {
+        let total = sum("price").filter("year > 2000");
+        assert_eq!(
+            total.clone().sql(Dialect::Postgres)?,
+            "SUM(price) FILTER (WHERE year > 2000)"
+        );
+        assert_eq!(
+            total.alias("total").sql(Dialect::MsSql)?,
+            "SUM(CASE WHEN year > 2000 THEN price END) AS total"
+        );
+        assert_eq!(
+            count_distinct("author")
+                .filter("price > 0")
+                .sql(Dialect::MySql)?,
+            "COUNT(DISTINCT CASE WHEN price > 0 THEN author END)"
+        );
+        assert_eq!(
+            group_concat("title", "|").sql(Dialect::Sqlite)?,
+            "GROUP_CONCAT(title, '|')"
+        );
+        assert_eq!(
+            array_agg("id").sql(Dialect::Sqlite)?,
+            "JSON_GROUP_ARRAY(id)"
+        );
+        assert_eq!(
+            array_agg("id").filter("x").sql(Dialect::Postgres)?,
+            "ARRAY_AGG(id) FILTER (WHERE x)"
+        );
+        assert_eq!(
+            greatest(["a", "b"])
+                .dialect(Dialect::Sqlite)
+                .sql(Dialect::MySql)?,
+            "MAX(a, b)"
+        );
+        assert_eq!(
+            least(["a", "b"]).filter("ignored").sql(Dialect::Generic)?,
+            "LEAST(a, b)"
+        );
+        assert_eq!(avg("price").sql(Dialect::MySql)?, "AVG(price)");
+        assert_eq!(
+            min("price").desc().sql(Dialect::Generic)?,
+            "MIN(price) DESC"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_builder_dialect() -> Result<()> {
+        let sql = SqlBuilder::update_table("books")
+            .dialect(Dialect::MsSql)
+            .set_func("total", sum("price").filter("year > 2000"))
+            .set_func("top", greatest(["a", "b"]).dialect(Dialect::Sqlite))
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "UPDATE books SET total = SUM(CASE WHEN year > 2000 THEN price END), top = MAX(a, b);"
+        );
+
+        let res = SqlBuilder::select_from("books")
+            .dialect(Dialect::MsSql)
+            .field_func(array_agg("id").alias("ids"))
+            .sql();
+        assert_eq!(
+            &res.unwrap_err().to_string(),
+            "Function \"ARRAY_AGG\" is not supported by dialect"
+        );
+        Ok(())
+    }
+}
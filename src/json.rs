@@ -0,0 +1,667 @@
+//! JSON path expressions and conditions.
+//!
+//! Paths render as Postgres operators `->`, `->>`, `#>` and `#>>`,
+//! as `JSON_EXTRACT` and `JSON_UNQUOTE` in MySQL, `json_extract` in SQLite
+//! and `JSON_QUERY` and `JSON_VALUE` in SQL Server.
+//! Builder methods like [`field_json`](SqlBuilder::field_json) and
+//! [`and_where_json_eq`](SqlBuilder::and_where_json_eq) use the dialect
+//! of the builder unless the path has its own one. `Display` and `SqlArg`
+//! of a path know no builder, they render Postgres operators unless
+//! the path has its own dialect.
+//! Key existence is checked with the `?` operator in Postgres,
+//! `Bind` does not take it for a placeholder.
+//!
+//! ```
+//! # use anyhow::Result;
+//! use sql_builder::{json::json, Dialect, SqlBuilder};
+//!
+//! # fn main() -> Result<()> {
+//! let color = json("attrs").key("color").text();
+//! let sql = SqlBuilder::select_from("items")
+//!     .field_json(color.clone().alias("color"))
+//!     .and_where_json_eq(color.clone(), "red")
+//!     .and_where_json_contains("attrs", r#"{"size": "XL"}"#)
+//!     .order_by_json(json("attrs").key("sizes").index(0).text())
+//!     .sql()?;
+//!
+//! assert_eq!(r#"SELECT attrs ->> 'color' AS color FROM items WHERE (attrs ->> 'color' = 'red') AND (attrs @> '{"size": "XL"}') ORDER BY attrs #>> '{sizes,0}';"#, &sql);
+//!
+//! let sql = SqlBuilder::select_from("items")
+//!     .dialect(Dialect::MySql)
+//!     .field_json(color.clone())
+//!     .and_where_json_eq(color, "red")
+//!     .and_where_json_has_key("attrs", "color")
+//!     .sql()?;
+//!
+//! assert_eq!("SELECT JSON_UNQUOTE(JSON_EXTRACT(attrs, '$.color')) FROM items WHERE (JSON_UNQUOTE(JSON_EXTRACT(attrs, '$.color')) = 'red') AND (JSON_CONTAINS_PATH(attrs, 'one', '$.color'));", &sql);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::arg::SqlArg;
+use crate::dialect::Dialect;
+use crate::error::SqlBuilderError;
+use crate::{quote, SqlBuilder};
+use std::fmt;
+
+/// Step of JSON path
+#[derive(Clone, Debug, PartialEq)]
+enum Step {
+    Key(String),
+    Index(i64),
+}
+
+/// JSON value at path inside of column
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsonPath {
+    column: String,
+    steps: Vec<Step>,
+    text: bool,
+    alias: Option<String>,
+    dialect: Option<Dialect>,
+}
+
+/// Start JSON path in the column.
+pub fn json<S: ToString>(column: S) -> JsonPath {
+    JsonPath {
+        column: column.to_string(),
+        steps: Vec::new(),
+        text: false,
+        alias: None,
+        dialect: None,
+    }
+}
+
+impl JsonPath {
+    /// Go to object key.
+    pub fn key<S: ToString>(mut self, key: S) -> Self {
+        self.steps.push(Step::Key(key.to_string()));
+        self
+    }
+
+    /// Go to array element.
+    pub fn index(mut self, index: i64) -> Self {
+        self.steps.push(Step::Index(index));
+        self
+    }
+
+    /// Extract value as text instead of JSON.
+    pub fn text(mut self) -> Self {
+        self.text = true;
+        self
+    }
+
+    /// Add alias for field.
+    pub fn alias<S: ToString>(mut self, alias: S) -> Self {
+        self.alias = Some(alias.to_string());
+        self
+    }
+
+    /// Render for the target SQL dialect instead of the dialect of builder.
+    pub fn dialect(mut self, dialect: Dialect) -> Self {
+        self.dialect = Some(dialect);
+        self
+    }
+
+    /// Make Postgres path like `{a,0}`
+    fn pg_path(&self) -> String {
+        let steps = self
+            .steps
+            .iter()
+            .map(|step| match step {
+                Step::Key(key)
+                    if key.is_empty() || key.contains([',', '{', '}', '"', '\\', ' ']) =>
+                {
+                    format!("\"{}\"", key.replace('\\', "\\\\").replace('"', "\\\""))
+                }
+                Step::Key(key) => key.clone(),
+                Step::Index(index) => index.to_string(),
+            })
+            .collect::<Vec<String>>();
+        format!("{{{}}}", steps.join(","))
+    }
+
+    /// Make SQL/JSON path like `$.a[0]`
+    fn js_path(&self) -> String {
+        let mut path = String::from("$");
+        for step in &self.steps {
+            match step {
+                Step::Key(key) if is_ident(key) => {
+                    path.push('.');
+                    path.push_str(key);
+                }
+                Step::Key(key) => {
+                    path.push_str(".\"");
+                    path.push_str(&key.replace('\\', "\\\\").replace('"', "\\\""));
+                    path.push('"');
+                }
+                Step::Index(index) => path.push_str(&format!("[{}]", index)),
+            }
+        }
+        path
+    }
+
+    /// Make expression for the dialect unless the path has its own
+    fn render(&self, dialect: Dialect) -> String {
+        match &self.alias {
+            Some(alias) => format!("{} AS {}", self.expr(dialect), alias),
+            None => self.expr(dialect),
+        }
+    }
+
+    /// Make expression without alias
+    fn expr(&self, dialect: Dialect) -> String {
+        match self.dialect.unwrap_or(dialect) {
+            Dialect::MySql => {
+                let extract = format!("JSON_EXTRACT({}, {})", self.column, quote(self.js_path()));
+                if self.text {
+                    format!("JSON_UNQUOTE({})", extract)
+                } else {
+                    extract
+                }
+            }
+            Dialect::Sqlite => format!("json_extract({}, {})", self.column, quote(self.js_path())),
+            Dialect::MsSql => {
+                let func = if self.text {
+                    "JSON_VALUE"
+                } else {
+                    "JSON_QUERY"
+                };
+                format!("{}({}, {})", func, self.column, quote(self.js_path()))
+            }
+            Dialect::Generic | Dialect::Postgres => match self.steps.as_slice() {
+                [Step::Key(key)] => {
+                    format!("{} {} {}", self.column, self.pg_op(), quote(key))
+                }
+                [Step::Index(index)] => format!("{} {} {}", self.column, self.pg_op(), index),
+                _ => {
+                    let op = if self.text { "#>>" } else { "#>" };
+                    format!("{} {} {}", self.column, op, quote(self.pg_path()))
+                }
+            },
+        }
+    }
+
+    /// Postgres operator of one step
+    fn pg_op(&self) -> &'static str {
+        if self.text {
+            "->>"
+        } else {
+            "->"
+        }
+    }
+}
+
+/// Check if JSON key may be used in path without quotes
+fn is_ident(key: &str) -> bool {
+    let mut chars = key.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+impl fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render(Dialect::default()))
+    }
+}
+
+impl SqlArg for JsonPath {
+    fn sql_arg(&self) -> String {
+        self.expr(Dialect::default())
+    }
+}
+
+impl SqlArg for &JsonPath {
+    fn sql_arg(&self) -> String {
+        self.expr(Dialect::default())
+    }
+}
+
+/// Make SQL Server value of JSON text, `JSON_QUERY` returns NULL for scalars
+fn mssql_value(value: &str) -> String {
+    let trimmed = value.trim();
+    match trimmed {
+        "true" => "CAST(1 AS BIT)".to_string(),
+        "false" => "CAST(0 AS BIT)".to_string(),
+        "null" => "NULL".to_string(),
+        _ if trimmed.starts_with('{') || trimmed.starts_with('[') => {
+            format!("JSON_QUERY({})", quote(value))
+        }
+        _ => match trimmed
+            .strip_prefix('"')
+            .and_then(|s| s.strip_suffix('"'))
+            .and_then(unescape)
+        {
+            Some(text) => quote(text),
+            None if trimmed.parse::<f64>().is_ok() => trimmed.to_string(),
+            None => format!("JSON_QUERY({})", quote(value)),
+        },
+    }
+}
+
+/// Decode escapes of JSON string without quotes
+fn unescape(text: &str) -> Option<String> {
+    let mut res = String::new();
+    let mut units = Vec::new();
+    let mut chars = text.chars();
+    while let Some(ch) = chars.next() {
+        let escape = if ch == '\\' {
+            Some(chars.next()?)
+        } else {
+            None
+        };
+        if escape != Some('u') && !units.is_empty() {
+            return None;
+        }
+        let escape = match escape {
+            Some(escape) => escape,
+            None => {
+                res.push(ch);
+                continue;
+            }
+        };
+        let ch = match escape {
+            'u' => {
+                let hex: String = chars.by_ref().take(4).collect();
+                if hex.len() != 4 {
+                    return None;
+                }
+                units.push(u16::from_str_radix(&hex, 16).ok()?);
+                if (0xD800..0xDC00).contains(units.last()?) {
+                    continue;
+                }
+                let decoded = char::decode_utf16(units.drain(..)).collect::<Result<String, _>>();
+                res.push_str(&decoded.ok()?);
+                continue;
+            }
+            '"' => '"',
+            '\\' => '\\',
+            '/' => '/',
+            'b' => '\u{8}',
+            'f' => '\u{c}',
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            _ => return None,
+        };
+        res.push(ch);
+    }
+    if units.is_empty() {
+        Some(res)
+    } else {
+        None
+    }
+}
+
+impl SqlBuilder {
+    /// Add JSON path field rendered for the dialect of builder.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{json::json, Dialect, SqlBuilder};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("items")
+    ///     .dialect(Dialect::Sqlite)
+    ///     .field_json(json("attrs").key("color").alias("color"))
+    ///     .order_by_json(json("attrs").key("size"))
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT json_extract(attrs, '$.color') AS color FROM items ORDER BY json_extract(attrs, '$.size');", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn field_json(&mut self, path: JsonPath) -> &mut Self {
+        let field = path.render(self.dialect);
+        self.field(field)
+    }
+
+    /// Add ORDER BY JSON path rendered for the dialect of builder.
+    pub fn order_by_json(&mut self, path: JsonPath) -> &mut Self {
+        let order = path.expr(self.dialect);
+        self.order_by(order)
+    }
+
+    /// Add WHERE JSON path value = value, path is rendered for the dialect of builder.
+    pub fn and_where_json_eq<T: SqlArg>(&mut self, path: JsonPath, value: T) -> &mut Self {
+        self.and_where_json_cmp(path, "=", value)
+    }
+
+    /// Add WHERE JSON path value <> value, path is rendered for the dialect of builder.
+    pub fn and_where_json_ne<T: SqlArg>(&mut self, path: JsonPath, value: T) -> &mut Self {
+        self.and_where_json_cmp(path, "<>", value)
+    }
+
+    /// Add WHERE comparison of JSON path value, path is rendered for the dialect of builder.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{json::json, Dialect, SqlBuilder};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("items")
+    ///     .dialect(Dialect::MsSql)
+    ///     .and_where_json_cmp(json("attrs").key("size").text(), ">=", 40)
+    ///     .or_where_json_eq(json("attrs").key("size").text(), "XL")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM items WHERE JSON_VALUE(attrs, '$.size') >= 40 OR JSON_VALUE(attrs, '$.size') = 'XL';", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_where_json_cmp<T: SqlArg>(
+        &mut self,
+        path: JsonPath,
+        op: &str,
+        value: T,
+    ) -> &mut Self {
+        match self.json_cmp(&path, op, value) {
+            Some(cond) => self.and_where(cond),
+            None => self,
+        }
+    }
+
+    /// Add OR JSON path value = value to the last WHERE condition.
+    pub fn or_where_json_eq<T: SqlArg>(&mut self, path: JsonPath, value: T) -> &mut Self {
+        match self.json_cmp(&path, "=", value) {
+            Some(cond) => self.or_where(cond),
+            None => self,
+        }
+    }
+
+    /// Make comparison of JSON path value for the dialect of builder
+    fn json_cmp<T: SqlArg>(&mut self, path: &JsonPath, op: &str, value: T) -> Option<String> {
+        if path.column.is_empty() {
+            self.set_error(&SqlBuilderError::NoWhereField);
+            return None;
+        }
+        if !["=", "<>", "!=", "<", "<=", ">", ">="].contains(&op) {
+            self.set_error(&SqlBuilderError::UnknownOperator(op.to_string()));
+            return None;
+        }
+        Some(format!(
+            "{} {} {}",
+            path.expr(self.dialect),
+            op,
+            value.sql_arg()
+        ))
+    }
+
+    /// Add WHERE JSON column contains the JSON document:
+    /// `@>` in Postgres, `JSON_CONTAINS` in MySQL.
+    /// SQLite and SQL Server have no containment operator.
+    pub fn and_where_json_contains<S, T>(&mut self, column: S, value: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        // Checks
+        let column = column.to_string();
+        if column.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+
+        // Change
+        let value = quote(value);
+        match self.dialect {
+            Dialect::Generic | Dialect::Postgres => {
+                self.and_where(format!("{} @> {}", column, value))
+            }
+            Dialect::MySql => self.and_where(format!("JSON_CONTAINS({}, {})", column, value)),
+            Dialect::Sqlite | Dialect::MsSql => {
+                self.set_error(&SqlBuilderError::UnknownOperator("@>".to_string()))
+            }
+        }
+    }
+
+    /// Add WHERE JSON column has the top-level key.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{Dialect, SqlBuilder};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("items")
+    ///     .dialect(Dialect::Postgres)
+    ///     .and_where_json_has_key("attrs", "color")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM items WHERE attrs ? 'color';", &sql);
+    ///
+    /// let sql = SqlBuilder::select_from("items")
+    ///     .dialect(Dialect::Sqlite)
+    ///     .and_where_json_has_key("attrs", "color")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM items WHERE json_type(attrs, '$.color') IS NOT NULL;", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_where_json_has_key<S, K>(&mut self, column: S, key: K) -> &mut Self
+    where
+        S: ToString,
+        K: ToString,
+    {
+        // Checks
+        let column = column.to_string();
+        if column.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+
+        // Change
+        let key = key.to_string();
+        let path = quote(json("").key(&key).js_path());
+        let cond = match self.dialect {
+            Dialect::Generic | Dialect::Postgres => {
+                format!("{} ? {}", column, quote(key))
+            }
+            Dialect::MySql => format!("JSON_CONTAINS_PATH({}, 'one', {})", column, path),
+            Dialect::Sqlite => format!("json_type({}, {}) IS NOT NULL", column, path),
+            Dialect::MsSql => format!("JSON_PATH_EXISTS({}, {}) = 1", column, path),
+        };
+        self.and_where(cond)
+    }
+
+    /// Add WHERE JSON path query returns any item:
+    /// `jsonb_path_exists` in Postgres, `JSON_CONTAINS_PATH` in MySQL,
+    /// `json_type(...) IS NOT NULL` in SQLite and `JSON_PATH_EXISTS` in SQL Server.
+    /// Filter expressions like `$.a ? (@ > 1)` are supported by Postgres only.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{prelude::Bind, Dialect, SqlBuilder};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("items")
+    ///     .dialect(Dialect::Postgres)
+    ///     .and_where_json_path_exists("attrs", "$.sizes[*] ? (@ > 40)")
+    ///     .and_where("price < ?".bind(100))
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM items WHERE (jsonb_path_exists(attrs, '$.sizes[*] ? (@ > 40)')) AND (price < 100);", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_where_json_path_exists<S, P>(&mut self, column: S, path: P) -> &mut Self
+    where
+        S: ToString,
+        P: ToString,
+    {
+        // Checks
+        let column = column.to_string();
+        if column.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+
+        // Change
+        let path = quote(path);
+        let cond = match self.dialect {
+            Dialect::Generic | Dialect::Postgres => {
+                format!("jsonb_path_exists({}, {})", column, path)
+            }
+            Dialect::MySql => format!("JSON_CONTAINS_PATH({}, 'one', {})", column, path),
+            Dialect::Sqlite => format!("json_type({}, {}) IS NOT NULL", column, path),
+            Dialect::MsSql => format!("JSON_PATH_EXISTS({}, {}) = 1", column, path),
+        };
+        self.and_where(cond)
+    }
+
+    /// Set JSON value at the path of column for UPDATE:
+    /// `jsonb_set` in Postgres, `JSON_SET` in MySQL and SQLite,
+    /// `JSON_MODIFY` in SQL Server. Dialect of the builder is used
+    /// unless the path has its own one. Paths of the same column
+    /// are nested into one SET part.
+    /// SQL Server gets scalars as plain values and deletes the key for `null`.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{json::json, Dialect, SqlBuilder};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::update_table("items")
+    ///     .set_json(json("attrs").key("size"), r#""XL""#)
+    ///     .sql()?;
+    ///
+    /// assert_eq!(r#"UPDATE items SET attrs = jsonb_set(attrs, '{size}', '"XL"'::jsonb);"#, &sql);
+    ///
+    /// let sql = SqlBuilder::update_table("items")
+    ///     .dialect(Dialect::MySql)
+    ///     .set_json(json("attrs").key("sizes").index(0), "42")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("UPDATE items SET attrs = JSON_SET(attrs, '$.sizes[0]', CAST('42' AS JSON));", &sql);
+    ///
+    /// let sql = SqlBuilder::update_table("items")
+    ///     .dialect(Dialect::MsSql)
+    ///     .set_json(json("attrs").key("size"), r#""XL""#)
+    ///     .set_json(json("attrs").key("count"), "42")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("UPDATE items SET attrs = JSON_MODIFY(JSON_MODIFY(attrs, '$.size', 'XL'), '$.count', 42);", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_json<T: ToString>(&mut self, path: JsonPath, value: T) -> &mut Self {
+        // Previous SET part of the column is modified further
+        let prefix = format!("{} = ", path.column);
+        let pos = self.sets.iter().position(|set| set.starts_with(&prefix));
+        let column = match pos {
+            Some(i) => self.sets[i][prefix.len()..].to_string(),
+            None => path.column.clone(),
+        };
+        let value = value.to_string();
+        let expr = match path.dialect.unwrap_or(self.dialect) {
+            Dialect::Generic | Dialect::Postgres => format!(
+                "jsonb_set({}, {}, {}::jsonb)",
+                column,
+                quote(path.pg_path()),
+                quote(value)
+            ),
+            Dialect::MySql => format!(
+                "JSON_SET({}, {}, CAST({} AS JSON))",
+                column,
+                quote(path.js_path()),
+                quote(value)
+            ),
+            Dialect::Sqlite => format!(
+                "json_set({}, {}, json({}))",
+                column,
+                quote(path.js_path()),
+                quote(value)
+            ),
+            Dialect::MsSql => format!(
+                "JSON_MODIFY({}, {}, {})",
+                column,
+                quote(path.js_path()),
+                mssql_value(&value)
+            ),
+        };
+        match pos {
+            Some(i) => {
+                self.sets[i] = format!("{}{}", prefix, expr);
+                self
+            }
+            None => self.set(&path.column, expr),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    #[test]
+    fn test_json_path() -> Result<()> {
+        let path = json("doc").key("a b").index(2).key("c");
+        assert_eq!(path.clone().to_string(), "doc #> '{\"a b\",2,c}'");
+        assert_eq!(
+            path.clone().text().dialect(Dialect::MsSql).to_string(),
+            "JSON_VALUE(doc, '$.\"a b\"[2].c')"
+        );
+        assert_eq!(
+            path.dialect(Dialect::Sqlite).alias("c").to_string(),
+            "json_extract(doc, '$.\"a b\"[2].c') AS c"
+        );
+        assert_eq!(json("doc").index(-1).sql_arg(), "doc -> -1");
+        assert_eq!(json("doc").key("it's").text().sql_arg(), "doc ->> 'it''s'");
+
+        let res = SqlBuilder::select_from("items")
+            .dialect(Dialect::Sqlite)
+            .and_where_json_contains("attrs", "{}")
+            .sql();
+        assert_eq!(
+            &res.unwrap_err().to_string(),
+            "Comparison operator \"@>\" is not supported"
+        );
+
+        let sql = SqlBuilder::update_table("items")
+            .dialect(Dialect::MsSql)
+            .set_json(json("attrs").key("tags"), "[]")
+            .and_where_json_has_key("attrs", "tags")
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "UPDATE items SET attrs = JSON_MODIFY(attrs, '$.tags', JSON_QUERY('[]')) WHERE JSON_PATH_EXISTS(attrs, '$.tags') = 1;"
+        );
+
+        let sql = SqlBuilder::update_table("items")
+            .dialect(Dialect::MsSql)
+            .set_json(json("attrs").key("size"), r#""X\"L\u00e9""#)
+            .set_json(json("attrs").key("count"), "42")
+            .set_json(json("attrs").key("sale"), "true")
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "UPDATE items SET attrs = JSON_MODIFY(JSON_MODIFY(JSON_MODIFY(attrs, '$.size', 'X\"Lé'), '$.count', 42), '$.sale', CAST(1 AS BIT));"
+        );
+
+        let sql = SqlBuilder::update_table("items")
+            .dialect(Dialect::Sqlite)
+            .set_json(json("attrs").key("a").dialect(Dialect::MySql), "1")
+            .set_json(json("attrs").key("b"), "1")
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "UPDATE items SET attrs = json_set(JSON_SET(attrs, '$.a', CAST('1' AS JSON)), '$.b', json('1'));"
+        );
+        let res = SqlBuilder::select_from("items")
+            .and_where_json_cmp(json("attrs").key("a"), "LIKE", 1)
+            .sql();
+        assert_eq!(
+            &res.unwrap_err().to_string(),
+            "Comparison operator \"LIKE\" is not supported"
+        );
+
+        assert_eq!(
+            SqlBuilder::select_from("items")
+                .dialect(Dialect::Sqlite)
+                .field_json(json("attrs").key("a").dialect(Dialect::MySql))
+                .field_json(json("attrs").key("b"))
+                .sql()?,
+            "SELECT JSON_EXTRACT(attrs, '$.a'), json_extract(attrs, '$.b') FROM items;"
+        );
+
+        Ok(())
+    }
+}
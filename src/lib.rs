@@ -159,18 +159,45 @@
 //! See [more examples](https://docs.rs/sql-builder/4.0.0/sql_builder/struct.SqlBuilder.html)
 
 pub mod arg;
+pub mod array;
 pub mod bind;
+pub mod bulk;
+pub mod case;
+pub mod chunk;
+pub mod compound;
+pub mod cursor;
+pub mod dialect;
 pub mod error;
+#[cfg(feature = "serde_json")]
+pub mod filter;
+pub mod func;
 pub mod insert;
+pub mod json;
+pub mod like;
 pub mod name;
+pub mod null;
+pub mod page;
 pub mod prelude;
+pub mod query;
+pub mod relation;
+pub mod row;
+pub mod scope;
+pub mod search;
 pub mod select;
+pub mod tenant;
 pub mod update;
 
+use crate::arg::SqlArg;
+use crate::chunk::InList;
+use crate::compound::CompoundBuilder;
+pub use crate::dialect::Dialect;
 pub use crate::error::SqlBuilderError;
 pub use crate::name::SqlName;
+pub use crate::query::SqlQuery;
 //pub use crate::where::WhereBuilder;
 use anyhow::Result;
+use std::borrow::Cow;
+use std::fmt;
 
 pub trait Builder {
     fn build(self) -> Result<String>;
@@ -181,15 +208,17 @@ pub trait Builder {
 pub struct SqlBuilder {
     statement: Statement,
     table: String,
-    joins: Vec<String>,
+    joins: Vec<Join>,
     distinct: bool,
     fields: Vec<String>,
     sets: Vec<String>,
     values: Values,
     returning: Option<String>,
     group_by: Vec<String>,
-    having: Option<String>,
-    unions: String,
+    // with positions among GROUP BY fields
+    groupings: Vec<(usize, Grouping)>,
+    havings: Vec<String>,
+    unions: Vec<(&'static str, String)>,
     wheres: Vec<String>,
     order_by: Vec<String>,
     limit: Option<String>,
@@ -199,6 +228,17 @@ pub struct SqlBuilder {
     on_conflict_key: Option<String>,
     on_conflict_action: Option<OnConflictAction>,
     on_conflict_sets: Vec<String>,
+    // for optimistic locking in UPDATE and DELETE
+    version_lock: Option<VersionLock>,
+    // for default scopes and soft-delete
+    unscoped: bool,
+    unscopes: Vec<String>,
+    // for multi-tenant row scoping
+    tenant: Option<String>,
+    // target SQL dialect
+    dialect: Dialect,
+    // IN lists which may be split into chunks
+    in_lists: Vec<InList>,
 }
 
 /// SQL query statement
@@ -217,11 +257,129 @@ enum OnConflictAction {
     DoUpdate,
 }
 
+/// Optimistic locking by version column
+#[derive(Clone)]
+pub(crate) struct VersionLock {
+    pub(crate) column: String,
+    pub(crate) version: String,
+    pub(crate) returning: bool,
+}
+
+impl VersionLock {
+    /// Make version check for WHERE part
+    pub(crate) fn where_cond(&self) -> String {
+        format!("{} = {}", self.column, self.version)
+    }
+
+    /// Make version increment for SET part
+    pub(crate) fn set_expr(&self) -> String {
+        format!("{col} = {col} + 1", col = self.column)
+    }
+
+    /// Add version column to RETURNING part
+    pub(crate) fn returning(&self, returning: &Option<String>) -> Option<String> {
+        match (returning, self.returning) {
+            (Some(ret), true) => Some(format!("{}, {}", ret, self.column)),
+            (None, true) => Some(self.column.clone()),
+            (ret, false) => ret.clone(),
+        }
+    }
+}
+
+/// JOIN part with table and constraint.
+/// Filters are applied to the table in subquery before joining.
+#[derive(Clone)]
+pub(crate) struct Join {
+    pub(crate) kind: String,
+    pub(crate) table: String,
+    pub(crate) on: Vec<String>,
+    pub(crate) using: Option<String>,
+    pub(crate) filters: Vec<String>,
+}
+
+impl Join {
+    /// Make JOIN part, constraint given with table is split from it
+    pub(crate) fn new(kind: &str, table: &str) -> Self {
+        let mut join = Self {
+            kind: kind.to_string(),
+            table: table.to_string(),
+            on: Vec::new(),
+            using: None,
+            filters: Vec::new(),
+        };
+        if let Some(pos) = find_keyword(table, " ON ") {
+            join.table = table[..pos].to_string();
+            join.on.push(table[pos + " ON ".len()..].to_string());
+        } else if let Some(pos) = find_keyword(table, " USING ") {
+            let columns = table[pos + " USING ".len()..].trim();
+            let columns = columns
+                .strip_prefix('(')
+                .and_then(|c| c.strip_suffix(')'))
+                .unwrap_or(columns);
+            join.table = table[..pos].to_string();
+            join.using = Some(columns.to_string());
+        }
+        join
+    }
+
+    /// Get joined table name and alias, None for subquery
+    pub(crate) fn target(&self) -> Option<(String, Option<String>)> {
+        scope::table_names(&self.table).into_iter().next()
+    }
+
+    /// Add AND constraint
+    pub(crate) fn and_on(&mut self, constraint: String) {
+        self.on.push(constraint);
+    }
+
+    /// Add OR constraint to the last AND constraint
+    pub(crate) fn or_on(&mut self, constraint: String) {
+        match self.on.last_mut() {
+            Some(last) => {
+                last.push_str(" OR ");
+                last.push_str(&constraint);
+            }
+            None => self.on.push(constraint),
+        }
+    }
+}
+
+impl fmt::Display for Join {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.filters.is_empty() {
+            write!(f, "{} {}", self.kind, self.table)?;
+        } else {
+            let table = scope::filtered_table(&self.table, &self.filters);
+            write!(f, "{} {}", self.kind, table)?;
+        }
+        match self.on.len() {
+            0 => {}
+            1 => write!(f, " ON {}", self.on[0])?,
+            _ => {
+                let on: Vec<String> = self.on.iter().map(|c| format!("({})", c)).collect();
+                write!(f, " ON {}", on.join(" AND "))?;
+            }
+        }
+        if let Some(using) = &self.using {
+            write!(f, " USING ({})", using)?;
+        }
+        Ok(())
+    }
+}
+
+/// GROUP BY grouping of fields rendered for the dialect
+#[derive(Clone)]
+enum Grouping {
+    Rollup(String),
+    Cube(String),
+    Sets(String),
+}
+
 /// INSERT values
 #[derive(Clone)]
 enum Values {
     Empty,
-    List(Vec<String>),
+    List(Vec<Vec<String>>),
     Select(String),
 }
 
@@ -238,8 +396,9 @@ impl SqlBuilder {
             values: Values::Empty,
             returning: None,
             group_by: Vec::new(),
-            having: None,
-            unions: String::new(),
+            groupings: Vec::new(),
+            havings: Vec::new(),
+            unions: Vec::new(),
             wheres: Vec::new(),
             order_by: Vec::new(),
             limit: None,
@@ -248,11 +407,17 @@ impl SqlBuilder {
             on_conflict_key: None,
             on_conflict_action: None,
             on_conflict_sets: Vec::new(),
+            version_lock: None,
+            unscoped: false,
+            unscopes: Vec::new(),
+            tenant: None,
+            dialect: Dialect::Generic,
+            in_lists: Vec::new(),
         }
     }
 
     /// Create SELECT query.
-    /// You may specify comma separted list of tables.
+    /// You may specify comma separted list of tables or an aliased builder of subquery.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -266,17 +431,21 @@ impl SqlBuilder {
     ///     .and_where_like_left("title", "Harry Potter")
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE (price > 100) AND (title LIKE 'Harry Potter%');", &sql);
+    /// assert_eq!("SELECT title, price FROM books WHERE (price > 100) AND (title LIKE 'Harry Potter%' ESCAPE '\\');", &sql);
     /// // add                               ^^^^^
     /// // here                              table
     /// # Ok(())
     /// # }
     /// ```
-    pub fn select_from<S: ToString>(table: S) -> Self {
-        Self {
-            table: table.to_string(),
-            ..Self::default()
+    pub fn select_from<S: SqlQuery>(table: S) -> Self {
+        let mut builder = Self::default();
+        match table.sql_table() {
+            Ok(table) => builder.table = table,
+            Err(err) => {
+                builder.set_query_error(err);
+            }
         }
+        builder
     }
 
     /// SELECT from additional table.
@@ -412,6 +581,27 @@ impl SqlBuilder {
         }
     }
 
+    /// Set target SQL dialect.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{page::SortKey, Dialect, SqlBuilder};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .dialect(Dialect::MySql)
+    ///     .order_by_keys(&[SortKey::asc("price").nulls_last()])
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM books ORDER BY CASE WHEN price IS NULL THEN 1 ELSE 0 END, price;", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn dialect(&mut self, dialect: Dialect) -> &mut Self {
+        self.dialect = dialect;
+        self
+    }
+
     /// Use LEFT JOIN
     ///
     /// ```
@@ -430,12 +620,8 @@ impl SqlBuilder {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn left_join<S: ToString>(&mut self, table: S) -> &mut Self {
-        let mut text = String::from("LEFT JOIN ");
-        text.push_str(&table.to_string());
-
-        self.joins.push(text);
-        self
+    pub fn left_join<S: SqlQuery>(&mut self, table: S) -> &mut Self {
+        self.add_join("LEFT JOIN", table)
     }
 
     /// Use RIGHT JOIN
@@ -456,12 +642,8 @@ impl SqlBuilder {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn right_join<S: ToString>(&mut self, table: S) -> &mut Self {
-        let mut text = String::from("RIGHT JOIN ");
-        text.push_str(&table.to_string());
-
-        self.joins.push(text);
-        self
+    pub fn right_join<S: SqlQuery>(&mut self, table: S) -> &mut Self {
+        self.add_join("RIGHT JOIN", table)
     }
 
     /// Use INNER JOIN
@@ -482,12 +664,8 @@ impl SqlBuilder {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn inner_join<S: ToString>(&mut self, table: S) -> &mut Self {
-        let mut text = String::from("INNER JOIN ");
-        text.push_str(&table.to_string());
-
-        self.joins.push(text);
-        self
+    pub fn inner_join<S: SqlQuery>(&mut self, table: S) -> &mut Self {
+        self.add_join("INNER JOIN", table)
     }
 
     /// Use CROSS JOIN
@@ -508,12 +686,95 @@ impl SqlBuilder {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn cross_join<S: ToString>(&mut self, table: S) -> &mut Self {
-        let mut text = String::from("CROSS JOIN ");
-        text.push_str(&table.to_string());
+    pub fn cross_join<S: SqlQuery>(&mut self, table: S) -> &mut Self {
+        self.add_join("CROSS JOIN", table)
+    }
 
-        self.joins.push(text);
-        self
+    /// Use FULL OUTER JOIN
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .field("total")
+    ///     .full_join("orders")
+    ///     .on_eq("books.id", "orders.book_id")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title, total FROM books FULL OUTER JOIN orders ON books.id = orders.book_id;", &sql);
+    /// // add here                                ^^^^^^^^^^
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn full_join<S: SqlQuery>(&mut self, table: S) -> &mut Self {
+        self.add_join("FULL OUTER JOIN", table)
+    }
+
+    /// Use NATURAL JOIN
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .field("total")
+    ///     .natural_join("orders")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title, total FROM books NATURAL JOIN orders;", &sql);
+    /// // add here                                ^^^^^^^
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn natural_join<S: SqlQuery>(&mut self, table: S) -> &mut Self {
+        self.add_join("NATURAL JOIN", table)
+    }
+
+    /// Use JOIN LATERAL with subquery.
+    /// LATERAL is supported by Postgres and MySQL 8.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{SqlBuilder, SqlQuery};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let last_order = SqlBuilder::select_from("orders AS o")
+    ///     .field("o.total")
+    ///     .and_where("o.book_id = b.id")
+    ///     .order_desc("o.created_at")
+    ///     .limit(1)
+    ///     .clone();
+    ///
+    /// let sql = SqlBuilder::select_from("books AS b")
+    ///     .field("b.title")
+    ///     .field("lo.total")
+    ///     .join_lateral(last_order.aliased("lo"))
+    ///     .on("TRUE")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT b.title, lo.total FROM books AS b JOIN LATERAL (SELECT o.total FROM orders AS o WHERE o.book_id = b.id ORDER BY o.created_at DESC LIMIT 1) AS lo ON TRUE;", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn join_lateral<S: SqlQuery>(&mut self, query: S) -> &mut Self {
+        self.add_join("JOIN LATERAL", query)
+    }
+
+    /// Use LEFT JOIN LATERAL with subquery.
+    /// LATERAL is supported by Postgres and MySQL 8.
+    pub fn left_join_lateral<S: SqlQuery>(&mut self, query: S) -> &mut Self {
+        self.add_join("LEFT JOIN LATERAL", query)
+    }
+
+    /// Use CROSS JOIN LATERAL with subquery.
+    /// LATERAL is supported by Postgres and MySQL 8.
+    pub fn cross_join_lateral<S: SqlQuery>(&mut self, query: S) -> &mut Self {
+        self.add_join("CROSS JOIN LATERAL", query)
     }
 
     /// Join with table.
@@ -536,12 +797,8 @@ impl SqlBuilder {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn join<S: ToString>(&mut self, table: S) -> &mut Self {
-        let mut text = String::from("JOIN ");
-        text.push_str(&table.to_string());
-
-        self.joins.push(text);
-        self
+    pub fn join<S: SqlQuery>(&mut self, table: S) -> &mut Self {
+        self.add_join("JOIN", table)
     }
 
     /// Join constraint to the last JOIN part.
@@ -565,13 +822,104 @@ impl SqlBuilder {
     /// # }
     /// ```
     pub fn on<S: ToString>(&mut self, constraint: S) -> &mut Self {
+        self.and_on(constraint)
+    }
+
+    /// Add AND constraint to the last JOIN part.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books AS b")
+    ///     .field("b.title")
+    ///     .field("s.total")
+    ///     .left_join("shops AS s")
+    ///     .on_eq("b.id", "s.book")
+    ///     .and_on("s.total > 0")
+    ///     .or_on("s.preorder")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT b.title, s.total FROM books AS b LEFT JOIN shops AS s ON (b.id = s.book) AND (s.total > 0 OR s.preorder);", &sql);
+    /// // add                                                                                         ^^^^^^^^^^^^^^^^
+    /// // here                                                                                           constraint
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_on<S: ToString>(&mut self, constraint: S) -> &mut Self {
+        if let Some(last) = self.joins.last_mut() {
+            last.and_on(constraint.to_string());
+        }
+        self
+    }
+
+    /// Add OR constraint to the last JOIN part.
+    pub fn or_on<S: ToString>(&mut self, constraint: S) -> &mut Self {
+        if let Some(last) = self.joins.last_mut() {
+            last.or_on(constraint.to_string());
+        }
+        self
+    }
+
+    /// Add constraint of equal fields to the last JOIN part.
+    pub fn on_eq<S, T>(&mut self, field: S, other: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        self.and_on(format!("{} = {}", field.to_string(), other.to_string()))
+    }
+
+    /// Add USING constraint to the last JOIN part.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .field("total")
+    ///     .join("shops")
+    ///     .using(["book_id", "edition"])
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title, total FROM books JOIN shops USING (book_id, edition);", &sql);
+    /// // add                                                ^^^^^^^^^^^^^^^^^
+    /// // here                                                    columns
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn using<S, I>(&mut self, columns: I) -> &mut Self
+    where
+        S: ToString,
+        I: IntoIterator<Item = S>,
+    {
+        let columns = columns
+            .into_iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<String>>();
+        if columns.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
         if let Some(last) = self.joins.last_mut() {
-            last.push_str(" ON ");
-            last.push_str(&constraint.to_string());
+            last.using = Some(columns.join(", "));
         }
         self
     }
 
+    /// Add JOIN part of the kind with table or subquery
+    fn add_join<S: SqlQuery>(&mut self, kind: &str, table: S) -> &mut Self {
+        match table.sql_table_for(self.tenant.as_deref()) {
+            Ok(table) => {
+                self.joins.push(Join::new(kind, &table));
+                self
+            }
+            Err(err) => self.set_query_error(err),
+        }
+    }
+
     /// Set DISTINCT for fields.
     ///
     /// ```
@@ -783,7 +1131,6 @@ impl SqlBuilder {
             .iter()
             .map(|v| (*v).to_string())
             .collect::<Vec<String>>();
-        let values = format!("({})", values.join(", "));
 
         match &mut self.values {
             Values::Empty => self.values = Values::List(vec![values]),
@@ -820,9 +1167,14 @@ impl SqlBuilder {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn select<S: ToString>(&mut self, query: S) -> &mut Self {
-        self.values = Values::Select(query.to_string());
-        self
+    pub fn select<S: SqlQuery>(&mut self, query: S) -> &mut Self {
+        match query.sql_query_for(self.tenant.as_deref()) {
+            Ok(query) => {
+                self.values = Values::Select(query);
+                self
+            }
+            Err(err) => self.set_query_error(err),
+        }
     }
 
     /// Add RETURNING part.
@@ -873,6 +1225,112 @@ impl SqlBuilder {
         self.returning("id")
     }
 
+    /// Use optimistic locking by version column (for UPDATE and DELETE).
+    /// Checks the expected version in WHERE and increments it in SET.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::update_table("books")
+    ///     .set("price", "price + 10")
+    ///     .and_where_eq("id", 42)
+    ///     .optimistic_lock("version", 3)
+    ///     .sql()?;
+    ///
+    /// assert_eq!("UPDATE books SET price = price + 10, version = version + 1 WHERE (id = 42) AND (version = 3);", &sql);
+    /// // add                                           ^^^^^^^^^^^^^^^^^^^^^^^                ^^^^^^^^^^^^^^^
+    /// // here                                             version increment                    version check
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::delete_from("books")
+    ///     .and_where_eq("id", 42)
+    ///     .optimistic_lock("version", 3)
+    ///     .sql()?;
+    ///
+    /// assert_eq!("DELETE FROM books WHERE (id = 42) AND (version = 3);", &sql);
+    /// // add                                            ^^^^^^^^^^^^^
+    /// // here                                           version check
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn optimistic_lock<S, T>(&mut self, column: S, version: T) -> &mut Self
+    where
+        S: ToString,
+        T: SqlArg,
+    {
+        self.set_version_lock(column, version, false)
+    }
+
+    /// Use optimistic locking by version column and return the new version.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::update_table("books")
+    ///     .set("price", "price + 10")
+    ///     .and_where_eq("id", 42)
+    ///     .optimistic_lock_returning("version", 3)
+    ///     .returning_id()
+    ///     .sql()?;
+    ///
+    /// assert_eq!("UPDATE books SET price = price + 10, version = version + 1 WHERE (id = 42) AND (version = 3) RETURNING id, version;", &sql);
+    /// // add                                                                                                             ^^^^^^^
+    /// // here                                                                                                            column
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn optimistic_lock_returning<S, T>(&mut self, column: S, version: T) -> &mut Self
+    where
+        S: ToString,
+        T: SqlArg,
+    {
+        self.set_version_lock(column, version, true)
+    }
+
+    /// Set optimistic locking parameters
+    fn set_version_lock<S, T>(&mut self, column: S, version: T, returning: bool) -> &mut Self
+    where
+        S: ToString,
+        T: SqlArg,
+    {
+        // Checks
+        if !matches!(
+            self.statement,
+            Statement::UpdateTable | Statement::DeleteFrom
+        ) {
+            return self.set_error(&SqlBuilderError::InvalidQuery(
+                "optimistic lock needs UPDATE or DELETE".to_string(),
+            ));
+        }
+        let column = column.to_string();
+        if column.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+        let version = version.sql_arg();
+        if version.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereValue(column));
+        }
+
+        // Change
+        self.version_lock = Some(VersionLock {
+            column,
+            version,
+            returning,
+        });
+        self
+    }
+
     /// Add GROUP BY part.
     ///
     /// ```
@@ -898,7 +1356,8 @@ impl SqlBuilder {
         self
     }
 
-    /// Add HAVING condition.
+    /// Set HAVING condition replacing previous ones.
+    /// Use [`and_having`](Self::and_having) to add more conditions.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -920,11 +1379,12 @@ impl SqlBuilder {
     /// # }
     /// ```
     pub fn having<S: ToString>(&mut self, cond: S) -> &mut Self {
-        self.having = Some(cond.to_string());
-        self
+        self.havings.clear();
+        self.and_having(cond)
     }
 
-    /// Add WHERE condition.
+    /// Add HAVING condition joined with AND.
+    /// HAVING without GROUP BY treats all rows as one group.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -932,19 +1392,18 @@ impl SqlBuilder {
     ///
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
-    ///     .field("title")
-    ///     .field("price")
-    ///     .and_where("price > 100")
-    ///     .and_where("title LIKE 'Harry Potter%'")
+    ///     .field("COUNT(*)")
+    ///     .and_having("MAX(price) > 100")
+    ///     .and_having("MIN(price) > 10")
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE (price > 100) AND (title LIKE 'Harry Potter%');", &sql);
-    /// // add                                            ^^^^^^^^^^^       ^^^^^^^^^^^^^^^^^^^^^^^^^^
-    /// // here                                              cond                      cond
+    /// assert_eq!("SELECT COUNT(*) FROM books HAVING (MAX(price) > 100) AND (MIN(price) > 10);", &sql);
+    /// // add                                       ^^^^^^^^^^^^^^^^
+    /// // here                                            cond
     /// # Ok(())
     /// # }
     /// ```
-    pub fn and_where<S: ToString>(&mut self, cond: S) -> &mut Self {
+    pub fn and_having<S: ToString>(&mut self, cond: S) -> &mut Self {
         // Checks
         let cond = cond.to_string();
         if cond.is_empty() {
@@ -952,91 +1411,73 @@ impl SqlBuilder {
         }
 
         // Change
-        self.wheres.push(cond);
+        self.havings.push(cond);
         self
     }
 
-    /// Add WHERE condition for equal parts.
+    /// Add OR condition to the last HAVING condition.
     ///
     /// ```
     /// # use anyhow::Result;
-    /// use sql_builder::{SqlBuilder, quote};
+    /// use sql_builder::SqlBuilder;
     ///
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
-    ///     .field("price")
-    ///     .and_where_eq("title", &quote("Harry Potter and the Philosopher's Stone"))
+    ///     .field("author")
+    ///     .group_by("author")
+    ///     .and_having("COUNT(*) > 10")
+    ///     .or_having("SUM(price) > 1000")
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT price FROM books WHERE title = 'Harry Potter and the Philosopher''s Stone';", &sql);
-    /// // add                                    ^^^^^   ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
-    /// // here                                   field                      value
+    /// assert_eq!("SELECT author FROM books GROUP BY author HAVING COUNT(*) > 10 OR SUM(price) > 1000;", &sql);
+    /// // add                                                                        ^^^^^^^^^^^^^^^^^
+    /// // here                                                                              cond
     /// # Ok(())
     /// # }
     /// ```
-    pub fn and_where_eq<S, T>(&mut self, field: S, value: T) -> &mut Self
-    where
-        S: ToString,
-        T: ToString,
-    {
+    pub fn or_having<S: ToString>(&mut self, cond: S) -> &mut Self {
         // Checks
-        let field = field.to_string();
-        if field.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereField);
-        }
-        let value = value.to_string();
-        if value.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereValue(field));
+        let cond = cond.to_string();
+        if cond.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereCond);
         }
 
         // Change
-        let mut cond = field;
-        cond.push_str(" = ");
-        cond.push_str(&value);
-        self.and_where(&cond)
+        if let Some(last) = self.havings.last_mut() {
+            last.push_str(" OR ");
+            last.push_str(&cond);
+        } else {
+            self.havings.push(cond);
+        }
+        self
     }
 
-    /// Add WHERE condition for non-equal parts.
+    /// Add HAVING condition for equal parts.
     ///
     /// ```
     /// # use anyhow::Result;
-    /// use sql_builder::{SqlBuilder, quote};
+    /// use sql_builder::SqlBuilder;
     ///
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
-    ///     .field("price")
-    ///     .and_where_ne("title", &quote("Harry Potter and the Philosopher's Stone"))
+    ///     .field("author")
+    ///     .group_by("author")
+    ///     .and_having_eq("COUNT(*)", 10)
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT price FROM books WHERE title <> 'Harry Potter and the Philosopher''s Stone';", &sql);
-    /// // add                                    ^^^^^    ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
-    /// // here                                   field                       value
+    /// assert_eq!("SELECT author FROM books GROUP BY author HAVING COUNT(*) = 10;", &sql);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn and_where_ne<S, T>(&mut self, field: S, value: T) -> &mut Self
+    pub fn and_having_eq<S, T>(&mut self, field: S, value: T) -> &mut Self
     where
         S: ToString,
         T: ToString,
     {
-        // Checks
-        let field = field.to_string();
-        if field.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereField);
-        }
-        let value = value.to_string();
-        if value.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereValue(field));
-        }
-
-        // Change
-        let mut cond = field;
-        cond.push_str(" <> ");
-        cond.push_str(&value);
-        self.and_where(&cond)
+        self.having_cmp(field, " = ", value, false)
     }
 
-    /// Add WHERE condition for field greater than value.
+    /// Add HAVING condition for non-equal parts.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -1044,40 +1485,24 @@ impl SqlBuilder {
     ///
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
-    ///     .field("title")
-    ///     .field("price")
-    ///     .and_where_gt("price", 300)
+    ///     .field("author")
+    ///     .group_by("author")
+    ///     .and_having_ne("COUNT(*)", 10)
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE price > 300;", &sql);
-    /// // add                                           ^^^^^   ^^^
-    /// // here                                          field  value
+    /// assert_eq!("SELECT author FROM books GROUP BY author HAVING COUNT(*) <> 10;", &sql);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn and_where_gt<S, T>(&mut self, field: S, value: T) -> &mut Self
+    pub fn and_having_ne<S, T>(&mut self, field: S, value: T) -> &mut Self
     where
         S: ToString,
         T: ToString,
     {
-        // Checks
-        let field = field.to_string();
-        if field.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereField);
-        }
-        let value = value.to_string();
-        if value.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereValue(field));
-        }
-
-        // Change
-        let mut cond = field;
-        cond.push_str(" > ");
-        cond.push_str(&value);
-        self.and_where(&cond)
+        self.having_cmp(field, " <> ", value, false)
     }
 
-    /// Add WHERE condition for field not less than value.
+    /// Add HAVING condition for greater parts.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -1085,40 +1510,24 @@ impl SqlBuilder {
     ///
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
-    ///     .field("title")
-    ///     .field("price")
-    ///     .and_where_ge("price", 300)
+    ///     .field("author")
+    ///     .group_by("author")
+    ///     .and_having_gt("COUNT(*)", 10)
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE price >= 300;", &sql);
-    /// // add                                           ^^^^^    ^^^
-    /// // here                                          field   value
+    /// assert_eq!("SELECT author FROM books GROUP BY author HAVING COUNT(*) > 10;", &sql);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn and_where_ge<S, T>(&mut self, field: S, value: T) -> &mut Self
+    pub fn and_having_gt<S, T>(&mut self, field: S, value: T) -> &mut Self
     where
         S: ToString,
         T: ToString,
     {
-        // Checks
-        let field = field.to_string();
-        if field.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereField);
-        }
-        let value = value.to_string();
-        if value.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereValue(field));
-        }
-
-        // Change
-        let mut cond = field;
-        cond.push_str(" >= ");
-        cond.push_str(&value);
-        self.and_where(&cond)
+        self.having_cmp(field, " > ", value, false)
     }
 
-    /// Add WHERE condition for field less than value.
+    /// Add HAVING condition for greater or equal parts.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -1126,40 +1535,24 @@ impl SqlBuilder {
     ///
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
-    ///     .field("title")
-    ///     .field("price")
-    ///     .and_where_lt("price", 300)
+    ///     .field("author")
+    ///     .group_by("author")
+    ///     .and_having_ge("COUNT(*)", 10)
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE price < 300;", &sql);
-    /// // add                                           ^^^^^   ^^^
-    /// // here                                          field  value
+    /// assert_eq!("SELECT author FROM books GROUP BY author HAVING COUNT(*) >= 10;", &sql);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn and_where_lt<S, T>(&mut self, field: S, value: T) -> &mut Self
+    pub fn and_having_ge<S, T>(&mut self, field: S, value: T) -> &mut Self
     where
         S: ToString,
         T: ToString,
     {
-        // Checks
-        let field = field.to_string();
-        if field.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereField);
-        }
-        let value = value.to_string();
-        if value.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereValue(field));
-        }
-
-        // Change
-        let mut cond = field;
-        cond.push_str(" < ");
-        cond.push_str(&value);
-        self.and_where(&cond)
+        self.having_cmp(field, " >= ", value, false)
     }
 
-    /// Add WHERE condition for field not greater than value.
+    /// Add HAVING condition for less parts.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -1167,40 +1560,24 @@ impl SqlBuilder {
     ///
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
-    ///     .field("title")
-    ///     .field("price")
-    ///     .and_where_le("price", 300)
+    ///     .field("author")
+    ///     .group_by("author")
+    ///     .and_having_lt("COUNT(*)", 10)
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE price <= 300;", &sql);
-    /// // add                                           ^^^^^    ^^^
-    /// // here                                          field   value
+    /// assert_eq!("SELECT author FROM books GROUP BY author HAVING COUNT(*) < 10;", &sql);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn and_where_le<S, T>(&mut self, field: S, value: T) -> &mut Self
+    pub fn and_having_lt<S, T>(&mut self, field: S, value: T) -> &mut Self
     where
         S: ToString,
         T: ToString,
     {
-        // Checks
-        let field = field.to_string();
-        if field.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereField);
-        }
-        let value = value.to_string();
-        if value.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereValue(field));
-        }
-
-        // Change
-        let mut cond = field;
-        cond.push_str(" <= ");
-        cond.push_str(&value);
-        self.and_where(&cond)
+        self.having_cmp(field, " < ", value, false)
     }
 
-    /// Add WHERE LIKE condition.
+    /// Add HAVING condition for less or equal parts.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -1208,36 +1585,24 @@ impl SqlBuilder {
     ///
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
-    ///     .field("price")
-    ///     .and_where_like("title", "%Philosopher's%")
+    ///     .field("author")
+    ///     .group_by("author")
+    ///     .and_having_le("COUNT(*)", 10)
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT price FROM books WHERE title LIKE '%Philosopher''s%';", &sql);
-    /// // add                                    ^^^^^       ^^^^^^^^^^^^^^^^
-    /// // here                                   field             mask
+    /// assert_eq!("SELECT author FROM books GROUP BY author HAVING COUNT(*) <= 10;", &sql);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn and_where_like<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    pub fn and_having_le<S, T>(&mut self, field: S, value: T) -> &mut Self
     where
         S: ToString,
         T: ToString,
     {
-        // Checks
-        let field = field.to_string();
-        if field.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereField);
-        }
-
-        // Change
-        let mut cond = field;
-        cond.push_str(" LIKE '");
-        cond.push_str(&esc(&mask.to_string()));
-        cond.push('\'');
-        self.and_where(&cond)
+        self.having_cmp(field, " <= ", value, false)
     }
 
-    /// Add WHERE LIKE %condition.
+    /// Add OR condition of equal parts to the last HAVING condition.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -1245,36 +1610,25 @@ impl SqlBuilder {
     ///
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
-    ///     .field("price")
-    ///     .and_where_like_right("title", "Stone")
+    ///     .field("author")
+    ///     .group_by("author")
+    ///     .and_having("SUM(price) > 1000")
+    ///     .or_having_eq("COUNT(*)", 10)
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT price FROM books WHERE title LIKE '%Stone';", &sql);
-    /// // add                                    ^^^^^        ^^^^^
-    /// // here                                   field        mask
+    /// assert_eq!("SELECT author FROM books GROUP BY author HAVING SUM(price) > 1000 OR COUNT(*) = 10;", &sql);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn and_where_like_right<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    pub fn or_having_eq<S, T>(&mut self, field: S, value: T) -> &mut Self
     where
         S: ToString,
         T: ToString,
     {
-        // Checks
-        let field = field.to_string();
-        if field.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereField);
-        }
-
-        // Change
-        let mut cond = field;
-        cond.push_str(" LIKE '%");
-        cond.push_str(&esc(&mask.to_string()));
-        cond.push('\'');
-        self.and_where(&cond)
+        self.having_cmp(field, " = ", value, true)
     }
 
-    /// Add WHERE LIKE condition%.
+    /// Add OR condition of non-equal parts to the last HAVING condition.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -1282,36 +1636,25 @@ impl SqlBuilder {
     ///
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
-    ///     .field("price")
-    ///     .and_where_like_left("title", "Harry")
+    ///     .field("author")
+    ///     .group_by("author")
+    ///     .and_having("SUM(price) > 1000")
+    ///     .or_having_ne("COUNT(*)", 10)
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT price FROM books WHERE title LIKE 'Harry%';", &sql);
-    /// // add                                    ^^^^^       ^^^^^
-    /// // here                                   field       mask
+    /// assert_eq!("SELECT author FROM books GROUP BY author HAVING SUM(price) > 1000 OR COUNT(*) <> 10;", &sql);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn and_where_like_left<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    pub fn or_having_ne<S, T>(&mut self, field: S, value: T) -> &mut Self
     where
         S: ToString,
         T: ToString,
     {
-        // Checks
-        let field = field.to_string();
-        if field.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereField);
-        }
-
-        // Change
-        let mut cond = field;
-        cond.push_str(" LIKE '");
-        cond.push_str(&esc(&mask.to_string()));
-        cond.push_str("%'");
-        self.and_where(&cond)
+        self.having_cmp(field, " <> ", value, true)
     }
 
-    /// Add WHERE LIKE %condition%.
+    /// Add OR condition of greater parts to the last HAVING condition.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -1319,36 +1662,25 @@ impl SqlBuilder {
     ///
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
-    ///     .field("price")
-    ///     .and_where_like_any("title", " and ")
+    ///     .field("author")
+    ///     .group_by("author")
+    ///     .and_having("SUM(price) > 1000")
+    ///     .or_having_gt("COUNT(*)", 10)
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT price FROM books WHERE title LIKE '% and %';", &sql);
-    /// // add                                    ^^^^^        ^^^^^
-    /// // here                                   field        mask
+    /// assert_eq!("SELECT author FROM books GROUP BY author HAVING SUM(price) > 1000 OR COUNT(*) > 10;", &sql);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn and_where_like_any<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    pub fn or_having_gt<S, T>(&mut self, field: S, value: T) -> &mut Self
     where
         S: ToString,
         T: ToString,
     {
-        // Checks
-        let field = field.to_string();
-        if field.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereField);
-        }
-
-        // Change
-        let mut cond = field;
-        cond.push_str(" LIKE '%");
-        cond.push_str(&esc(&mask.to_string()));
-        cond.push_str("%'");
-        self.and_where(&cond)
+        self.having_cmp(field, " > ", value, true)
     }
 
-    /// Add WHERE NOT LIKE condition.
+    /// Add OR condition of greater or equal parts to the last HAVING condition.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -1356,40 +1688,25 @@ impl SqlBuilder {
     ///
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
-    ///     .field("title")
-    ///     .and_where_not_like("title", "%Alice's%")
+    ///     .field("author")
+    ///     .group_by("author")
+    ///     .and_having("SUM(price) > 1000")
+    ///     .or_having_ge("COUNT(*)", 10)
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title FROM books WHERE title NOT LIKE '%Alice''s%';", &sql);
-    /// // add                                    ^^^^^           ^^^^^^^^^^
-    /// // here                                   field              mask
+    /// assert_eq!("SELECT author FROM books GROUP BY author HAVING SUM(price) > 1000 OR COUNT(*) >= 10;", &sql);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn and_where_not_like<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    pub fn or_having_ge<S, T>(&mut self, field: S, value: T) -> &mut Self
     where
         S: ToString,
         T: ToString,
     {
-        // Checks
-        let field = field.to_string();
-        if field.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereField);
-        }
-        let mask = mask.to_string();
-        if mask.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereValue(field));
-        }
-
-        // Change
-        let mut cond = field;
-        cond.push_str(" NOT LIKE '");
-        cond.push_str(&esc(&mask));
-        cond.push('\'');
-        self.and_where(&cond)
+        self.having_cmp(field, " >= ", value, true)
     }
 
-    /// Add WHERE NOT LIKE %condition.
+    /// Add OR condition of less parts to the last HAVING condition.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -1397,40 +1714,25 @@ impl SqlBuilder {
     ///
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
-    ///     .field("price")
-    ///     .and_where_not_like_right("title", "Stone")
+    ///     .field("author")
+    ///     .group_by("author")
+    ///     .and_having("SUM(price) > 1000")
+    ///     .or_having_lt("COUNT(*)", 10)
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT price FROM books WHERE title NOT LIKE '%Stone';", &sql);
-    /// // add                                    ^^^^^            ^^^^^
-    /// // here                                   field            mask
+    /// assert_eq!("SELECT author FROM books GROUP BY author HAVING SUM(price) > 1000 OR COUNT(*) < 10;", &sql);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn and_where_not_like_right<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    pub fn or_having_lt<S, T>(&mut self, field: S, value: T) -> &mut Self
     where
         S: ToString,
         T: ToString,
     {
-        // Checks
-        let field = field.to_string();
-        if field.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereField);
-        }
-        let mask = mask.to_string();
-        if mask.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereValue(field));
-        }
+        self.having_cmp(field, " < ", value, true)
+    }
 
-        // Change
-        let mut cond = field;
-        cond.push_str(" NOT LIKE '%");
-        cond.push_str(&esc(&mask));
-        cond.push('\'');
-        self.and_where(&cond)
-    }
-
-    /// Add WHERE NOT LIKE condition%.
+    /// Add OR condition of less or equal parts to the last HAVING condition.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -1438,40 +1740,25 @@ impl SqlBuilder {
     ///
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
-    ///     .field("price")
-    ///     .and_where_not_like_left("title", "Harry")
+    ///     .field("author")
+    ///     .group_by("author")
+    ///     .and_having("SUM(price) > 1000")
+    ///     .or_having_le("COUNT(*)", 10)
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT price FROM books WHERE title NOT LIKE 'Harry%';", &sql);
-    /// // add                                    ^^^^^           ^^^^^
-    /// // here                                   field           mask
+    /// assert_eq!("SELECT author FROM books GROUP BY author HAVING SUM(price) > 1000 OR COUNT(*) <= 10;", &sql);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn and_where_not_like_left<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    pub fn or_having_le<S, T>(&mut self, field: S, value: T) -> &mut Self
     where
         S: ToString,
         T: ToString,
     {
-        // Checks
-        let field = field.to_string();
-        if field.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereField);
-        }
-        let mask = mask.to_string();
-        if mask.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereValue(field));
-        }
-
-        // Change
-        let mut cond = field;
-        cond.push_str(" NOT LIKE '");
-        cond.push_str(&esc(&mask));
-        cond.push_str("%'");
-        self.and_where(&cond)
+        self.having_cmp(field, " <= ", value, true)
     }
 
-    /// Add WHERE NOT LIKE %condition%.
+    /// Add HAVING condition for value in the list.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -1479,40 +1766,26 @@ impl SqlBuilder {
     ///
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
-    ///     .field("price")
-    ///     .and_where_not_like_any("title", " and ")
+    ///     .field("author")
+    ///     .group_by("author")
+    ///     .and_having_in("COUNT(*)", &[1, 2, 3])
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT price FROM books WHERE title NOT LIKE '% and %';", &sql);
-    /// // add                                    ^^^^^            ^^^^^
-    /// // here                                   field            mask
+    /// assert_eq!("SELECT author FROM books GROUP BY author HAVING COUNT(*) IN (1, 2, 3);", &sql);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn and_where_not_like_any<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    pub fn and_having_in<S, T>(&mut self, field: S, list: &[T]) -> &mut Self
     where
         S: ToString,
         T: ToString,
     {
-        // Checks
-        let field = field.to_string();
-        if field.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereField);
-        }
-        let mask = mask.to_string();
-        if mask.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereValue(field));
-        }
-
-        // Change
-        let mut cond = field;
-        cond.push_str(" NOT LIKE '%");
-        cond.push_str(&esc(&mask));
-        cond.push_str("%'");
-        self.and_where(&cond)
+        self.having_where(false, |b| {
+            b.and_where_in(field, list);
+        })
     }
 
-    /// Add WHERE IS NULL condition.
+    /// Add HAVING condition for value in the range.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -1520,30 +1793,27 @@ impl SqlBuilder {
     ///
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
-    ///     .field("title")
-    ///     .and_where_is_null("price")
+    ///     .field("author")
+    ///     .group_by("author")
+    ///     .and_having_between("SUM(price)", 100, 1000)
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title FROM books WHERE price IS NULL;", &sql);
-    /// // add                                    ^^^^^
-    /// // here                                   field
+    /// assert_eq!("SELECT author FROM books GROUP BY author HAVING SUM(price) BETWEEN 100 AND 1000;", &sql);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn and_where_is_null<S: ToString>(&mut self, field: S) -> &mut Self {
-        // Checks
-        let field = field.to_string();
-        if field.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereField);
-        }
-
-        // Change
-        let mut cond = field;
-        cond.push_str(" IS NULL");
-        self.and_where(&cond)
+    pub fn and_having_between<S, T, U>(&mut self, field: S, min: T, max: U) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+        U: ToString,
+    {
+        self.having_where(false, |b| {
+            b.and_where_between(field, min, max);
+        })
     }
 
-    /// Add WHERE IS NOT NULL condition.
+    /// Add HAVING condition for value matching the mask.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -1551,293 +1821,290 @@ impl SqlBuilder {
     ///
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
-    ///     .field("title")
-    ///     .and_where_is_not_null("price")
+    ///     .field("author")
+    ///     .group_by("author")
+    ///     .and_having_like("MAX(title)", "Harry%")
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title FROM books WHERE price IS NOT NULL;", &sql);
-    /// // add                                    ^^^^^
-    /// // here                                   field
+    /// assert_eq!("SELECT author FROM books GROUP BY author HAVING MAX(title) LIKE 'Harry%';", &sql);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn and_where_is_not_null<S: ToString>(&mut self, field: S) -> &mut Self {
+    pub fn and_having_like<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        self.having_where(false, |b| {
+            b.and_where_like(field, mask);
+        })
+    }
+
+    /// Add OR condition for value in the list to the last HAVING condition.
+    pub fn or_having_in<S, T>(&mut self, field: S, list: &[T]) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        self.having_where(true, |b| {
+            b.and_where_in(field, list);
+        })
+    }
+
+    /// Add OR condition for value in the range to the last HAVING condition.
+    pub fn or_having_between<S, T, U>(&mut self, field: S, min: T, max: U) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+        U: ToString,
+    {
+        self.having_where(true, |b| {
+            b.and_where_between(field, min, max);
+        })
+    }
+
+    /// Add OR condition for value matching the mask to the last HAVING condition.
+    pub fn or_having_like<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        self.having_where(true, |b| {
+            b.and_where_like(field, mask);
+        })
+    }
+
+    /// Add HAVING comparison of field with value
+    fn having_cmp<S, T>(&mut self, field: S, op: &str, value: T, or: bool) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
         // Checks
         let field = field.to_string();
         if field.is_empty() {
             return self.set_error(&SqlBuilderError::NoWhereField);
         }
+        let value = value.to_string();
+        if value.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereValue(field));
+        }
 
         // Change
-        let mut cond = field;
-        cond.push_str(" IS NOT NULL");
-        self.and_where(&cond)
+        let cond = format!("{}{}{}", field, op, value);
+        if or {
+            self.or_having(cond)
+        } else {
+            self.and_having(cond)
+        }
     }
 
-    /// Add WHERE field IN (list).
+    /// Add HAVING condition made by WHERE methods of another builder
+    fn having_where<F>(&mut self, or: bool, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut SqlBuilder),
+    {
+        let mut builder = SqlBuilder::select_from("");
+        f(&mut builder);
+        if let Some(err) = builder.error {
+            return self.set_error(&err);
+        }
+        let cond = builder.wheres.join(" AND ");
+        if or {
+            self.or_having(cond)
+        } else {
+            self.and_having(cond)
+        }
+    }
+
+    /// Add GROUP BY ROLLUP part.
+    /// MySQL dialect uses `WITH ROLLUP` modifier after all GROUP BY fields.
     ///
     /// ```
     /// # use anyhow::Result;
-    /// use sql_builder::{SqlBuilder, quote};
+    /// use sql_builder::{Dialect, SqlBuilder};
     ///
     /// # fn main() -> Result<()> {
-    /// let sql = SqlBuilder::select_from("books")
-    ///     .field("title")
-    ///     .field("price")
-    ///     .and_where_in("title", &[quote("G"), quote("L"), quote("t")])
+    /// let sql = SqlBuilder::select_from("sales")
+    ///     .fields(["year", "month", "SUM(total)"])
+    ///     .group_by_rollup(["year", "month"])
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE title IN ('G', 'L', 't');", &sql);
-    /// // add                                           ^^^^^     ^^^^^^^^^^^^^
-    /// // here                                          field         list
+    /// assert_eq!("SELECT year, month, SUM(total) FROM sales GROUP BY ROLLUP (year, month);", &sql);
+    ///
+    /// let sql = SqlBuilder::select_from("sales")
+    ///     .fields(["year", "month", "SUM(total)"])
+    ///     .group_by_rollup(["year", "month"])
+    ///     .dialect(Dialect::MySql)
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT year, month, SUM(total) FROM sales GROUP BY year, month WITH ROLLUP;", &sql);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn and_where_in<S, T>(&mut self, field: S, list: &[T]) -> &mut Self
+    pub fn group_by_rollup<S, I>(&mut self, fields: I) -> &mut Self
     where
         S: ToString,
-        T: ToString,
+        I: IntoIterator<Item = S>,
     {
-        // Checks
-        let field = field.to_string();
-        if field.is_empty() {
+        let fields = group_list(fields);
+        if fields.is_empty() {
             return self.set_error(&SqlBuilderError::NoWhereField);
         }
-        if list.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereList(field));
-        }
-
-        // Change
-        let list: Vec<String> = list
-            .iter()
-            .map(|v| (*v).to_string())
-            .collect::<Vec<String>>();
-        let list = list.join(", ");
-
-        let mut cond = field;
-        cond.push_str(" IN (");
-        cond.push_str(&list);
-        cond.push(')');
-        self.and_where(&cond)
+        self.groupings
+            .push((self.group_by.len(), Grouping::Rollup(fields)));
+        self
     }
 
-    /// Add WHERE field IN (string list).
+    /// Add GROUP BY CUBE part.
     ///
     /// ```
     /// # use anyhow::Result;
-    /// use sql_builder::{SqlBuilder, quote};
+    /// use sql_builder::SqlBuilder;
     ///
     /// # fn main() -> Result<()> {
-    /// let sql = SqlBuilder::select_from("books")
-    ///     .field("title")
-    ///     .field("price")
-    ///     .and_where_in_quoted("title", &["G", "L", "t"])
+    /// let sql = SqlBuilder::select_from("sales")
+    ///     .fields(["region", "product", "SUM(total)"])
+    ///     .group_by_cube(["region", "product"])
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE title IN ('G', 'L', 't');", &sql);
-    /// // add                                           ^^^^^     ^^^^^^^^^^^^^
-    /// // here                                          field         list
+    /// assert_eq!("SELECT region, product, SUM(total) FROM sales GROUP BY CUBE (region, product);", &sql);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn and_where_in_quoted<S, T>(&mut self, field: S, list: &[T]) -> &mut Self
+    pub fn group_by_cube<S, I>(&mut self, fields: I) -> &mut Self
     where
         S: ToString,
-        T: ToString,
+        I: IntoIterator<Item = S>,
     {
-        // Checks
-        let field = field.to_string();
-        if field.is_empty() {
+        let fields = group_list(fields);
+        if fields.is_empty() {
             return self.set_error(&SqlBuilderError::NoWhereField);
         }
-        if list.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereList(field));
-        }
-
-        // Change
-        let list: Vec<String> = list
-            .iter()
-            .map(|v| quote((*v).to_string()))
-            .collect::<Vec<String>>();
-        let list = list.join(", ");
-
-        let mut cond = field;
-        cond.push_str(" IN (");
-        cond.push_str(&list);
-        cond.push(')');
-        self.and_where(&cond)
+        self.groupings
+            .push((self.group_by.len(), Grouping::Cube(fields)));
+        self
     }
 
-    /// Add WHERE field NOT IN (list).
+    /// Add GROUP BY GROUPING SETS part.
+    /// Empty set makes the grand total.
     ///
     /// ```
     /// # use anyhow::Result;
-    /// use sql_builder::{SqlBuilder, quote};
+    /// use sql_builder::SqlBuilder;
     ///
     /// # fn main() -> Result<()> {
-    /// let sql = SqlBuilder::select_from("books")
-    ///     .field("title")
-    ///     .field("price")
-    ///     .and_where_not_in("title", &[quote("G"), quote("L"), quote("t")])
+    /// let sql = SqlBuilder::select_from("sales")
+    ///     .fields(["region", "product", "SUM(total)"])
+    ///     .group_by_grouping_sets(vec![vec!["region", "product"], vec!["region"], vec![]])
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE title NOT IN ('G', 'L', 't');", &sql);
-    /// // add                                           ^^^^^         ^^^^^^^^^^^^^
-    /// // here                                          field             list
+    /// assert_eq!("SELECT region, product, SUM(total) FROM sales GROUP BY GROUPING SETS ((region, product), (region), ());", &sql);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn and_where_not_in<S, T>(&mut self, field: S, list: &[T]) -> &mut Self
+    pub fn group_by_grouping_sets<S, I, J>(&mut self, sets: J) -> &mut Self
     where
         S: ToString,
-        T: ToString,
+        I: IntoIterator<Item = S>,
+        J: IntoIterator<Item = I>,
     {
-        // Checks
-        let field = field.to_string();
-        if field.is_empty() {
+        let sets = sets
+            .into_iter()
+            .map(|set| format!("({})", group_list(set)))
+            .collect::<Vec<String>>();
+        if sets.is_empty() {
             return self.set_error(&SqlBuilderError::NoWhereField);
         }
-        if list.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereList(field));
-        }
-
-        // Change
-        let list: Vec<String> = list
-            .iter()
-            .map(|v| (*v).to_string())
-            .collect::<Vec<String>>();
-        let list = list.join(", ");
-
-        let mut cond = field;
-        cond.push_str(" NOT IN (");
-        cond.push_str(&list);
-        cond.push(')');
-        self.and_where(&cond)
+        self.groupings
+            .push((self.group_by.len(), Grouping::Sets(sets.join(", "))));
+        self
     }
 
-    /// Add WHERE field NOT IN (string list).
+    /// Add GROUPING(fields) AS name to distinguish subtotal rows.
     ///
     /// ```
     /// # use anyhow::Result;
-    /// use sql_builder::{SqlBuilder, quote};
+    /// use sql_builder::SqlBuilder;
     ///
     /// # fn main() -> Result<()> {
-    /// let sql = SqlBuilder::select_from("books")
-    ///     .field("title")
-    ///     .field("price")
-    ///     .and_where_not_in_quoted("title", &["G", "L", "t"])
+    /// let sql = SqlBuilder::select_from("sales")
+    ///     .fields(["year", "SUM(total)"])
+    ///     .grouping_as(["year"], "is_total")
+    ///     .group_by_rollup(["year"])
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE title NOT IN ('G', 'L', 't');", &sql);
-    /// // add                                           ^^^^^         ^^^^^^^^^^^^^
-    /// // here                                          field             list
+    /// assert_eq!("SELECT year, SUM(total), GROUPING(year) AS is_total FROM sales GROUP BY ROLLUP (year);", &sql);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn and_where_not_in_quoted<S, T>(&mut self, field: S, list: &[T]) -> &mut Self
+    pub fn grouping_as<S, I, T>(&mut self, fields: I, name: T) -> &mut Self
     where
         S: ToString,
+        I: IntoIterator<Item = S>,
         T: ToString,
     {
-        // Checks
-        let field = field.to_string();
-        if field.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereField);
-        }
-        if list.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereList(field));
-        }
-
-        // Change
-        let list: Vec<String> = list
-            .iter()
-            .map(|v| quote((*v).to_string()))
-            .collect::<Vec<String>>();
-        let list = list.join(", ");
+        self.fields.push(format!(
+            "GROUPING({}) AS {}",
+            group_list(fields),
+            name.to_string()
+        ));
+        self
+    }
 
-        let mut cond = field;
-        cond.push_str(" NOT IN (");
-        cond.push_str(&list);
-        cond.push(')');
-        self.and_where(&cond)
-    }
-
-    /// Add WHERE field IN (query).
+    /// Add WHERE condition.
     ///
     /// ```
     /// # use anyhow::Result;
-    /// use sql_builder::{SqlBuilder, quote};
+    /// use sql_builder::SqlBuilder;
     ///
     /// # fn main() -> Result<()> {
-    /// let query = SqlBuilder::select_from("shop")
-    ///     .field("title")
-    ///     .and_where("sold")
-    ///     .query()?;
-    ///
-    /// assert_eq!("SELECT title FROM shop WHERE sold", &query);
-    ///
     /// let sql = SqlBuilder::select_from("books")
     ///     .field("title")
     ///     .field("price")
-    ///     .and_where_in_query("title", &query)
+    ///     .and_where("price > 100")
+    ///     .and_where("title LIKE 'Harry Potter%'")
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE title IN (SELECT title FROM shop WHERE sold);", &sql);
-    /// // add                                           ^^^^^     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
-    /// // here                                          field                   query
+    /// assert_eq!("SELECT title, price FROM books WHERE (price > 100) AND (title LIKE 'Harry Potter%');", &sql);
+    /// // add                                            ^^^^^^^^^^^       ^^^^^^^^^^^^^^^^^^^^^^^^^^
+    /// // here                                              cond                      cond
     /// # Ok(())
     /// # }
     /// ```
-    pub fn and_where_in_query<S, T>(&mut self, field: S, query: T) -> &mut Self
-    where
-        S: ToString,
-        T: ToString,
-    {
+    pub fn and_where<S: ToString>(&mut self, cond: S) -> &mut Self {
         // Checks
-        let field = field.to_string();
-        if field.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereField);
-        }
-        let query = query.to_string();
-        if query.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereQuery(field));
+        let cond = cond.to_string();
+        if cond.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereCond);
         }
 
         // Change
-        let mut cond = field;
-        cond.push_str(" IN (");
-        cond.push_str(&query);
-        cond.push(')');
-        self.and_where(&cond)
+        self.wheres.push(cond);
+        self
     }
 
-    /// Add WHERE field NOT IN (query).
+    /// Add WHERE condition for equal parts.
+    /// NULL value renders `field = NULL` which never matches,
+    /// use [`and_where_eq_nullable`](Self::and_where_eq_nullable) for nullable values.
     ///
     /// ```
     /// # use anyhow::Result;
     /// use sql_builder::{SqlBuilder, quote};
     ///
     /// # fn main() -> Result<()> {
-    /// let query = SqlBuilder::select_from("shop")
-    ///     .field("title")
-    ///     .and_where("sold")
-    ///     .query()?;
-    ///
-    /// assert_eq!("SELECT title FROM shop WHERE sold", &query);
-    ///
     /// let sql = SqlBuilder::select_from("books")
-    ///     .field("title")
     ///     .field("price")
-    ///     .and_where_not_in_query("title", &query)
+    ///     .and_where_eq("title", &quote("Harry Potter and the Philosopher's Stone"))
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE title NOT IN (SELECT title FROM shop WHERE sold);", &sql);
-    /// // add                                           ^^^^^         ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
-    /// // here                                          field                       query
+    /// assert_eq!("SELECT price FROM books WHERE title = 'Harry Potter and the Philosopher''s Stone';", &sql);
+    /// // add                                    ^^^^^   ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+    /// // here                                   field                      value
     /// # Ok(())
     /// # }
     /// ```
-    pub fn and_where_not_in_query<S, T>(&mut self, field: S, query: T) -> &mut Self
+    pub fn and_where_eq<S, T>(&mut self, field: S, value: T) -> &mut Self
     where
         S: ToString,
         T: ToString,
@@ -1847,68 +2114,61 @@ impl SqlBuilder {
         if field.is_empty() {
             return self.set_error(&SqlBuilderError::NoWhereField);
         }
-        let query = query.to_string();
-        if query.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereQuery(field));
+        let value = value.to_string();
+        if value.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereValue(field));
         }
 
         // Change
         let mut cond = field;
-        cond.push_str(" NOT IN (");
-        cond.push_str(&query);
-        cond.push(')');
+        cond.push_str(" = ");
+        cond.push_str(&value);
         self.and_where(&cond)
     }
 
-    /// Add WHERE field BETWEEN values.
+    /// Add WHERE condition for non-equal parts.
+    /// NULL value renders `field <> NULL` which never matches,
+    /// use [`and_where_ne_nullable`](Self::and_where_ne_nullable) for nullable values.
     ///
     /// ```
     /// # use anyhow::Result;
-    /// use sql_builder::SqlBuilder;
+    /// use sql_builder::{SqlBuilder, quote};
     ///
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
-    ///     .field("title")
     ///     .field("price")
-    ///     .and_where_between("price", 10_000, 20_000)
+    ///     .and_where_ne("title", &quote("Harry Potter and the Philosopher's Stone"))
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE price BETWEEN 10000 AND 20000;", &sql);
-    /// // add                                           ^^^^^         ^^^^^     ^^^^^
-    /// // here                                          field          min       max
+    /// assert_eq!("SELECT price FROM books WHERE title <> 'Harry Potter and the Philosopher''s Stone';", &sql);
+    /// // add                                    ^^^^^    ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+    /// // here                                   field                       value
     /// # Ok(())
     /// # }
     /// ```
-    pub fn and_where_between<S, T, U>(&mut self, field: S, min: T, max: U) -> &mut Self
+    pub fn and_where_ne<S, T>(&mut self, field: S, value: T) -> &mut Self
     where
         S: ToString,
         T: ToString,
-        U: ToString,
     {
         // Checks
         let field = field.to_string();
         if field.is_empty() {
             return self.set_error(&SqlBuilderError::NoWhereField);
         }
-        let min = min.to_string();
-        if min.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereValue(field));
-        }
-        let max = max.to_string();
-        if max.is_empty() {
+        let value = value.to_string();
+        if value.is_empty() {
             return self.set_error(&SqlBuilderError::NoWhereValue(field));
         }
 
         // Change
         let mut cond = field;
-        cond.push_str(" BETWEEN ");
-        cond.push_str(&min);
-        cond.push_str(" AND ");
-        cond.push_str(&max);
+        cond.push_str(" <> ");
+        cond.push_str(&value);
         self.and_where(&cond)
     }
 
-    /// Add WHERE field NOT BETWEEN values.
+    /// Add WHERE condition for field greater than value.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -1918,45 +2178,38 @@ impl SqlBuilder {
     /// let sql = SqlBuilder::select_from("books")
     ///     .field("title")
     ///     .field("price")
-    ///     .and_where_not_between("price", 10_000, 20_000)
+    ///     .and_where_gt("price", 300)
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE price NOT BETWEEN 10000 AND 20000;", &sql);
-    /// // add                                           ^^^^^             ^^^^^     ^^^^^
-    /// // here                                          field              min       max
+    /// assert_eq!("SELECT title, price FROM books WHERE price > 300;", &sql);
+    /// // add                                           ^^^^^   ^^^
+    /// // here                                          field  value
     /// # Ok(())
     /// # }
     /// ```
-    pub fn and_where_not_between<S, T, U>(&mut self, field: S, min: T, max: U) -> &mut Self
+    pub fn and_where_gt<S, T>(&mut self, field: S, value: T) -> &mut Self
     where
         S: ToString,
         T: ToString,
-        U: ToString,
     {
         // Checks
         let field = field.to_string();
         if field.is_empty() {
             return self.set_error(&SqlBuilderError::NoWhereField);
         }
-        let min = min.to_string();
-        if min.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereValue(field));
-        }
-        let max = max.to_string();
-        if max.is_empty() {
+        let value = value.to_string();
+        if value.is_empty() {
             return self.set_error(&SqlBuilderError::NoWhereValue(field));
         }
 
         // Change
         let mut cond = field;
-        cond.push_str(" NOT BETWEEN ");
-        cond.push_str(&min);
-        cond.push_str(" AND ");
-        cond.push_str(&max);
+        cond.push_str(" > ");
+        cond.push_str(&value);
         self.and_where(&cond)
     }
 
-    /// Add OR condition to the last WHERE condition.
+    /// Add WHERE condition for field not less than value.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -1966,53 +2219,16 @@ impl SqlBuilder {
     /// let sql = SqlBuilder::select_from("books")
     ///     .field("title")
     ///     .field("price")
-    ///     .and_where("price < 10")
-    ///     .or_where("price > 1000")
-    ///     .sql()?;
-    ///
-    /// assert_eq!("SELECT title, price FROM books WHERE price < 10 OR price > 1000;", &sql);
-    /// // add                                                         ^^^^^^^^^^^^
-    /// // here                                                            cond
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub fn or_where<S: ToString>(&mut self, cond: S) -> &mut Self {
-        // Checks
-        let cond = cond.to_string();
-        if cond.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereCond);
-        }
-
-        // Change
-        if self.wheres.is_empty() {
-            self.wheres.push(cond);
-        } else if let Some(last) = self.wheres.last_mut() {
-            last.push_str(" OR ");
-            last.push_str(&cond);
-        }
-        self
-    }
-
-    /// Add OR condition of equal parts to the last WHERE condition.
-    ///
-    /// ```
-    /// # use anyhow::Result;
-    /// use sql_builder::{SqlBuilder, quote};
-    ///
-    /// # fn main() -> Result<()> {
-    /// let sql = SqlBuilder::select_from("books")
-    ///     .field("price")
-    ///     .and_where_eq("title", &quote("Harry Potter and the Philosopher's Stone"))
-    ///     .or_where_eq("title", &quote("Harry Potter and the Chamber of Secrets"))
+    ///     .and_where_ge("price", 300)
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT price FROM books WHERE title = 'Harry Potter and the Philosopher''s Stone' OR title = 'Harry Potter and the Chamber of Secrets';", &sql);
-    /// // add                                                                                           ^^^^^   ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
-    /// // here                                                                                          field                     value
+    /// assert_eq!("SELECT title, price FROM books WHERE price >= 300;", &sql);
+    /// // add                                           ^^^^^    ^^^
+    /// // here                                          field   value
     /// # Ok(())
     /// # }
     /// ```
-    pub fn or_where_eq<S, T>(&mut self, field: S, value: T) -> &mut Self
+    pub fn and_where_ge<S, T>(&mut self, field: S, value: T) -> &mut Self
     where
         S: ToString,
         T: ToString,
@@ -2029,31 +2245,31 @@ impl SqlBuilder {
 
         // Change
         let mut cond = field;
-        cond.push_str(" = ");
+        cond.push_str(" >= ");
         cond.push_str(&value);
-        self.or_where(&cond)
+        self.and_where(&cond)
     }
 
-    /// Add OR condition of non-equal parts to the last WHERE condition.
+    /// Add WHERE condition for field less than value.
     ///
     /// ```
     /// # use anyhow::Result;
-    /// use sql_builder::{SqlBuilder, quote};
+    /// use sql_builder::SqlBuilder;
     ///
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
     ///     .field("price")
-    ///     .or_where_ne("title", &quote("Harry Potter and the Philosopher's Stone"))
-    ///     .or_where_ne("title", &quote("Harry Potter and the Chamber of Secrets"))
+    ///     .and_where_lt("price", 300)
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT price FROM books WHERE title <> 'Harry Potter and the Philosopher''s Stone' OR title <> 'Harry Potter and the Chamber of Secrets';", &sql);
-    /// // add                                    ^^^^^    ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^    ^^^^^    ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
-    /// // here                                   field                       value                       field                      value
+    /// assert_eq!("SELECT title, price FROM books WHERE price < 300;", &sql);
+    /// // add                                           ^^^^^   ^^^
+    /// // here                                          field  value
     /// # Ok(())
     /// # }
     /// ```
-    pub fn or_where_ne<S, T>(&mut self, field: S, value: T) -> &mut Self
+    pub fn and_where_lt<S, T>(&mut self, field: S, value: T) -> &mut Self
     where
         S: ToString,
         T: ToString,
@@ -2070,12 +2286,12 @@ impl SqlBuilder {
 
         // Change
         let mut cond = field;
-        cond.push_str(" <> ");
+        cond.push_str(" < ");
         cond.push_str(&value);
-        self.or_where(&cond)
+        self.and_where(&cond)
     }
 
-    /// Add OR condition for field greater than value to the last WHERE condition.
+    /// Add WHERE condition for field not greater than value.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -2085,17 +2301,16 @@ impl SqlBuilder {
     /// let sql = SqlBuilder::select_from("books")
     ///     .field("title")
     ///     .field("price")
-    ///     .and_where_lt("price", 100)
-    ///     .or_where_gt("price", 300)
+    ///     .and_where_le("price", 300)
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE price < 100 OR price > 300;", &sql);
-    /// // add                                                          ^^^^^   ^^^
-    /// // here                                                         field  value
+    /// assert_eq!("SELECT title, price FROM books WHERE price <= 300;", &sql);
+    /// // add                                           ^^^^^    ^^^
+    /// // here                                          field   value
     /// # Ok(())
     /// # }
     /// ```
-    pub fn or_where_gt<S, T>(&mut self, field: S, value: T) -> &mut Self
+    pub fn and_where_le<S, T>(&mut self, field: S, value: T) -> &mut Self
     where
         S: ToString,
         T: ToString,
@@ -2112,12 +2327,12 @@ impl SqlBuilder {
 
         // Change
         let mut cond = field;
-        cond.push_str(" > ");
+        cond.push_str(" <= ");
         cond.push_str(&value);
-        self.or_where(&cond)
+        self.and_where(&cond)
     }
 
-    /// Add OR condition for field not less than value to the last WHERE condition.
+    /// Add WHERE LIKE condition.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -2125,19 +2340,17 @@ impl SqlBuilder {
     ///
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
-    ///     .field("title")
     ///     .field("price")
-    ///     .or_where_lt("price", 100)
-    ///     .or_where_ge("price", 300)
+    ///     .and_where_like("title", "%Philosopher's%")
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE price < 100 OR price >= 300;", &sql);
-    /// // add                                                          ^^^^^    ^^^
-    /// // here                                                         field   value
+    /// assert_eq!("SELECT price FROM books WHERE title LIKE '%Philosopher''s%';", &sql);
+    /// // add                                    ^^^^^       ^^^^^^^^^^^^^^^^
+    /// // here                                   field             mask
     /// # Ok(())
     /// # }
     /// ```
-    pub fn or_where_ge<S, T>(&mut self, field: S, value: T) -> &mut Self
+    pub fn and_where_like<S, T>(&mut self, field: S, mask: T) -> &mut Self
     where
         S: ToString,
         T: ToString,
@@ -2147,19 +2360,16 @@ impl SqlBuilder {
         if field.is_empty() {
             return self.set_error(&SqlBuilderError::NoWhereField);
         }
-        let value = value.to_string();
-        if value.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereValue(field));
-        }
 
         // Change
         let mut cond = field;
-        cond.push_str(" >= ");
-        cond.push_str(&value);
-        self.or_where(&cond)
+        cond.push_str(" LIKE '");
+        cond.push_str(&esc(&mask.to_string()));
+        cond.push('\'');
+        self.and_where(&cond)
     }
 
-    /// Add OR condition for field less than value to the last WHERE condition.
+    /// Add WHERE LIKE %condition.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -2167,19 +2377,17 @@ impl SqlBuilder {
     ///
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
-    ///     .field("title")
     ///     .field("price")
-    ///     .and_where_lt("price", 100)
-    ///     .or_where_lt("price", 300)
+    ///     .and_where_like_right("title", "Stone")
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE price < 100 OR price < 300;", &sql);
-    /// // add                                                          ^^^^^   ^^^
-    /// // here                                                         field  value
+    /// assert_eq!("SELECT price FROM books WHERE title LIKE '%Stone' ESCAPE '\\';", &sql);
+    /// // add                                    ^^^^^        ^^^^^
+    /// // here                                   field        mask
     /// # Ok(())
     /// # }
     /// ```
-    pub fn or_where_lt<S, T>(&mut self, field: S, value: T) -> &mut Self
+    pub fn and_where_like_right<S, T>(&mut self, field: S, mask: T) -> &mut Self
     where
         S: ToString,
         T: ToString,
@@ -2189,19 +2397,15 @@ impl SqlBuilder {
         if field.is_empty() {
             return self.set_error(&SqlBuilderError::NoWhereField);
         }
-        let value = value.to_string();
-        if value.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereValue(field));
-        }
 
         // Change
         let mut cond = field;
-        cond.push_str(" < ");
-        cond.push_str(&value);
-        self.or_where(&cond)
+        cond.push_str(" LIKE ");
+        cond.push_str(&self.like_mask("%", &mask.to_string(), ""));
+        self.and_where(&cond)
     }
 
-    /// Add OR condition for field not greater than value to the last WHERE condition.
+    /// Add WHERE LIKE condition%.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -2209,19 +2413,17 @@ impl SqlBuilder {
     ///
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
-    ///     .field("title")
     ///     .field("price")
-    ///     .or_where_le("price", 100)
-    ///     .or_where_ge("price", 300)
+    ///     .and_where_like_left("title", "Harry")
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE price <= 100 OR price >= 300;", &sql);
-    /// // add                                           ^^^^^    ^^^
-    /// // here                                          field   value
+    /// assert_eq!("SELECT price FROM books WHERE title LIKE 'Harry%' ESCAPE '\\';", &sql);
+    /// // add                                    ^^^^^       ^^^^^
+    /// // here                                   field       mask
     /// # Ok(())
     /// # }
     /// ```
-    pub fn or_where_le<S, T>(&mut self, field: S, value: T) -> &mut Self
+    pub fn and_where_like_left<S, T>(&mut self, field: S, mask: T) -> &mut Self
     where
         S: ToString,
         T: ToString,
@@ -2231,19 +2433,15 @@ impl SqlBuilder {
         if field.is_empty() {
             return self.set_error(&SqlBuilderError::NoWhereField);
         }
-        let value = value.to_string();
-        if value.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereValue(field));
-        }
 
         // Change
         let mut cond = field;
-        cond.push_str(" <= ");
-        cond.push_str(&value);
-        self.or_where(&cond)
+        cond.push_str(" LIKE ");
+        cond.push_str(&self.like_mask("", &mask.to_string(), "%"));
+        self.and_where(&cond)
     }
 
-    /// Add OR LIKE condition to the last WHERE condition.
+    /// Add WHERE LIKE %condition%.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -2252,17 +2450,16 @@ impl SqlBuilder {
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
     ///     .field("price")
-    ///     .or_where_like("title", "%Alice's%")
-    ///     .or_where_like("title", "%Philosopher's%")
+    ///     .and_where_like_any("title", " and ")
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT price FROM books WHERE title LIKE '%Alice''s%' OR title LIKE '%Philosopher''s%';", &sql);
-    /// // add                                    ^^^^^      ^^^^^^^^^^^^    ^^^^^      ^^^^^^^^^^^^^^^^^^
-    /// // here                                   field          mask        field             mask
+    /// assert_eq!("SELECT price FROM books WHERE title LIKE '% and %' ESCAPE '\\';", &sql);
+    /// // add                                    ^^^^^        ^^^^^
+    /// // here                                   field        mask
     /// # Ok(())
     /// # }
     /// ```
-    pub fn or_where_like<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    pub fn and_where_like_any<S, T>(&mut self, field: S, mask: T) -> &mut Self
     where
         S: ToString,
         T: ToString,
@@ -2275,13 +2472,12 @@ impl SqlBuilder {
 
         // Change
         let mut cond = field;
-        cond.push_str(" LIKE '");
-        cond.push_str(&esc(&mask.to_string()));
-        cond.push('\'');
-        self.or_where(&cond)
+        cond.push_str(" LIKE ");
+        cond.push_str(&self.like_mask("%", &mask.to_string(), "%"));
+        self.and_where(&cond)
     }
 
-    /// Add OR LIKE condition to the last WHERE %condition.
+    /// Add WHERE NOT LIKE condition.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -2289,18 +2485,17 @@ impl SqlBuilder {
     ///
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
-    ///     .field("price")
-    ///     .or_where_like_right("title", "Alice's")
-    ///     .or_where_like_right("title", "Philosopher's")
+    ///     .field("title")
+    ///     .and_where_not_like("title", "%Alice's%")
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT price FROM books WHERE title LIKE '%Alice''s' OR title LIKE '%Philosopher''s';", &sql);
-    /// // add                                    ^^^^^        ^^^^^^^^     ^^^^^        ^^^^^^^^^^^^^^
-    /// // here                                   field          mask       field             mask
+    /// assert_eq!("SELECT title FROM books WHERE title NOT LIKE '%Alice''s%';", &sql);
+    /// // add                                    ^^^^^           ^^^^^^^^^^
+    /// // here                                   field              mask
     /// # Ok(())
     /// # }
     /// ```
-    pub fn or_where_like_right<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    pub fn and_where_not_like<S, T>(&mut self, field: S, mask: T) -> &mut Self
     where
         S: ToString,
         T: ToString,
@@ -2310,16 +2505,20 @@ impl SqlBuilder {
         if field.is_empty() {
             return self.set_error(&SqlBuilderError::NoWhereField);
         }
+        let mask = mask.to_string();
+        if mask.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereValue(field));
+        }
 
         // Change
         let mut cond = field;
-        cond.push_str(" LIKE '%");
-        cond.push_str(&esc(&mask.to_string()));
+        cond.push_str(" NOT LIKE '");
+        cond.push_str(&esc(&mask));
         cond.push('\'');
-        self.or_where(&cond)
+        self.and_where(&cond)
     }
 
-    /// Add OR LIKE condition to the last WHERE condition%.
+    /// Add WHERE NOT LIKE %condition.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -2328,17 +2527,16 @@ impl SqlBuilder {
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
     ///     .field("price")
-    ///     .or_where_like_left("title", "Alice's")
-    ///     .or_where_like_left("title", "Philosopher's")
+    ///     .and_where_not_like_right("title", "Stone")
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT price FROM books WHERE title LIKE 'Alice''s%' OR title LIKE 'Philosopher''s%';", &sql);
-    /// // add                                    ^^^^^       ^^^^^^^^      ^^^^^       ^^^^^^^^^^^^^^
-    /// // here                                   field         mask        field            mask
+    /// assert_eq!("SELECT price FROM books WHERE title NOT LIKE '%Stone' ESCAPE '\\';", &sql);
+    /// // add                                    ^^^^^            ^^^^^
+    /// // here                                   field            mask
     /// # Ok(())
     /// # }
     /// ```
-    pub fn or_where_like_left<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    pub fn and_where_not_like_right<S, T>(&mut self, field: S, mask: T) -> &mut Self
     where
         S: ToString,
         T: ToString,
@@ -2348,16 +2546,19 @@ impl SqlBuilder {
         if field.is_empty() {
             return self.set_error(&SqlBuilderError::NoWhereField);
         }
+        let mask = mask.to_string();
+        if mask.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereValue(field));
+        }
 
         // Change
         let mut cond = field;
-        cond.push_str(" LIKE '");
-        cond.push_str(&esc(&mask.to_string()));
-        cond.push_str("%'");
-        self.or_where(&cond)
+        cond.push_str(" NOT LIKE ");
+        cond.push_str(&self.like_mask("%", &mask, ""));
+        self.and_where(&cond)
     }
 
-    /// Add OR LIKE condition to the last WHERE %condition%.
+    /// Add WHERE NOT LIKE condition%.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -2366,17 +2567,16 @@ impl SqlBuilder {
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
     ///     .field("price")
-    ///     .or_where_like_any("title", "Alice's")
-    ///     .or_where_like_any("title", "Philosopher's")
+    ///     .and_where_not_like_left("title", "Harry")
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT price FROM books WHERE title LIKE '%Alice''s%' OR title LIKE '%Philosopher''s%';", &sql);
-    /// // add                                    ^^^^^      ^^^^^^^^^^^^    ^^^^^      ^^^^^^^^^^^^^^^^^^
-    /// // here                                   field          mask        field             mask
+    /// assert_eq!("SELECT price FROM books WHERE title NOT LIKE 'Harry%' ESCAPE '\\';", &sql);
+    /// // add                                    ^^^^^           ^^^^^
+    /// // here                                   field           mask
     /// # Ok(())
     /// # }
     /// ```
-    pub fn or_where_like_any<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    pub fn and_where_not_like_left<S, T>(&mut self, field: S, mask: T) -> &mut Self
     where
         S: ToString,
         T: ToString,
@@ -2386,16 +2586,19 @@ impl SqlBuilder {
         if field.is_empty() {
             return self.set_error(&SqlBuilderError::NoWhereField);
         }
+        let mask = mask.to_string();
+        if mask.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereValue(field));
+        }
 
         // Change
         let mut cond = field;
-        cond.push_str(" LIKE '%");
-        cond.push_str(&esc(&mask.to_string()));
-        cond.push_str("%'");
-        self.or_where(&cond)
+        cond.push_str(" NOT LIKE ");
+        cond.push_str(&self.like_mask("", &mask, "%"));
+        self.and_where(&cond)
     }
 
-    /// Add OR NOT LIKE condition to the last WHERE condition.
+    /// Add WHERE NOT LIKE %condition%.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -2403,18 +2606,17 @@ impl SqlBuilder {
     ///
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
-    ///     .field("title")
-    ///     .and_where_not_like("title", "%Alice's%")
-    ///     .or_where_not_like("title", "%Philosopher's%")
+    ///     .field("price")
+    ///     .and_where_not_like_any("title", " and ")
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title FROM books WHERE title NOT LIKE '%Alice''s%' OR title NOT LIKE '%Philosopher''s%';", &sql);
-    /// // add                                                                   ^^^^^          ^^^^^^^^^^^^^^^^^^
-    /// // here                                                                  field                 mask
+    /// assert_eq!("SELECT price FROM books WHERE title NOT LIKE '% and %' ESCAPE '\\';", &sql);
+    /// // add                                    ^^^^^            ^^^^^
+    /// // here                                   field            mask
     /// # Ok(())
     /// # }
     /// ```
-    pub fn or_where_not_like<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    pub fn and_where_not_like_any<S, T>(&mut self, field: S, mask: T) -> &mut Self
     where
         S: ToString,
         T: ToString,
@@ -2431,13 +2633,12 @@ impl SqlBuilder {
 
         // Change
         let mut cond = field;
-        cond.push_str(" NOT LIKE '");
-        cond.push_str(&esc(&mask));
-        cond.push('\'');
-        self.or_where(&cond)
+        cond.push_str(" NOT LIKE ");
+        cond.push_str(&self.like_mask("%", &mask, "%"));
+        self.and_where(&cond)
     }
 
-    /// Add OR NOT LIKE condition to the last WHERE %condition.
+    /// Add WHERE IS NULL condition.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -2445,41 +2646,30 @@ impl SqlBuilder {
     ///
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
-    ///     .field("price")
-    ///     .or_where_not_like_right("title", "Alice's")
-    ///     .or_where_not_like_right("title", "Philosopher's")
+    ///     .field("title")
+    ///     .and_where_is_null("price")
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT price FROM books WHERE title NOT LIKE '%Alice''s' OR title NOT LIKE '%Philosopher''s';", &sql);
-    /// // add                                    ^^^^^            ^^^^^^^^     ^^^^^            ^^^^^^^^^^^^^^
-    /// // here                                   field              mask       field                 mask
+    /// assert_eq!("SELECT title FROM books WHERE price IS NULL;", &sql);
+    /// // add                                    ^^^^^
+    /// // here                                   field
     /// # Ok(())
     /// # }
     /// ```
-    pub fn or_where_not_like_right<S, T>(&mut self, field: S, mask: T) -> &mut Self
-    where
-        S: ToString,
-        T: ToString,
-    {
+    pub fn and_where_is_null<S: ToString>(&mut self, field: S) -> &mut Self {
         // Checks
         let field = field.to_string();
         if field.is_empty() {
             return self.set_error(&SqlBuilderError::NoWhereField);
         }
-        let mask = mask.to_string();
-        if mask.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereValue(field));
-        }
 
         // Change
         let mut cond = field;
-        cond.push_str(" NOT LIKE '%");
-        cond.push_str(&esc(&mask));
-        cond.push('\'');
-        self.or_where(&cond)
+        cond.push_str(" IS NULL");
+        self.and_where(&cond)
     }
 
-    /// Add OR NOT LIKE condition to the last WHERE condition%.
+    /// Add WHERE IS NOT NULL condition.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -2487,60 +2677,49 @@ impl SqlBuilder {
     ///
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
-    ///     .field("price")
-    ///     .or_where_not_like_left("title", "Alice's")
-    ///     .or_where_not_like_left("title", "Philosopher's")
+    ///     .field("title")
+    ///     .and_where_is_not_null("price")
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT price FROM books WHERE title NOT LIKE 'Alice''s%' OR title NOT LIKE 'Philosopher''s%';", &sql);
-    /// // add                                    ^^^^^           ^^^^^^^^      ^^^^^           ^^^^^^^^^^^^^^
-    /// // here                                   field             mask        field                mask
+    /// assert_eq!("SELECT title FROM books WHERE price IS NOT NULL;", &sql);
+    /// // add                                    ^^^^^
+    /// // here                                   field
     /// # Ok(())
     /// # }
     /// ```
-    pub fn or_where_not_like_left<S, T>(&mut self, field: S, mask: T) -> &mut Self
-    where
-        S: ToString,
-        T: ToString,
-    {
+    pub fn and_where_is_not_null<S: ToString>(&mut self, field: S) -> &mut Self {
         // Checks
         let field = field.to_string();
         if field.is_empty() {
             return self.set_error(&SqlBuilderError::NoWhereField);
         }
-        let mask = mask.to_string();
-        if mask.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereValue(field));
-        }
 
         // Change
         let mut cond = field;
-        cond.push_str(" NOT LIKE '");
-        cond.push_str(&esc(&mask));
-        cond.push_str("%'");
-        self.or_where(&cond)
+        cond.push_str(" IS NOT NULL");
+        self.and_where(&cond)
     }
 
-    /// Add OR NOT LIKE condition to the last WHERE %condition%.
+    /// Add WHERE field IN (list).
     ///
     /// ```
     /// # use anyhow::Result;
-    /// use sql_builder::SqlBuilder;
+    /// use sql_builder::{SqlBuilder, quote};
     ///
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
     ///     .field("price")
-    ///     .or_where_not_like_any("title", "Alice's")
-    ///     .or_where_not_like_any("title", "Philosopher's")
+    ///     .and_where_in("title", &[quote("G"), quote("L"), quote("t")])
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT price FROM books WHERE title NOT LIKE '%Alice''s%' OR title NOT LIKE '%Philosopher''s%';", &sql);
-    /// // add                                    ^^^^^          ^^^^^^^^^^^^    ^^^^^          ^^^^^^^^^^^^^^^^^^
-    /// // here                                   field              mask        field                 mask
+    /// assert_eq!("SELECT title, price FROM books WHERE title IN ('G', 'L', 't');", &sql);
+    /// // add                                           ^^^^^     ^^^^^^^^^^^^^
+    /// // here                                          field         list
     /// # Ok(())
     /// # }
     /// ```
-    pub fn or_where_not_like_any<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    pub fn and_where_in<S, T>(&mut self, field: S, list: &[T]) -> &mut Self
     where
         S: ToString,
         T: ToString,
@@ -2550,104 +2729,79 @@ impl SqlBuilder {
         if field.is_empty() {
             return self.set_error(&SqlBuilderError::NoWhereField);
         }
-        let mask = mask.to_string();
-        if mask.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereValue(field));
+        if list.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereList(field));
         }
 
         // Change
-        let mut cond = field;
-        cond.push_str(" NOT LIKE '%");
-        cond.push_str(&esc(&mask));
-        cond.push_str("%'");
-        self.or_where(&cond)
+        let list: Vec<String> = list
+            .iter()
+            .map(|v| (*v).to_string())
+            .collect::<Vec<String>>();
+        self.add_in_list(field, list)
     }
 
-    /// Add OR IS NULL condition to the last WHERE condition.
+    /// Add WHERE field IN (string list).
     ///
     /// ```
     /// # use anyhow::Result;
-    /// use sql_builder::SqlBuilder;
+    /// use sql_builder::{SqlBuilder, quote};
     ///
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
     ///     .field("title")
-    ///     .and_where_eq("price", 0)
-    ///     .or_where_is_null("price")
+    ///     .field("price")
+    ///     .and_where_in_quoted("title", &["G", "L", "t"])
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title FROM books WHERE price = 0 OR price IS NULL;", &sql);
-    /// // add                                                 ^^^^^
-    /// // here                                                field
+    /// assert_eq!("SELECT title, price FROM books WHERE title IN ('G', 'L', 't');", &sql);
+    /// // add                                           ^^^^^     ^^^^^^^^^^^^^
+    /// // here                                          field         list
     /// # Ok(())
     /// # }
     /// ```
-    pub fn or_where_is_null<S: ToString>(&mut self, field: S) -> &mut Self {
+    pub fn and_where_in_quoted<S, T>(&mut self, field: S, list: &[T]) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
         // Checks
         let field = field.to_string();
         if field.is_empty() {
             return self.set_error(&SqlBuilderError::NoWhereField);
         }
+        if list.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereList(field));
+        }
 
         // Change
-        let mut cond = field;
-        cond.push_str(" IS NULL");
-        self.or_where(&cond)
+        let list: Vec<String> = list
+            .iter()
+            .map(|v| quote((*v).to_string()))
+            .collect::<Vec<String>>();
+        self.add_in_list(field, list)
     }
 
-    /// Add OR IS NOT NULL condition to the last WHERE condition.
+    /// Add WHERE field NOT IN (list).
     ///
     /// ```
     /// # use anyhow::Result;
-    /// use sql_builder::SqlBuilder;
+    /// use sql_builder::{SqlBuilder, quote};
     ///
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
     ///     .field("title")
-    ///     .or_where_is_not_null("title")
-    ///     .or_where_is_not_null("price")
+    ///     .field("price")
+    ///     .and_where_not_in("title", &[quote("G"), quote("L"), quote("t")])
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title FROM books WHERE title IS NOT NULL OR price IS NOT NULL;", &sql);
-    /// // add                                    ^^^^^                ^^^^^
-    /// // here                                   field                field
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub fn or_where_is_not_null<S: ToString>(&mut self, field: S) -> &mut Self {
-        // Checks
-        let field = field.to_string();
-        if field.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereField);
-        }
-
-        // Change
-        let mut cond = field;
-        cond.push_str(" IS NOT NULL");
-        self.or_where(&cond)
-    }
-
-    /// Add OR field IN (list) to the last WHERE condition.
-    ///
-    /// ```
-    /// # use anyhow::Result;
-    /// use sql_builder::{SqlBuilder, quote};
-    ///
-    /// # fn main() -> Result<()> {
-    /// let sql = SqlBuilder::select_from("books")
-    ///     .field("title")
-    ///     .field("price")
-    ///     .or_where_lt("price", 100)
-    ///     .or_where_in("title", &[quote("G"), quote("L"), quote("t")])
-    ///     .sql()?;
-    ///
-    /// assert_eq!("SELECT title, price FROM books WHERE price < 100 OR title IN ('G', 'L', 't');", &sql);
-    /// // add                                                          ^^^^^     ^^^^^^^^^^^^^
-    /// // here                                                         field         list
+    /// assert_eq!("SELECT title, price FROM books WHERE title NOT IN ('G', 'L', 't');", &sql);
+    /// // add                                           ^^^^^         ^^^^^^^^^^^^^
+    /// // here                                          field             list
     /// # Ok(())
     /// # }
     /// ```
-    pub fn or_where_in<S, T>(&mut self, field: S, list: &[T]) -> &mut Self
+    pub fn and_where_not_in<S, T>(&mut self, field: S, list: &[T]) -> &mut Self
     where
         S: ToString,
         T: ToString,
@@ -2669,13 +2823,13 @@ impl SqlBuilder {
         let list = list.join(", ");
 
         let mut cond = field;
-        cond.push_str(" IN (");
+        cond.push_str(" NOT IN (");
         cond.push_str(&list);
         cond.push(')');
-        self.or_where(&cond)
+        self.and_where(&cond)
     }
 
-    /// Add OR field IN (string list) to the last WHERE condition.
+    /// Add WHERE field NOT IN (string list).
     ///
     /// ```
     /// # use anyhow::Result;
@@ -2685,17 +2839,16 @@ impl SqlBuilder {
     /// let sql = SqlBuilder::select_from("books")
     ///     .field("title")
     ///     .field("price")
-    ///     .or_where_lt("price", 100)
-    ///     .or_where_in_quoted("title", &["G", "L", "t"])
+    ///     .and_where_not_in_quoted("title", &["G", "L", "t"])
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE price < 100 OR title IN ('G', 'L', 't');", &sql);
-    /// // add                                                          ^^^^^     ^^^^^^^^^^^^^
-    /// // here                                                         field         list
+    /// assert_eq!("SELECT title, price FROM books WHERE title NOT IN ('G', 'L', 't');", &sql);
+    /// // add                                           ^^^^^         ^^^^^^^^^^^^^
+    /// // here                                          field             list
     /// # Ok(())
     /// # }
     /// ```
-    pub fn or_where_in_quoted<S, T>(&mut self, field: S, list: &[T]) -> &mut Self
+    pub fn and_where_not_in_quoted<S, T>(&mut self, field: S, list: &[T]) -> &mut Self
     where
         S: ToString,
         T: ToString,
@@ -2717,209 +2870,272 @@ impl SqlBuilder {
         let list = list.join(", ");
 
         let mut cond = field;
-        cond.push_str(" IN (");
+        cond.push_str(" NOT IN (");
         cond.push_str(&list);
         cond.push(')');
-        self.or_where(&cond)
+        self.and_where(&cond)
     }
 
-    /// Add OR field NOT IN (list) to the last WHERE condition.
+    /// Add WHERE field IN (query).
+    /// Query may be a text or a builder.
     ///
     /// ```
     /// # use anyhow::Result;
     /// use sql_builder::{SqlBuilder, quote};
     ///
     /// # fn main() -> Result<()> {
+    /// let query = SqlBuilder::select_from("shop")
+    ///     .field("title")
+    ///     .and_where("sold")
+    ///     .query()?;
+    ///
+    /// assert_eq!("SELECT title FROM shop WHERE sold", &query);
+    ///
     /// let sql = SqlBuilder::select_from("books")
     ///     .field("title")
     ///     .field("price")
-    ///     .or_where_lt("price", 100)
-    ///     .or_where_not_in("title", &[quote("G"), quote("L"), quote("t")])
+    ///     .and_where_in_query("title", &query)
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE price < 100 OR title NOT IN ('G', 'L', 't');", &sql);
-    /// // add                                                          ^^^^^         ^^^^^^^^^^^^^
-    /// // here                                                         field             list
+    /// assert_eq!("SELECT title, price FROM books WHERE title IN (SELECT title FROM shop WHERE sold);", &sql);
+    /// // add                                           ^^^^^     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+    /// // here                                          field                   query
     /// # Ok(())
     /// # }
     /// ```
-    pub fn or_where_not_in<S, T>(&mut self, field: S, list: &[T]) -> &mut Self
+    pub fn and_where_in_query<S, T>(&mut self, field: S, query: T) -> &mut Self
     where
         S: ToString,
-        T: ToString,
+        T: SqlQuery,
     {
         // Checks
         let field = field.to_string();
         if field.is_empty() {
             return self.set_error(&SqlBuilderError::NoWhereField);
         }
-        if list.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereList(field));
+        let query = match query.sql_query_for(self.tenant.as_deref()) {
+            Ok(query) => query,
+            Err(err) => return self.set_query_error(err),
+        };
+        if query.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereQuery(field));
         }
 
         // Change
-        let list: Vec<String> = list
-            .iter()
-            .map(|v| (*v).to_string())
-            .collect::<Vec<String>>();
-        let list = list.join(", ");
-
         let mut cond = field;
-        cond.push_str(" NOT IN (");
-        cond.push_str(&list);
+        cond.push_str(" IN (");
+        cond.push_str(&query);
         cond.push(')');
-        self.or_where(&cond)
+        self.and_where(&cond)
     }
 
-    /// Add OR field NOT IN (string list) to the last WHERE condition.
+    /// Add WHERE field NOT IN (query).
     ///
     /// ```
     /// # use anyhow::Result;
     /// use sql_builder::{SqlBuilder, quote};
     ///
     /// # fn main() -> Result<()> {
+    /// let query = SqlBuilder::select_from("shop")
+    ///     .field("title")
+    ///     .and_where("sold")
+    ///     .query()?;
+    ///
+    /// assert_eq!("SELECT title FROM shop WHERE sold", &query);
+    ///
     /// let sql = SqlBuilder::select_from("books")
     ///     .field("title")
     ///     .field("price")
-    ///     .or_where_lt("price", 100)
-    ///     .or_where_not_in_quoted("title", &["G", "L", "t"])
+    ///     .and_where_not_in_query("title", &query)
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE price < 100 OR title NOT IN ('G', 'L', 't');", &sql);
-    /// // add                                                          ^^^^^         ^^^^^^^^^^^^^
-    /// // here                                                         field             list
+    /// assert_eq!("SELECT title, price FROM books WHERE title NOT IN (SELECT title FROM shop WHERE sold);", &sql);
+    /// // add                                           ^^^^^         ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+    /// // here                                          field                       query
     /// # Ok(())
     /// # }
     /// ```
-    pub fn or_where_not_in_quoted<S, T>(&mut self, field: S, list: &[T]) -> &mut Self
+    pub fn and_where_not_in_query<S, T>(&mut self, field: S, query: T) -> &mut Self
     where
         S: ToString,
-        T: ToString,
+        T: SqlQuery,
     {
         // Checks
         let field = field.to_string();
         if field.is_empty() {
             return self.set_error(&SqlBuilderError::NoWhereField);
         }
-        if list.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereList(field));
+        let query = match query.sql_query_for(self.tenant.as_deref()) {
+            Ok(query) => query,
+            Err(err) => return self.set_query_error(err),
+        };
+        if query.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereQuery(field));
         }
 
         // Change
-        let list: Vec<String> = list
-            .iter()
-            .map(|v| quote((*v).to_string()))
-            .collect::<Vec<String>>();
-        let list = list.join(", ");
-
         let mut cond = field;
         cond.push_str(" NOT IN (");
-        cond.push_str(&list);
+        cond.push_str(&query);
         cond.push(')');
-        self.or_where(&cond)
+        self.and_where(&cond)
     }
 
-    /// Add OR field IN (query) to the last WHERE condition.
+    /// Add WHERE EXISTS (query).
     ///
     /// ```
     /// # use anyhow::Result;
     /// use sql_builder::SqlBuilder;
     ///
     /// # fn main() -> Result<()> {
-    /// let query = SqlBuilder::select_from("shop")
+    /// let orders = SqlBuilder::select_from("orders AS o")
+    ///     .field("1")
+    ///     .and_where("o.book_id = b.id")
+    ///     .clone();
+    ///
+    /// let sql = SqlBuilder::select_from("books AS b")
     ///     .field("title")
-    ///     .and_where("sold")
-    ///     .query()?;
+    ///     .and_where_exists(&orders)
+    ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title FROM shop WHERE sold", &query);
+    /// assert_eq!("SELECT title FROM books AS b WHERE EXISTS (SELECT 1 FROM orders AS o WHERE o.book_id = b.id);", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_where_exists<T: SqlQuery>(&mut self, query: T) -> &mut Self {
+        match self.nested_query("EXISTS", query) {
+            Some(query) => self.and_where(format!("EXISTS ({})", query)),
+            None => self,
+        }
+    }
+
+    /// Add WHERE NOT EXISTS (query).
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let orders = SqlBuilder::select_from("orders AS o")
+    ///     .field("1")
+    ///     .and_where("o.book_id = b.id")
+    ///     .clone();
+    ///
+    /// let sql = SqlBuilder::select_from("books AS b")
+    ///     .field("title")
+    ///     .and_where_not_exists(&orders)
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title FROM books AS b WHERE NOT EXISTS (SELECT 1 FROM orders AS o WHERE o.book_id = b.id);", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_where_not_exists<T: SqlQuery>(&mut self, query: T) -> &mut Self {
+        match self.nested_query("NOT EXISTS", query) {
+            Some(query) => self.and_where(format!("NOT EXISTS ({})", query)),
+            None => self,
+        }
+    }
+
+    /// Add WHERE field operator ANY (query).
+    /// Operator is one of `=`, `<>`, `!=`, `<`, `<=`, `>`, `>=`.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let authors = SqlBuilder::select_from("authors")
+    ///     .field("id")
+    ///     .and_where_eq("country", "'UK'")
+    ///     .clone();
     ///
     /// let sql = SqlBuilder::select_from("books")
     ///     .field("title")
-    ///     .field("price")
-    ///     .or_where_lt("price", 100)
-    ///     .or_where_in_query("title", &query)
+    ///     .and_where_any("author_id", "=", &authors)
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE price < 100 OR title IN (SELECT title FROM shop WHERE sold);", &sql);
-    /// // add                                                          ^^^^^     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
-    /// // here                                                         field                   query
+    /// assert_eq!("SELECT title FROM books WHERE author_id = ANY (SELECT id FROM authors WHERE country = 'UK');", &sql);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn or_where_in_query<S, T>(&mut self, field: S, query: T) -> &mut Self
+    pub fn and_where_any<S, T>(&mut self, field: S, op: &str, query: T) -> &mut Self
     where
         S: ToString,
-        T: ToString,
+        T: SqlQuery,
     {
-        // Checks
-        let field = field.to_string();
-        if field.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereField);
+        match self.query_cond(field, op, query) {
+            Some((field, query)) => self.and_where(format!("{} {} ANY ({})", field, op, query)),
+            None => self,
         }
-        let query = query.to_string();
-        if query.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereQuery(field));
-        }
-
-        // Change
-        let mut cond = field;
-        cond.push_str(" IN (");
-        cond.push_str(&query);
-        cond.push(')');
-        self.or_where(&cond)
     }
 
-    /// Add OR field NOT IN (query) to the last WHERE condition.
+    /// Add WHERE field operator ALL (query).
+    /// Operator is one of `=`, `<>`, `!=`, `<`, `<=`, `>`, `>=`.
     ///
     /// ```
     /// # use anyhow::Result;
     /// use sql_builder::SqlBuilder;
     ///
     /// # fn main() -> Result<()> {
-    /// let query = SqlBuilder::select_from("shop")
+    /// let sales = SqlBuilder::select_from("sales")
+    ///     .field("price")
+    ///     .and_where_eq("year", 2020)
+    ///     .clone();
+    ///
+    /// let sql = SqlBuilder::select_from("books")
     ///     .field("title")
-    ///     .and_where("sold")
-    ///     .query()?;
+    ///     .and_where_all("price", ">", &sales)
+    ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title FROM shop WHERE sold", &query);
+    /// assert_eq!("SELECT title FROM books WHERE price > ALL (SELECT price FROM sales WHERE year = 2020);", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_where_all<S, T>(&mut self, field: S, op: &str, query: T) -> &mut Self
+    where
+        S: ToString,
+        T: SqlQuery,
+    {
+        match self.query_cond(field, op, query) {
+            Some((field, query)) => self.and_where(format!("{} {} ALL ({})", field, op, query)),
+            None => self,
+        }
+    }
+
+    /// Add WHERE field operator (query).
+    /// Operator is one of `=`, `<>`, `!=`, `<`, `<=`, `>`, `>=`.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let avg = SqlBuilder::select_from("books")
+    ///     .field("AVG(price)")
+    ///     .clone();
     ///
     /// let sql = SqlBuilder::select_from("books")
     ///     .field("title")
-    ///     .field("price")
-    ///     .or_where_lt("price", 100)
-    ///     .or_where_not_in_query("title", &query)
+    ///     .and_where_scalar("price", ">=", &avg)
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE price < 100 OR title NOT IN (SELECT title FROM shop WHERE sold);", &sql);
-    /// // add                                                          ^^^^^         ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
-    /// // here                                                         field                       query
+    /// assert_eq!("SELECT title FROM books WHERE price >= (SELECT AVG(price) FROM books);", &sql);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn or_where_not_in_query<S, T>(&mut self, field: S, query: T) -> &mut Self
+    pub fn and_where_scalar<S, T>(&mut self, field: S, op: &str, query: T) -> &mut Self
     where
         S: ToString,
-        T: ToString,
+        T: SqlQuery,
     {
-        // Checks
-        let field = field.to_string();
-        if field.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereField);
-        }
-        let query = query.to_string();
-        if query.is_empty() {
-            return self.set_error(&SqlBuilderError::NoWhereQuery(field));
+        match self.query_cond(field, op, query) {
+            Some((field, query)) => self.and_where(format!("{} {} ({})", field, op, query)),
+            None => self,
         }
-
-        // Change
-        let mut cond = field;
-        cond.push_str(" NOT IN (");
-        cond.push_str(&query);
-        cond.push(')');
-        self.or_where(&cond)
     }
 
-    /// Add OR field BETWEEN values to the last WHERE condition.
+    /// Add WHERE field BETWEEN values.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -2929,17 +3145,16 @@ impl SqlBuilder {
     /// let sql = SqlBuilder::select_from("books")
     ///     .field("title")
     ///     .field("price")
-    ///     .or_where_between("price", 100, 200)
-    ///     .or_where_between("price", 10_000, 20_000)
+    ///     .and_where_between("price", 10_000, 20_000)
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE price BETWEEN 100 AND 200 OR price BETWEEN 10000 AND 20000;", &sql);
-    /// // add                                           ^^^^^         ^^^     ^^^    ^^^^^         ^^^^^     ^^^^^
-    /// // here                                          field         min     max    field          min       max
+    /// assert_eq!("SELECT title, price FROM books WHERE price BETWEEN 10000 AND 20000;", &sql);
+    /// // add                                           ^^^^^         ^^^^^     ^^^^^
+    /// // here                                          field          min       max
     /// # Ok(())
     /// # }
     /// ```
-    pub fn or_where_between<S, T, U>(&mut self, field: S, min: T, max: U) -> &mut Self
+    pub fn and_where_between<S, T, U>(&mut self, field: S, min: T, max: U) -> &mut Self
     where
         S: ToString,
         T: ToString,
@@ -2965,10 +3180,10 @@ impl SqlBuilder {
         cond.push_str(&min);
         cond.push_str(" AND ");
         cond.push_str(&max);
-        self.or_where(&cond)
+        self.and_where(&cond)
     }
 
-    /// Add OR field NOT BETWEEN values to the last WHERE condition.
+    /// Add WHERE field NOT BETWEEN values.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -2978,17 +3193,16 @@ impl SqlBuilder {
     /// let sql = SqlBuilder::select_from("books")
     ///     .field("title")
     ///     .field("price")
-    ///     .or_where_not_between("price", 100, 200)
-    ///     .or_where_not_between("price", 10_000, 20_000)
+    ///     .and_where_not_between("price", 10_000, 20_000)
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE price NOT BETWEEN 100 AND 200 OR price NOT BETWEEN 10000 AND 20000;", &sql);
-    /// // add                                           ^^^^^             ^^^     ^^^    ^^^^^             ^^^^^     ^^^^^
-    /// // here                                          field             min     max    field              min       max
+    /// assert_eq!("SELECT title, price FROM books WHERE price NOT BETWEEN 10000 AND 20000;", &sql);
+    /// // add                                           ^^^^^             ^^^^^     ^^^^^
+    /// // here                                          field              min       max
     /// # Ok(())
     /// # }
     /// ```
-    pub fn or_where_not_between<S, T, U>(&mut self, field: S, min: T, max: U) -> &mut Self
+    pub fn and_where_not_between<S, T, U>(&mut self, field: S, min: T, max: U) -> &mut Self
     where
         S: ToString,
         T: ToString,
@@ -3014,76 +3228,122 @@ impl SqlBuilder {
         cond.push_str(&min);
         cond.push_str(" AND ");
         cond.push_str(&max);
-        self.or_where(&cond)
+        self.and_where(&cond)
     }
 
-    /// Union query with subquery.
-    /// ORDER BY must be in the last subquery.
+    /// Add WHERE condition if flag is set.
     ///
     /// ```
     /// # use anyhow::Result;
     /// use sql_builder::SqlBuilder;
     ///
     /// # fn main() -> Result<()> {
-    /// let append = SqlBuilder::select_from("books")
+    /// let only_cheap = true;
+    /// let only_new = false;
+    ///
+    /// let sql = SqlBuilder::select_from("books")
     ///     .field("title")
-    ///     .field("price")
-    ///     .and_where("price < 100")
-    ///     .order_asc("title")
-    ///     .query()?;
+    ///     .and_where_if(only_cheap, "price < 100")
+    ///     .and_where_if(only_new, "year > 2020")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title FROM books WHERE price < 100;", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_where_if<S: ToString>(&mut self, flag: bool, cond: S) -> &mut Self {
+        if flag {
+            self.and_where(cond)
+        } else {
+            self
+        }
+    }
+
+    /// Add WHERE condition for equal parts if value is present.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
     ///
+    /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
     ///     .field("title")
-    ///     .field("price")
-    ///     .and_where_like_left("title", "Harry Potter")
-    ///     .order_desc("price")
-    ///     .union(&append)
+    ///     .and_where_eq_opt("price", None::<i32>)
+    ///     .and_where_eq_opt("author", Some("'Tolkien'"))
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' UNION SELECT title, price FROM books WHERE price < 100 ORDER BY title;", &sql);
-    /// // add                                                                            ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
-    /// // here                                                                                                        query
+    /// assert_eq!("SELECT title FROM books WHERE author = 'Tolkien';", &sql);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn union<S: ToString>(&mut self, query: S) -> &mut Self {
-        let append = format!(" UNION {}", &query.to_string());
-        self.unions.push_str(&append);
-        self
+    pub fn and_where_eq_opt<S, T>(&mut self, field: S, value: Option<T>) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match value {
+            Some(value) => self.and_where_eq(field, value),
+            None => self,
+        }
     }
 
-    /// Union query with all subquery.
-    /// ORDER BY must be in the last subquery.
+    /// Add WHERE condition for non-equal parts if value is present.
     ///
     /// ```
     /// # use anyhow::Result;
     /// use sql_builder::SqlBuilder;
     ///
     /// # fn main() -> Result<()> {
-    /// let append = SqlBuilder::select_values(&["'The Great Gatsby'", "124"])
-    ///     .query_values()?;
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .and_where_ne_opt("price", None::<i32>)
+    ///     .and_where_ne_opt("author", Some("'Tolkien'"))
+    ///     .sql()?;
     ///
+    /// assert_eq!("SELECT title FROM books WHERE author <> 'Tolkien';", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_where_ne_opt<S, T>(&mut self, field: S, value: Option<T>) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match value {
+            Some(value) => self.and_where_ne(field, value),
+            None => self,
+        }
+    }
+
+    /// Add WHERE condition for field greater than value if value is present.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
     ///     .field("title")
-    ///     .field("price")
-    ///     .and_where_like_left("title", "Harry Potter")
-    ///     .order_desc("price")
-    ///     .union_all(&append)
+    ///     .and_where_gt_opt("price", None::<i32>)
+    ///     .and_where_gt_opt("year", Some(2000))
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' UNION ALL SELECT 'The Great Gatsby', 124;", &sql);
-    /// // add                                                                                ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
-    /// // here                                                                                           query
+    /// assert_eq!("SELECT title FROM books WHERE year > 2000;", &sql);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn union_all<S: ToString>(&mut self, query: S) -> &mut Self {
-        self.unions.push_str(" UNION ALL ");
-        self.unions.push_str(&query.to_string());
-        self
+    pub fn and_where_gt_opt<S, T>(&mut self, field: S, value: Option<T>) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match value {
+            Some(value) => self.and_where_gt(field, value),
+            None => self,
+        }
     }
 
-    /// Add ORDER BY.
+    /// Add WHERE condition for field not less than value if value is present.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -3092,24 +3352,26 @@ impl SqlBuilder {
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
     ///     .field("title")
-    ///     .field("price")
-    ///     .and_where_like_left("title", "Harry Potter")
-    ///     .order_by("price")
+    ///     .and_where_ge_opt("price", None::<i32>)
+    ///     .and_where_ge_opt("year", Some(2000))
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' ORDER BY price;", &sql);
-    /// // add                                                                               ^^^^^
-    /// // here                                                                              field
+    /// assert_eq!("SELECT title FROM books WHERE year >= 2000;", &sql);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn order_by<S: ToString>(&mut self, field: S) -> &mut Self {
-        let order = field.to_string();
-        self.order_by.push(order);
-        self
+    pub fn and_where_ge_opt<S, T>(&mut self, field: S, value: Option<T>) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match value {
+            Some(value) => self.and_where_ge(field, value),
+            None => self,
+        }
     }
 
-    /// Add ORDER BY ASC.
+    /// Add WHERE condition for field less than value if value is present.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -3118,22 +3380,26 @@ impl SqlBuilder {
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
     ///     .field("title")
-    ///     .field("price")
-    ///     .and_where_like_left("title", "Harry Potter")
-    ///     .order_asc("title")
+    ///     .and_where_lt_opt("price", None::<i32>)
+    ///     .and_where_lt_opt("year", Some(2000))
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' ORDER BY title;", &sql);
-    /// // add                                                                               ^^^^^
-    /// // here                                                                              field
+    /// assert_eq!("SELECT title FROM books WHERE year < 2000;", &sql);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn order_asc<S: ToString>(&mut self, field: S) -> &mut Self {
-        self.order_by(field)
+    pub fn and_where_lt_opt<S, T>(&mut self, field: S, value: Option<T>) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match value {
+            Some(value) => self.and_where_lt(field, value),
+            None => self,
+        }
     }
 
-    /// Add ORDER BY DESC.
+    /// Add WHERE condition for field not greater than value if value is present.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -3142,22 +3408,138 @@ impl SqlBuilder {
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
     ///     .field("title")
+    ///     .and_where_le_opt("price", None::<i32>)
+    ///     .and_where_le_opt("year", Some(2000))
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title FROM books WHERE year <= 2000;", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_where_le_opt<S, T>(&mut self, field: S, value: Option<T>) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match value {
+            Some(value) => self.and_where_le(field, value),
+            None => self,
+        }
+    }
+
+    /// Add WHERE LIKE condition if mask is present.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
     ///     .field("price")
-    ///     .and_where_like_left("title", "Harry Potter")
-    ///     .order_desc("price")
+    ///     .and_where_like_opt("author", None::<&str>)
+    ///     .and_where_like_opt("title", Some("Harry"))
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' ORDER BY price DESC;", &sql);
-    /// // add                                                                               ^^^^^
-    /// // here                                                                              field
+    /// assert_eq!("SELECT price FROM books WHERE title LIKE 'Harry';", &sql);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn order_desc<S: ToString>(&mut self, field: S) -> &mut Self {
-        self.order_by(format!("{} DESC", field.to_string()))
+    pub fn and_where_like_opt<S, T>(&mut self, field: S, mask: Option<T>) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match mask {
+            Some(mask) => self.and_where_like(field, mask),
+            None => self,
+        }
     }
 
-    /// Set LIMIT.
+    /// Add WHERE LIKE %condition if mask is present.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("price")
+    ///     .and_where_like_right_opt("author", None::<&str>)
+    ///     .and_where_like_right_opt("title", Some("Harry"))
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT price FROM books WHERE title LIKE '%Harry' ESCAPE '\\';", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_where_like_right_opt<S, T>(&mut self, field: S, mask: Option<T>) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match mask {
+            Some(mask) => self.and_where_like_right(field, mask),
+            None => self,
+        }
+    }
+
+    /// Add WHERE LIKE condition% if mask is present.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("price")
+    ///     .and_where_like_left_opt("author", None::<&str>)
+    ///     .and_where_like_left_opt("title", Some("Harry"))
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT price FROM books WHERE title LIKE 'Harry%' ESCAPE '\\';", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_where_like_left_opt<S, T>(&mut self, field: S, mask: Option<T>) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match mask {
+            Some(mask) => self.and_where_like_left(field, mask),
+            None => self,
+        }
+    }
+
+    /// Add WHERE LIKE %condition% if mask is present.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("price")
+    ///     .and_where_like_any_opt("author", None::<&str>)
+    ///     .and_where_like_any_opt("title", Some("Harry"))
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT price FROM books WHERE title LIKE '%Harry%' ESCAPE '\\';", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_where_like_any_opt<S, T>(&mut self, field: S, mask: Option<T>) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match mask {
+            Some(mask) => self.and_where_like_any(field, mask),
+            None => self,
+        }
+    }
+
+    /// Add WHERE field IN (list) if list is present and not empty.
     ///
     /// ```
     /// # use anyhow::Result;
@@ -3166,60 +3548,2014 @@ impl SqlBuilder {
     /// # fn main() -> Result<()> {
     /// let sql = SqlBuilder::select_from("books")
     ///     .field("title")
+    ///     .and_where_in_opt("author_id", None::<&[i32]>)
+    ///     .and_where_in_opt("shop_id", Some(&[] as &[i32]))
+    ///     .and_where_in_opt("genre_id", Some(&[1, 2, 3][..]))
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title FROM books WHERE genre_id IN (1, 2, 3);", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_where_in_opt<S, T>(&mut self, field: S, list: Option<&[T]>) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match list {
+            Some(list) if !list.is_empty() => self.and_where_in(field, list),
+            _ => self,
+        }
+    }
+
+    /// Add WHERE field IN (string list) if list is present and not empty.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
     ///     .field("price")
-    ///     .and_where_like_left("title", "Harry Potter")
-    ///     .order_desc("price")
-    ///     .limit(10)
+    ///     .and_where_in_quoted_opt("author", None::<&[&str]>)
+    ///     .and_where_in_quoted_opt("title", Some(&["G", "L"][..]))
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' ORDER BY price DESC LIMIT 10;", &sql);
-    /// // add                                                                                                ^^
-    /// // here                                                                                              limit
+    /// assert_eq!("SELECT price FROM books WHERE title IN ('G', 'L');", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_where_in_quoted_opt<S, T>(&mut self, field: S, list: Option<&[T]>) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match list {
+            Some(list) if !list.is_empty() => self.and_where_in_quoted(field, list),
+            _ => self,
+        }
+    }
+
+    /// Add WHERE field BETWEEN values if bounds are present.
+    /// Only one present bound makes comparison with it.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .and_where_between_opt("price", Some(100), Some(200))
+    ///     .and_where_between_opt("year", Some(2000), None::<i32>)
+    ///     .and_where_between_opt("pages", None::<i32>, Some(300))
+    ///     .and_where_between_opt("rating", None::<i32>, None::<i32>)
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title FROM books WHERE (price BETWEEN 100 AND 200) AND (year >= 2000) AND (pages <= 300);", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_where_between_opt<S, T, U>(
+        &mut self,
+        field: S,
+        min: Option<T>,
+        max: Option<U>,
+    ) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+        U: ToString,
+    {
+        match (min, max) {
+            (Some(min), Some(max)) => self.and_where_between(field, min, max),
+            (Some(min), None) => self.and_where_ge(field, min),
+            (None, Some(max)) => self.and_where_le(field, max),
+            (None, None) => self,
+        }
+    }
+
+    /// Add OR condition to the last WHERE condition.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .field("price")
+    ///     .and_where("price < 10")
+    ///     .or_where("price > 1000")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title, price FROM books WHERE price < 10 OR price > 1000;", &sql);
+    /// // add                                                         ^^^^^^^^^^^^
+    /// // here                                                            cond
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where<S: ToString>(&mut self, cond: S) -> &mut Self {
+        // Checks
+        let cond = cond.to_string();
+        if cond.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereCond);
+        }
+
+        // Change
+        if self.wheres.is_empty() {
+            self.wheres.push(cond);
+        } else if let Some(last) = self.wheres.last_mut() {
+            last.push_str(" OR ");
+            last.push_str(&cond);
+        }
+        self
+    }
+
+    /// Add OR condition of equal parts to the last WHERE condition.
+    /// Use [`or_where_eq_nullable`](Self::or_where_eq_nullable) for nullable values.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{SqlBuilder, quote};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("price")
+    ///     .and_where_eq("title", &quote("Harry Potter and the Philosopher's Stone"))
+    ///     .or_where_eq("title", &quote("Harry Potter and the Chamber of Secrets"))
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT price FROM books WHERE title = 'Harry Potter and the Philosopher''s Stone' OR title = 'Harry Potter and the Chamber of Secrets';", &sql);
+    /// // add                                                                                           ^^^^^   ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+    /// // here                                                                                          field                     value
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where_eq<S, T>(&mut self, field: S, value: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+        let value = value.to_string();
+        if value.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereValue(field));
+        }
+
+        // Change
+        let mut cond = field;
+        cond.push_str(" = ");
+        cond.push_str(&value);
+        self.or_where(&cond)
+    }
+
+    /// Add OR condition of non-equal parts to the last WHERE condition.
+    /// Use [`or_where_ne_nullable`](Self::or_where_ne_nullable) for nullable values.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{SqlBuilder, quote};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("price")
+    ///     .or_where_ne("title", &quote("Harry Potter and the Philosopher's Stone"))
+    ///     .or_where_ne("title", &quote("Harry Potter and the Chamber of Secrets"))
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT price FROM books WHERE title <> 'Harry Potter and the Philosopher''s Stone' OR title <> 'Harry Potter and the Chamber of Secrets';", &sql);
+    /// // add                                    ^^^^^    ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^    ^^^^^    ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+    /// // here                                   field                       value                       field                      value
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where_ne<S, T>(&mut self, field: S, value: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+        let value = value.to_string();
+        if value.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereValue(field));
+        }
+
+        // Change
+        let mut cond = field;
+        cond.push_str(" <> ");
+        cond.push_str(&value);
+        self.or_where(&cond)
+    }
+
+    /// Add OR condition for field greater than value to the last WHERE condition.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .field("price")
+    ///     .and_where_lt("price", 100)
+    ///     .or_where_gt("price", 300)
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title, price FROM books WHERE price < 100 OR price > 300;", &sql);
+    /// // add                                                          ^^^^^   ^^^
+    /// // here                                                         field  value
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where_gt<S, T>(&mut self, field: S, value: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+        let value = value.to_string();
+        if value.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereValue(field));
+        }
+
+        // Change
+        let mut cond = field;
+        cond.push_str(" > ");
+        cond.push_str(&value);
+        self.or_where(&cond)
+    }
+
+    /// Add OR condition for field not less than value to the last WHERE condition.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .field("price")
+    ///     .or_where_lt("price", 100)
+    ///     .or_where_ge("price", 300)
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title, price FROM books WHERE price < 100 OR price >= 300;", &sql);
+    /// // add                                                          ^^^^^    ^^^
+    /// // here                                                         field   value
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where_ge<S, T>(&mut self, field: S, value: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+        let value = value.to_string();
+        if value.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereValue(field));
+        }
+
+        // Change
+        let mut cond = field;
+        cond.push_str(" >= ");
+        cond.push_str(&value);
+        self.or_where(&cond)
+    }
+
+    /// Add OR condition for field less than value to the last WHERE condition.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .field("price")
+    ///     .and_where_lt("price", 100)
+    ///     .or_where_lt("price", 300)
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title, price FROM books WHERE price < 100 OR price < 300;", &sql);
+    /// // add                                                          ^^^^^   ^^^
+    /// // here                                                         field  value
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where_lt<S, T>(&mut self, field: S, value: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+        let value = value.to_string();
+        if value.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereValue(field));
+        }
+
+        // Change
+        let mut cond = field;
+        cond.push_str(" < ");
+        cond.push_str(&value);
+        self.or_where(&cond)
+    }
+
+    /// Add OR condition for field not greater than value to the last WHERE condition.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .field("price")
+    ///     .or_where_le("price", 100)
+    ///     .or_where_ge("price", 300)
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title, price FROM books WHERE price <= 100 OR price >= 300;", &sql);
+    /// // add                                           ^^^^^    ^^^
+    /// // here                                          field   value
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where_le<S, T>(&mut self, field: S, value: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+        let value = value.to_string();
+        if value.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereValue(field));
+        }
+
+        // Change
+        let mut cond = field;
+        cond.push_str(" <= ");
+        cond.push_str(&value);
+        self.or_where(&cond)
+    }
+
+    /// Add OR LIKE condition to the last WHERE condition.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("price")
+    ///     .or_where_like("title", "%Alice's%")
+    ///     .or_where_like("title", "%Philosopher's%")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT price FROM books WHERE title LIKE '%Alice''s%' OR title LIKE '%Philosopher''s%';", &sql);
+    /// // add                                    ^^^^^      ^^^^^^^^^^^^    ^^^^^      ^^^^^^^^^^^^^^^^^^
+    /// // here                                   field          mask        field             mask
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where_like<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+
+        // Change
+        let mut cond = field;
+        cond.push_str(" LIKE '");
+        cond.push_str(&esc(&mask.to_string()));
+        cond.push('\'');
+        self.or_where(&cond)
+    }
+
+    /// Add OR LIKE condition to the last WHERE %condition.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("price")
+    ///     .or_where_like_right("title", "Alice's")
+    ///     .or_where_like_right("title", "Philosopher's")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT price FROM books WHERE title LIKE '%Alice''s' ESCAPE '\\' OR title LIKE '%Philosopher''s' ESCAPE '\\';", &sql);
+    /// // add                                    ^^^^^        ^^^^^^^^     ^^^^^        ^^^^^^^^^^^^^^
+    /// // here                                   field          mask       field             mask
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where_like_right<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+
+        // Change
+        let mut cond = field;
+        cond.push_str(" LIKE ");
+        cond.push_str(&self.like_mask("%", &mask.to_string(), ""));
+        self.or_where(&cond)
+    }
+
+    /// Add OR LIKE condition to the last WHERE condition%.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("price")
+    ///     .or_where_like_left("title", "Alice's")
+    ///     .or_where_like_left("title", "Philosopher's")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT price FROM books WHERE title LIKE 'Alice''s%' ESCAPE '\\' OR title LIKE 'Philosopher''s%' ESCAPE '\\';", &sql);
+    /// // add                                    ^^^^^       ^^^^^^^^      ^^^^^       ^^^^^^^^^^^^^^
+    /// // here                                   field         mask        field            mask
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where_like_left<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+
+        // Change
+        let mut cond = field;
+        cond.push_str(" LIKE ");
+        cond.push_str(&self.like_mask("", &mask.to_string(), "%"));
+        self.or_where(&cond)
+    }
+
+    /// Add OR LIKE condition to the last WHERE %condition%.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("price")
+    ///     .or_where_like_any("title", "Alice's")
+    ///     .or_where_like_any("title", "Philosopher's")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT price FROM books WHERE title LIKE '%Alice''s%' ESCAPE '\\' OR title LIKE '%Philosopher''s%' ESCAPE '\\';", &sql);
+    /// // add                                    ^^^^^      ^^^^^^^^^^^^    ^^^^^      ^^^^^^^^^^^^^^^^^^
+    /// // here                                   field          mask        field             mask
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where_like_any<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+
+        // Change
+        let mut cond = field;
+        cond.push_str(" LIKE ");
+        cond.push_str(&self.like_mask("%", &mask.to_string(), "%"));
+        self.or_where(&cond)
+    }
+
+    /// Add OR NOT LIKE condition to the last WHERE condition.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .and_where_not_like("title", "%Alice's%")
+    ///     .or_where_not_like("title", "%Philosopher's%")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title FROM books WHERE title NOT LIKE '%Alice''s%' OR title NOT LIKE '%Philosopher''s%';", &sql);
+    /// // add                                                                   ^^^^^          ^^^^^^^^^^^^^^^^^^
+    /// // here                                                                  field                 mask
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where_not_like<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+        let mask = mask.to_string();
+        if mask.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereValue(field));
+        }
+
+        // Change
+        let mut cond = field;
+        cond.push_str(" NOT LIKE '");
+        cond.push_str(&esc(&mask));
+        cond.push('\'');
+        self.or_where(&cond)
+    }
+
+    /// Add OR NOT LIKE condition to the last WHERE %condition.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("price")
+    ///     .or_where_not_like_right("title", "Alice's")
+    ///     .or_where_not_like_right("title", "Philosopher's")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT price FROM books WHERE title NOT LIKE '%Alice''s' ESCAPE '\\' OR title NOT LIKE '%Philosopher''s' ESCAPE '\\';", &sql);
+    /// // add                                    ^^^^^            ^^^^^^^^     ^^^^^            ^^^^^^^^^^^^^^
+    /// // here                                   field              mask       field                 mask
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where_not_like_right<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+        let mask = mask.to_string();
+        if mask.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereValue(field));
+        }
+
+        // Change
+        let mut cond = field;
+        cond.push_str(" NOT LIKE ");
+        cond.push_str(&self.like_mask("%", &mask, ""));
+        self.or_where(&cond)
+    }
+
+    /// Add OR NOT LIKE condition to the last WHERE condition%.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("price")
+    ///     .or_where_not_like_left("title", "Alice's")
+    ///     .or_where_not_like_left("title", "Philosopher's")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT price FROM books WHERE title NOT LIKE 'Alice''s%' ESCAPE '\\' OR title NOT LIKE 'Philosopher''s%' ESCAPE '\\';", &sql);
+    /// // add                                    ^^^^^           ^^^^^^^^      ^^^^^           ^^^^^^^^^^^^^^
+    /// // here                                   field             mask        field                mask
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where_not_like_left<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+        let mask = mask.to_string();
+        if mask.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereValue(field));
+        }
+
+        // Change
+        let mut cond = field;
+        cond.push_str(" NOT LIKE ");
+        cond.push_str(&self.like_mask("", &mask, "%"));
+        self.or_where(&cond)
+    }
+
+    /// Add OR NOT LIKE condition to the last WHERE %condition%.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("price")
+    ///     .or_where_not_like_any("title", "Alice's")
+    ///     .or_where_not_like_any("title", "Philosopher's")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT price FROM books WHERE title NOT LIKE '%Alice''s%' ESCAPE '\\' OR title NOT LIKE '%Philosopher''s%' ESCAPE '\\';", &sql);
+    /// // add                                    ^^^^^          ^^^^^^^^^^^^    ^^^^^          ^^^^^^^^^^^^^^^^^^
+    /// // here                                   field              mask        field                 mask
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where_not_like_any<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+        let mask = mask.to_string();
+        if mask.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereValue(field));
+        }
+
+        // Change
+        let mut cond = field;
+        cond.push_str(" NOT LIKE ");
+        cond.push_str(&self.like_mask("%", &mask, "%"));
+        self.or_where(&cond)
+    }
+
+    /// Add OR IS NULL condition to the last WHERE condition.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .and_where_eq("price", 0)
+    ///     .or_where_is_null("price")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title FROM books WHERE price = 0 OR price IS NULL;", &sql);
+    /// // add                                                 ^^^^^
+    /// // here                                                field
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where_is_null<S: ToString>(&mut self, field: S) -> &mut Self {
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+
+        // Change
+        let mut cond = field;
+        cond.push_str(" IS NULL");
+        self.or_where(&cond)
+    }
+
+    /// Add OR IS NOT NULL condition to the last WHERE condition.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .or_where_is_not_null("title")
+    ///     .or_where_is_not_null("price")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title FROM books WHERE title IS NOT NULL OR price IS NOT NULL;", &sql);
+    /// // add                                    ^^^^^                ^^^^^
+    /// // here                                   field                field
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where_is_not_null<S: ToString>(&mut self, field: S) -> &mut Self {
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+
+        // Change
+        let mut cond = field;
+        cond.push_str(" IS NOT NULL");
+        self.or_where(&cond)
+    }
+
+    /// Add OR field IN (list) to the last WHERE condition.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{SqlBuilder, quote};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .field("price")
+    ///     .or_where_lt("price", 100)
+    ///     .or_where_in("title", &[quote("G"), quote("L"), quote("t")])
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title, price FROM books WHERE price < 100 OR title IN ('G', 'L', 't');", &sql);
+    /// // add                                                          ^^^^^     ^^^^^^^^^^^^^
+    /// // here                                                         field         list
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where_in<S, T>(&mut self, field: S, list: &[T]) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+        if list.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereList(field));
+        }
+
+        // Change
+        let list: Vec<String> = list
+            .iter()
+            .map(|v| (*v).to_string())
+            .collect::<Vec<String>>();
+        let list = list.join(", ");
+
+        let mut cond = field;
+        cond.push_str(" IN (");
+        cond.push_str(&list);
+        cond.push(')');
+        self.or_where(&cond)
+    }
+
+    /// Add OR field IN (string list) to the last WHERE condition.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{SqlBuilder, quote};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .field("price")
+    ///     .or_where_lt("price", 100)
+    ///     .or_where_in_quoted("title", &["G", "L", "t"])
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title, price FROM books WHERE price < 100 OR title IN ('G', 'L', 't');", &sql);
+    /// // add                                                          ^^^^^     ^^^^^^^^^^^^^
+    /// // here                                                         field         list
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where_in_quoted<S, T>(&mut self, field: S, list: &[T]) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+        if list.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereList(field));
+        }
+
+        // Change
+        let list: Vec<String> = list
+            .iter()
+            .map(|v| quote((*v).to_string()))
+            .collect::<Vec<String>>();
+        let list = list.join(", ");
+
+        let mut cond = field;
+        cond.push_str(" IN (");
+        cond.push_str(&list);
+        cond.push(')');
+        self.or_where(&cond)
+    }
+
+    /// Add OR field NOT IN (list) to the last WHERE condition.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{SqlBuilder, quote};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .field("price")
+    ///     .or_where_lt("price", 100)
+    ///     .or_where_not_in("title", &[quote("G"), quote("L"), quote("t")])
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title, price FROM books WHERE price < 100 OR title NOT IN ('G', 'L', 't');", &sql);
+    /// // add                                                          ^^^^^         ^^^^^^^^^^^^^
+    /// // here                                                         field             list
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where_not_in<S, T>(&mut self, field: S, list: &[T]) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+        if list.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereList(field));
+        }
+
+        // Change
+        let list: Vec<String> = list
+            .iter()
+            .map(|v| (*v).to_string())
+            .collect::<Vec<String>>();
+        let list = list.join(", ");
+
+        let mut cond = field;
+        cond.push_str(" NOT IN (");
+        cond.push_str(&list);
+        cond.push(')');
+        self.or_where(&cond)
+    }
+
+    /// Add OR field NOT IN (string list) to the last WHERE condition.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{SqlBuilder, quote};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .field("price")
+    ///     .or_where_lt("price", 100)
+    ///     .or_where_not_in_quoted("title", &["G", "L", "t"])
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title, price FROM books WHERE price < 100 OR title NOT IN ('G', 'L', 't');", &sql);
+    /// // add                                                          ^^^^^         ^^^^^^^^^^^^^
+    /// // here                                                         field             list
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where_not_in_quoted<S, T>(&mut self, field: S, list: &[T]) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+        if list.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereList(field));
+        }
+
+        // Change
+        let list: Vec<String> = list
+            .iter()
+            .map(|v| quote((*v).to_string()))
+            .collect::<Vec<String>>();
+        let list = list.join(", ");
+
+        let mut cond = field;
+        cond.push_str(" NOT IN (");
+        cond.push_str(&list);
+        cond.push(')');
+        self.or_where(&cond)
+    }
+
+    /// Add OR field IN (query) to the last WHERE condition.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let query = SqlBuilder::select_from("shop")
+    ///     .field("title")
+    ///     .and_where("sold")
+    ///     .query()?;
+    ///
+    /// assert_eq!("SELECT title FROM shop WHERE sold", &query);
+    ///
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .field("price")
+    ///     .or_where_lt("price", 100)
+    ///     .or_where_in_query("title", &query)
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title, price FROM books WHERE price < 100 OR title IN (SELECT title FROM shop WHERE sold);", &sql);
+    /// // add                                                          ^^^^^     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+    /// // here                                                         field                   query
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where_in_query<S, T>(&mut self, field: S, query: T) -> &mut Self
+    where
+        S: ToString,
+        T: SqlQuery,
+    {
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+        let query = match query.sql_query_for(self.tenant.as_deref()) {
+            Ok(query) => query,
+            Err(err) => return self.set_query_error(err),
+        };
+        if query.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereQuery(field));
+        }
+
+        // Change
+        let mut cond = field;
+        cond.push_str(" IN (");
+        cond.push_str(&query);
+        cond.push(')');
+        self.or_where(&cond)
+    }
+
+    /// Add OR field NOT IN (query) to the last WHERE condition.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let query = SqlBuilder::select_from("shop")
+    ///     .field("title")
+    ///     .and_where("sold")
+    ///     .query()?;
+    ///
+    /// assert_eq!("SELECT title FROM shop WHERE sold", &query);
+    ///
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .field("price")
+    ///     .or_where_lt("price", 100)
+    ///     .or_where_not_in_query("title", &query)
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title, price FROM books WHERE price < 100 OR title NOT IN (SELECT title FROM shop WHERE sold);", &sql);
+    /// // add                                                          ^^^^^         ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+    /// // here                                                         field                       query
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where_not_in_query<S, T>(&mut self, field: S, query: T) -> &mut Self
+    where
+        S: ToString,
+        T: SqlQuery,
+    {
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+        let query = match query.sql_query_for(self.tenant.as_deref()) {
+            Ok(query) => query,
+            Err(err) => return self.set_query_error(err),
+        };
+        if query.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereQuery(field));
+        }
+
+        // Change
+        let mut cond = field;
+        cond.push_str(" NOT IN (");
+        cond.push_str(&query);
+        cond.push(')');
+        self.or_where(&cond)
+    }
+
+    /// Add OR EXISTS (query) to the last WHERE condition.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let orders = SqlBuilder::select_from("orders AS o")
+    ///     .field("1")
+    ///     .and_where("o.book_id = b.id")
+    ///     .clone();
+    ///
+    /// let sql = SqlBuilder::select_from("books AS b")
+    ///     .field("title")
+    ///     .and_where_gt("price", 100)
+    ///     .or_where_exists(&orders)
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title FROM books AS b WHERE price > 100 OR EXISTS (SELECT 1 FROM orders AS o WHERE o.book_id = b.id);", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where_exists<T: SqlQuery>(&mut self, query: T) -> &mut Self {
+        match self.nested_query("EXISTS", query) {
+            Some(query) => self.or_where(format!("EXISTS ({})", query)),
+            None => self,
+        }
+    }
+
+    /// Add OR NOT EXISTS (query) to the last WHERE condition.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let orders = SqlBuilder::select_from("orders AS o")
+    ///     .field("1")
+    ///     .and_where("o.book_id = b.id")
+    ///     .clone();
+    ///
+    /// let sql = SqlBuilder::select_from("books AS b")
+    ///     .field("title")
+    ///     .and_where_gt("price", 100)
+    ///     .or_where_not_exists(&orders)
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title FROM books AS b WHERE price > 100 OR NOT EXISTS (SELECT 1 FROM orders AS o WHERE o.book_id = b.id);", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where_not_exists<T: SqlQuery>(&mut self, query: T) -> &mut Self {
+        match self.nested_query("NOT EXISTS", query) {
+            Some(query) => self.or_where(format!("NOT EXISTS ({})", query)),
+            None => self,
+        }
+    }
+
+    /// Add OR field operator ANY (query) to the last WHERE condition.
+    /// Operator is one of `=`, `<>`, `!=`, `<`, `<=`, `>`, `>=`.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let authors = SqlBuilder::select_from("authors")
+    ///     .field("id")
+    ///     .and_where_eq("country", "'UK'")
+    ///     .clone();
+    ///
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .and_where_gt("price", 100)
+    ///     .or_where_any("author_id", "=", &authors)
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title FROM books WHERE price > 100 OR author_id = ANY (SELECT id FROM authors WHERE country = 'UK');", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where_any<S, T>(&mut self, field: S, op: &str, query: T) -> &mut Self
+    where
+        S: ToString,
+        T: SqlQuery,
+    {
+        match self.query_cond(field, op, query) {
+            Some((field, query)) => self.or_where(format!("{} {} ANY ({})", field, op, query)),
+            None => self,
+        }
+    }
+
+    /// Add OR field operator ALL (query) to the last WHERE condition.
+    /// Operator is one of `=`, `<>`, `!=`, `<`, `<=`, `>`, `>=`.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sales = SqlBuilder::select_from("sales")
+    ///     .field("price")
+    ///     .and_where_eq("year", 2020)
+    ///     .clone();
+    ///
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .and_where_gt("price", 100)
+    ///     .or_where_all("price", ">", &sales)
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title FROM books WHERE price > 100 OR price > ALL (SELECT price FROM sales WHERE year = 2020);", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where_all<S, T>(&mut self, field: S, op: &str, query: T) -> &mut Self
+    where
+        S: ToString,
+        T: SqlQuery,
+    {
+        match self.query_cond(field, op, query) {
+            Some((field, query)) => self.or_where(format!("{} {} ALL ({})", field, op, query)),
+            None => self,
+        }
+    }
+
+    /// Add OR field operator (query) to the last WHERE condition.
+    /// Operator is one of `=`, `<>`, `!=`, `<`, `<=`, `>`, `>=`.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let avg = SqlBuilder::select_from("books")
+    ///     .field("AVG(price)")
+    ///     .clone();
+    ///
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .and_where_gt("price", 100)
+    ///     .or_where_scalar("price", ">=", &avg)
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title FROM books WHERE price > 100 OR price >= (SELECT AVG(price) FROM books);", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where_scalar<S, T>(&mut self, field: S, op: &str, query: T) -> &mut Self
+    where
+        S: ToString,
+        T: SqlQuery,
+    {
+        match self.query_cond(field, op, query) {
+            Some((field, query)) => self.or_where(format!("{} {} ({})", field, op, query)),
+            None => self,
+        }
+    }
+
+    /// Add OR field BETWEEN values to the last WHERE condition.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .field("price")
+    ///     .or_where_between("price", 100, 200)
+    ///     .or_where_between("price", 10_000, 20_000)
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title, price FROM books WHERE price BETWEEN 100 AND 200 OR price BETWEEN 10000 AND 20000;", &sql);
+    /// // add                                           ^^^^^         ^^^     ^^^    ^^^^^         ^^^^^     ^^^^^
+    /// // here                                          field         min     max    field          min       max
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where_between<S, T, U>(&mut self, field: S, min: T, max: U) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+        U: ToString,
+    {
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+        let min = min.to_string();
+        if min.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereValue(field));
+        }
+        let max = max.to_string();
+        if max.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereValue(field));
+        }
+
+        // Change
+        let mut cond = field;
+        cond.push_str(" BETWEEN ");
+        cond.push_str(&min);
+        cond.push_str(" AND ");
+        cond.push_str(&max);
+        self.or_where(&cond)
+    }
+
+    /// Add OR field NOT BETWEEN values to the last WHERE condition.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .field("price")
+    ///     .or_where_not_between("price", 100, 200)
+    ///     .or_where_not_between("price", 10_000, 20_000)
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title, price FROM books WHERE price NOT BETWEEN 100 AND 200 OR price NOT BETWEEN 10000 AND 20000;", &sql);
+    /// // add                                           ^^^^^             ^^^     ^^^    ^^^^^             ^^^^^     ^^^^^
+    /// // here                                          field             min     max    field              min       max
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or_where_not_between<S, T, U>(&mut self, field: S, min: T, max: U) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+        U: ToString,
+    {
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+        let min = min.to_string();
+        if min.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereValue(field));
+        }
+        let max = max.to_string();
+        if max.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereValue(field));
+        }
+
+        // Change
+        let mut cond = field;
+        cond.push_str(" NOT BETWEEN ");
+        cond.push_str(&min);
+        cond.push_str(" AND ");
+        cond.push_str(&max);
+        self.or_where(&cond)
+    }
+
+    /// Union query with subquery.
+    /// ORDER BY, LIMIT and OFFSET of the builder apply to the whole result,
+    /// queries are parenthesized then like in [`CompoundBuilder`](crate::compound::CompoundBuilder).
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let append = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .field("price")
+    ///     .and_where("price < 100")
+    ///     .order_asc("title")
+    ///     .query()?;
+    ///
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .field("price")
+    ///     .and_where_like_left("title", "Harry Potter")
+    ///     .order_desc("price")
+    ///     .union(&append)
+    ///     .sql()?;
+    ///
+    /// assert_eq!("(SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' ESCAPE '\\') UNION (SELECT title, price FROM books WHERE price < 100 ORDER BY title) ORDER BY price DESC;", &sql);
+    /// // add                                                                                ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+    /// // here                                                                                                           query
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn union<S: SqlQuery>(&mut self, query: S) -> &mut Self {
+        match query.sql_query_for(self.tenant.as_deref()) {
+            Ok(query) => {
+                self.unions.push(("UNION", query));
+                self
+            }
+            Err(err) => self.set_query_error(err),
+        }
+    }
+
+    /// Union query with all subquery.
+    /// ORDER BY, LIMIT and OFFSET of the builder apply to the whole result.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let append = SqlBuilder::select_values(&["'The Great Gatsby'", "124"])
+    ///     .query_values()?;
+    ///
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .field("price")
+    ///     .and_where_like_left("title", "Harry Potter")
+    ///     .union_all(&append)
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' ESCAPE '\\' UNION ALL SELECT 'The Great Gatsby', 124;", &sql);
+    /// // add                                                                                ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+    /// // here                                                                                           query
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn union_all<S: SqlQuery>(&mut self, query: S) -> &mut Self {
+        match query.sql_query_for(self.tenant.as_deref()) {
+            Ok(query) => {
+                self.unions.push(("UNION ALL", query));
+                self
+            }
+            Err(err) => self.set_query_error(err),
+        }
+    }
+
+    /// Add ORDER BY.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .field("price")
+    ///     .and_where_like_left("title", "Harry Potter")
+    ///     .order_by("price")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' ESCAPE '\\' ORDER BY price;", &sql);
+    /// // add                                                                               ^^^^^
+    /// // here                                                                              field
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn order_by<S: ToString>(&mut self, field: S) -> &mut Self {
+        let order = field.to_string();
+        self.order_by.push(order);
+        self
+    }
+
+    /// Add ORDER BY ASC.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .field("price")
+    ///     .and_where_like_left("title", "Harry Potter")
+    ///     .order_asc("title")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' ESCAPE '\\' ORDER BY title;", &sql);
+    /// // add                                                                               ^^^^^
+    /// // here                                                                              field
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn order_asc<S: ToString>(&mut self, field: S) -> &mut Self {
+        self.order_by(field)
+    }
+
+    /// Add ORDER BY DESC.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .field("price")
+    ///     .and_where_like_left("title", "Harry Potter")
+    ///     .order_desc("price")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' ESCAPE '\\' ORDER BY price DESC;", &sql);
+    /// // add                                                                               ^^^^^
+    /// // here                                                                              field
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn order_desc<S: ToString>(&mut self, field: S) -> &mut Self {
+        self.order_by(format!("{} DESC", field.to_string()))
+    }
+
+    /// Set LIMIT.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .field("price")
+    ///     .and_where_like_left("title", "Harry Potter")
+    ///     .order_desc("price")
+    ///     .limit(10)
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' ESCAPE '\\' ORDER BY price DESC LIMIT 10;", &sql);
+    /// // add                                                                                                ^^
+    /// // here                                                                                              limit
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn limit<S: ToString>(&mut self, limit: S) -> &mut Self {
+        self.limit = Some(limit.to_string());
+        self
+    }
+
+    /// Set OFFSET.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .field("price")
+    ///     .and_where_like_left("title", "Harry Potter")
+    ///     .order_desc("price")
+    ///     .limit(10)
+    ///     .offset(100)
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' ESCAPE '\\' ORDER BY price DESC LIMIT 10 OFFSET 100;", &sql);
+    /// // add                                                                                                          ^^^
+    /// // here                                                                                                        offset
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn offset<S: ToString>(&mut self, offset: S) -> &mut Self {
+        self.offset = Some(offset.to_string());
+        self
+    }
+
+    /// Apply changes to the builder if flag is set.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let newest_first = true;
+    ///
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .apply_if(newest_first, |b| {
+    ///         b.order_desc("year").limit(10);
+    ///     })
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT title FROM books ORDER BY year DESC LIMIT 10;", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn apply_if<F>(&mut self, flag: bool, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut Self),
+    {
+        if flag {
+            f(self);
+        }
+        self
+    }
+
+    /// Get table name or FROM part.
+    ///
+    /// ```
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// let builder = SqlBuilder::select_from("books");
+    ///
+    /// assert_eq!("books", builder.get_table());
+    /// ```
+    pub fn get_table(&self) -> &str {
+        &self.table
+    }
+
+    /// Replace table name or FROM part.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("events")
+    ///     .and_where_eq("kind", "'login'")
+    ///     .replace_table("events_2024_01")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM events_2024_01 WHERE kind = 'login';", &sql);
+    /// // add                  ^^^^^^^^^^^^^^
+    /// // here                     table
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn replace_table<S: ToString>(&mut self, table: S) -> &mut Self {
+        self.table = table.to_string();
+        self
+    }
+
+    /// Check if DISTINCT is set.
+    pub fn is_distinct(&self) -> bool {
+        self.distinct
+    }
+
+    /// Remove DISTINCT.
+    pub fn clear_distinct(&mut self) -> &mut Self {
+        self.distinct = false;
+        self
+    }
+
+    /// Get fields.
+    ///
+    /// ```
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// let builder = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .field("price")
+    ///     .clone();
+    ///
+    /// assert_eq!(&["title", "price"], builder.get_fields());
+    /// ```
+    pub fn get_fields(&self) -> &[String] {
+        &self.fields
+    }
+
+    /// Remove all fields.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("title")
+    ///     .clear_fields()
+    ///     .field("id")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT id FROM books;", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn clear_fields(&mut self) -> &mut Self {
+        self.fields.clear();
+        self
+    }
+
+    /// Get SET parts of UPDATE.
+    pub fn get_sets(&self) -> &[String] {
+        &self.sets
+    }
+
+    /// Get JOIN parts.
+    ///
+    /// ```
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// let builder = SqlBuilder::select_from("books")
+    ///     .left_join("shops")
+    ///     .on("books.id = shops.book")
+    ///     .clone();
+    ///
+    /// assert_eq!(vec!["LEFT JOIN shops ON books.id = shops.book"], builder.get_joins());
+    /// ```
+    pub fn get_joins(&self) -> Vec<String> {
+        self.joins.iter().map(|join| join.to_string()).collect()
+    }
+
+    /// Remove all JOIN parts.
+    pub fn clear_joins(&mut self) -> &mut Self {
+        self.joins.clear();
+        self
+    }
+
+    /// Get WHERE conditions joined with AND.
+    /// Scope and tenant conditions are added at build time and are not included.
+    ///
+    /// ```
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// let builder = SqlBuilder::select_from("books")
+    ///     .and_where_gt("price", 100)
+    ///     .and_where_eq("title", "'Dune'")
+    ///     .or_where_eq("title", "'Emma'")
+    ///     .clone();
+    ///
+    /// assert_eq!(&["price > 100", "title = 'Dune' OR title = 'Emma'"], builder.get_wheres());
+    /// ```
+    pub fn get_wheres(&self) -> &[String] {
+        &self.wheres
+    }
+
+    /// Remove WHERE conditions equal to the given one.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .and_where("deleted_at IS NULL")
+    ///     .and_where_gt("price", 100)
+    ///     .remove_where("deleted_at IS NULL")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM books WHERE price > 100;", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn remove_where<S: ToString>(&mut self, cond: S) -> &mut Self {
+        let cond = cond.to_string();
+        self.wheres.retain(|w| w != &cond);
+        self
+    }
+
+    /// Remove all WHERE conditions.
+    pub fn clear_where(&mut self) -> &mut Self {
+        self.wheres.clear();
+        self
+    }
+
+    /// Get GROUP BY fields without ROLLUP, CUBE and GROUPING SETS parts.
+    pub fn get_group_by(&self) -> &[String] {
+        &self.group_by
+    }
+
+    /// Get HAVING conditions joined with AND.
+    pub fn get_havings(&self) -> &[String] {
+        &self.havings
+    }
+
+    /// Remove GROUP BY fields with ROLLUP, CUBE and GROUPING SETS parts.
+    /// HAVING conditions are kept, use [`clear_having`](Self::clear_having) to remove them.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .field("price")
+    ///     .group_by("price")
+    ///     .having("MAX(price) > 100")
+    ///     .clear_group_by()
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT price FROM books HAVING MAX(price) > 100;", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn clear_group_by(&mut self) -> &mut Self {
+        self.group_by.clear();
+        self.groupings.clear();
+        self
+    }
+
+    /// Remove all HAVING conditions.
+    pub fn clear_having(&mut self) -> &mut Self {
+        self.havings.clear();
+        self
+    }
+
+    /// Get UNION parts.
+    pub fn get_unions(&self) -> String {
+        self.unions
+            .iter()
+            .map(|(op, query)| format!(" {} {}", op, query))
+            .collect()
+    }
+
+    /// Remove all UNION parts.
+    pub fn clear_unions(&mut self) -> &mut Self {
+        self.unions.clear();
+        self
+    }
+
+    /// Get ORDER BY parts.
+    ///
+    /// ```
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// let builder = SqlBuilder::select_from("books")
+    ///     .order_desc("price")
+    ///     .order_asc("title")
+    ///     .clone();
+    ///
+    /// assert_eq!(&["price DESC", "title"], builder.get_order_by());
+    /// ```
+    pub fn get_order_by(&self) -> &[String] {
+        &self.order_by
+    }
+
+    /// Remove ORDER BY parts.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .order_desc("price")
+    ///     .clear_order()
+    ///     .order_asc("title")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM books ORDER BY title;", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn clear_order(&mut self) -> &mut Self {
+        self.order_by.clear();
+        self
+    }
+
+    /// Get LIMIT.
+    pub fn get_limit(&self) -> Option<&str> {
+        self.limit.as_deref()
+    }
+
+    /// Remove LIMIT.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let mut builder = SqlBuilder::select_from("books");
+    /// builder.limit(10).offset(20);
+    ///
+    /// assert_eq!(Some("10"), builder.get_limit());
+    ///
+    /// let sql = builder.clear_limit().clear_offset().sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM books;", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn clear_limit(&mut self) -> &mut Self {
+        self.limit = None;
+        self
+    }
+
+    /// Get OFFSET.
+    pub fn get_offset(&self) -> Option<&str> {
+        self.offset.as_deref()
+    }
+
+    /// Remove OFFSET.
+    pub fn clear_offset(&mut self) -> &mut Self {
+        self.offset = None;
+        self
+    }
+
+    /// Get RETURNING field.
+    pub fn get_returning(&self) -> Option<&str> {
+        self.returning.as_deref()
+    }
+
+    /// Remove RETURNING field.
+    pub fn clear_returning(&mut self) -> &mut Self {
+        self.returning = None;
+        self
+    }
+
+    /// Get target SQL dialect.
+    pub fn get_dialect(&self) -> Dialect {
+        self.dialect
+    }
+
+    /// Set error during build.
+    fn set_error(&mut self, err: &SqlBuilderError) -> &mut Self {
+        self.error = Some(err.clone());
+        self
+    }
+
+    /// Build nested query, set error if it fails or is empty.
+    fn nested_query<T: SqlQuery>(&mut self, field: &str, query: T) -> Option<String> {
+        match query.sql_query_for(self.tenant.as_deref()) {
+            Ok(query) if query.is_empty() => {
+                self.set_error(&SqlBuilderError::NoWhereQuery(field.to_string()));
+                None
+            }
+            Ok(query) => Some(query),
+            Err(err) => {
+                self.set_query_error(err);
+                None
+            }
+        }
+    }
+
+    /// Check field and operator of comparison with nested query and build it.
+    fn query_cond<S, T>(&mut self, field: S, op: &str, query: T) -> Option<(String, String)>
+    where
+        S: ToString,
+        T: SqlQuery,
+    {
+        let field = field.to_string();
+        if field.is_empty() {
+            self.set_error(&SqlBuilderError::NoWhereField);
+            return None;
+        }
+        if !["=", "<>", "!=", "<", "<=", ">", ">="].contains(&op) {
+            self.set_error(&SqlBuilderError::UnknownOperator(op.to_string()));
+            return None;
+        }
+        let query = self.nested_query(&field, query)?;
+        Some((field, query))
+    }
+
+    /// Set error of nested query build.
+    fn set_query_error(&mut self, err: anyhow::Error) -> &mut Self {
+        let err = match err.downcast::<SqlBuilderError>() {
+            Ok(err) => err,
+            Err(err) => SqlBuilderError::InvalidQuery(err.to_string()),
+        };
+        self.set_error(&err)
+    }
+
+    /// Clean error code.
+    pub fn drop_error(&mut self) -> &mut Self {
+        self.error = None;
+        self
+    }
+
+    /// Do not apply default scopes and soft-delete.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{scope, SqlBuilder};
+    ///
+    /// # fn main() -> Result<()> {
+    /// scope::register_soft_delete("reviews", "deleted_at");
+    ///
+    /// let sql = SqlBuilder::select_from("reviews")
+    ///     .unscoped()
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM reviews;", &sql);
+    ///
+    /// let sql = SqlBuilder::delete_from("reviews")
+    ///     .and_where("id = 42")
+    ///     .unscoped()
+    ///     .sql()?;
+    ///
+    /// assert_eq!("DELETE FROM reviews WHERE id = 42;", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn unscoped(&mut self) -> &mut Self {
+        self.unscoped = true;
+        self
+    }
+
+    /// Do not apply named default scope.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{scope, SqlBuilder};
+    ///
+    /// # fn main() -> Result<()> {
+    /// scope::register_scope("orders", "paid", &["paid"]);
+    /// scope::register_soft_delete("orders", "deleted_at");
+    ///
+    /// let sql = SqlBuilder::select_from("orders")
+    ///     .unscope(scope::SOFT_DELETE_SCOPE)
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM orders WHERE paid;", &sql);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn limit<S: ToString>(&mut self, limit: S) -> &mut Self {
-        self.limit = Some(limit.to_string());
+    pub fn unscope<S: ToString>(&mut self, name: S) -> &mut Self {
+        self.unscopes.push(name.to_string());
         self
     }
 
-    /// Set OFFSET.
+    /// Set tenant for tenant-scoped tables.
+    /// Builder subqueries without own tenant take the tenant of the builder
+    /// when they are added, so set it before adding them.
     ///
     /// ```
     /// # use anyhow::Result;
-    /// use sql_builder::SqlBuilder;
+    /// use sql_builder::{tenant, SqlBuilder};
     ///
     /// # fn main() -> Result<()> {
-    /// let sql = SqlBuilder::select_from("books")
-    ///     .field("title")
-    ///     .field("price")
-    ///     .and_where_like_left("title", "Harry Potter")
-    ///     .order_desc("price")
-    ///     .limit(10)
-    ///     .offset(100)
+    /// tenant::register_tenant_table("projects", "tenant_id");
+    ///
+    /// let sql = SqlBuilder::select_from("projects AS p")
+    ///     .field("p.name")
+    ///     .and_where("p.active")
+    ///     .tenant(7)
     ///     .sql()?;
     ///
-    /// assert_eq!("SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' ORDER BY price DESC LIMIT 10 OFFSET 100;", &sql);
-    /// // add                                                                                                          ^^^
-    /// // here                                                                                                        offset
+    /// assert_eq!("SELECT p.name FROM projects AS p WHERE (p.active) AND (p.tenant_id = 7);", &sql);
+    /// // add                                                            ^^^^^^^^^^^^^^^^
+    /// // here                                                            tenant filter
+    ///
+    /// let sql = SqlBuilder::insert_into("projects")
+    ///     .field("name")
+    ///     .values(&["'Apollo'"])
+    ///     .tenant(7)
+    ///     .sql()?;
+    ///
+    /// assert_eq!("INSERT INTO projects (name, tenant_id) VALUES ('Apollo', 7);", &sql);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn offset<S: ToString>(&mut self, offset: S) -> &mut Self {
-        self.offset = Some(offset.to_string());
+    pub fn tenant<T: SqlArg>(&mut self, id: T) -> &mut Self {
+        self.tenant = Some(id.sql_arg());
         self
     }
 
-    /// Set error during build.
-    fn set_error(&mut self, err: &SqlBuilderError) -> &mut Self {
-        self.error = Some(err.clone());
-        self
+    /// Get builder with the outer tenant unless it has own one
+    pub(crate) fn inherit_tenant(&self, tenant: Option<&str>) -> Cow<'_, Self> {
+        match (&self.tenant, tenant) {
+            (None, Some(tenant)) => {
+                let mut builder = self.clone();
+                builder.tenant = Some(tenant.to_string());
+                Cow::Owned(builder)
+            }
+            _ => Cow::Borrowed(self),
+        }
     }
 
-    /// Clean error code.
-    pub fn drop_error(&mut self) -> &mut Self {
-        self.error = None;
-        self
+    /// Apply default scopes to FROM and JOIN parts, get scope conditions for WHERE part
+    fn scoped_sources(&self, joins: &mut [Join]) -> (String, Vec<String>) {
+        if self.unscoped {
+            return (self.table.clone(), Vec::new());
+        }
+        scope::scope_sources(&self.table, joins, &self.unscopes)
     }
 
     /// Build complete SQL command.
@@ -3338,6 +5674,60 @@ impl SqlBuilder {
         Ok(text)
     }
 
+    /// Make query counting rows of SELECT query.
+    /// ORDER BY, LIMIT and OFFSET are dropped. Queries with DISTINCT,
+    /// GROUP BY, HAVING or UNION are counted in subquery.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let mut query = SqlBuilder::select_from("books");
+    /// query
+    ///     .field("title")
+    ///     .and_where_gt("price", 100)
+    ///     .order_asc("title")
+    ///     .limit(10)
+    ///     .offset(20);
+    ///
+    /// let sql = query.count_query().sql()?;
+    ///
+    /// assert_eq!("SELECT COUNT(*) FROM books WHERE price > 100;", &sql);
+    ///
+    /// let sql = query.distinct().count_query().sql()?;
+    ///
+    /// assert_eq!("SELECT COUNT(*) FROM (SELECT DISTINCT title FROM books WHERE price > 100) AS t;", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn count_query(&self) -> Self {
+        let mut query = self.clone();
+        query.order_by.clear();
+        query.limit = None;
+        query.offset = None;
+
+        let grouped = query.distinct
+            || !query.group_by.is_empty()
+            || !query.groupings.is_empty()
+            || !query.havings.is_empty()
+            || !query.unions.is_empty();
+        if !grouped {
+            query.fields = vec!["COUNT(*)".to_string()];
+            return query;
+        }
+
+        let mut count = SqlBuilder::select_from("");
+        count.field("COUNT(*)").dialect(self.dialect);
+        match query.subquery_as("t") {
+            Ok(table) => count.table = table,
+            Err(err) => {
+                count.set_query_error(err);
+            }
+        }
+        count
+    }
+
     /// SQL command generator for query or subquery.
     ///
     /// ```
@@ -3367,6 +5757,25 @@ impl SqlBuilder {
             return Err(err.clone().into());
         }
 
+        // UNION parts with ORDER BY, LIMIT or OFFSET of the whole result
+        let outer = !self.order_by.is_empty() || self.limit.is_some() || self.offset.is_some();
+        if !self.unions.is_empty() && outer {
+            let mut first = self.clone();
+            first.unions.clear();
+            first.order_by.clear();
+            first.limit = None;
+            first.offset = None;
+            return CompoundBuilder::from_unions(
+                first.query()?,
+                &self.unions,
+                &self.order_by,
+                &self.limit,
+                &self.offset,
+                self.dialect,
+            )
+            .query();
+        }
+
         // Distinct results
         let distinct = if self.distinct { " DISTINCT" } else { "" };
 
@@ -3377,30 +5786,68 @@ impl SqlBuilder {
             self.fields.join(", ")
         };
 
+        // Make tenant filter
+        let (mut tenant_wheres, mut joins) =
+            tenant::tenant_filter(&self.table, &self.joins, self.tenant.as_deref())?;
+
+        // Make default scopes
+        let (table, mut scope_wheres) = self.scoped_sources(&mut joins);
+
         // Make JOIN parts
-        let joins = if self.joins.is_empty() {
+        let joins = if joins.is_empty() {
             String::new()
         } else {
-            format!(" {}", self.joins.join(" "))
+            let joins: Vec<String> = joins.iter().map(|join| join.to_string()).collect();
+            format!(" {}", joins.join(" "))
         };
 
         // Make GROUP BY part
-        let group_by = if self.group_by.is_empty() {
+        let mut group_by = self.group_by.clone();
+        let mut rollup = "";
+        for (i, (pos, grouping)) in self.groupings.iter().enumerate() {
+            let part = match (grouping, self.dialect) {
+                (Grouping::Rollup(fields), Dialect::MySql) => {
+                    rollup = " WITH ROLLUP";
+                    fields.clone()
+                }
+                (Grouping::Rollup(fields), _) => format!("ROLLUP ({})", fields),
+                (Grouping::Cube(fields), _) => format!("CUBE ({})", fields),
+                (Grouping::Sets(sets), _) => format!("GROUPING SETS ({})", sets),
+            };
+            group_by.insert(pos + i, part);
+        }
+        let group_by = if group_by.is_empty() {
             String::new()
         } else {
-            let having = if let Some(having) = &self.having {
-                format!(" HAVING {}", having)
-            } else {
-                String::new()
-            };
-            format!(" GROUP BY {}{}", self.group_by.join(", "), having)
+            format!(" GROUP BY {}{}", group_by.join(", "), rollup)
+        };
+
+        // Make HAVING part
+        let having = match self.havings.len() {
+            0 => String::new(),
+            1 => format!(" HAVING {}", self.havings[0]),
+            _ => {
+                let havings: Vec<String> =
+                    self.havings.iter().map(|h| format!("({})", h)).collect();
+                format!(" HAVING {}", havings.join(" AND "))
+            }
         };
 
         // Make WHERE part
-        let wheres = SqlBuilder::make_wheres(&self.wheres);
+        let mut wheres = self.wheres.clone();
+        wheres.append(&mut scope_wheres);
+        wheres.append(&mut tenant_wheres);
+        let wheres = SqlBuilder::make_wheres(&wheres);
+
+        // Make UNION parts
+        let unions: String = self
+            .unions
+            .iter()
+            .map(|(op, query)| format!(" {} {}", op, query))
+            .collect();
 
         // Make ORDER BY part
-        let order_by = if self.order_by.is_empty() || !self.unions.is_empty() {
+        let order_by = if self.order_by.is_empty() {
             String::new()
         } else {
             format!(" ORDER BY {}", self.order_by.join(", "))
@@ -3419,14 +5866,15 @@ impl SqlBuilder {
         };
 
         // Make SQL
-        let sql = format!("SELECT{distinct} {fields} FROM {table}{joins}{wheres}{group_by}{unions}{order_by}{limit}{offset}",
+        let sql = format!("SELECT{distinct} {fields} FROM {table}{joins}{wheres}{group_by}{having}{unions}{order_by}{limit}{offset}",
                           distinct = distinct,
                           fields = fields,
-                          table = &self.table,
+                          table = table,
                           joins = joins,
                           group_by = group_by,
+                          having = having,
                           wheres = wheres,
-                          unions = &self.unions,
+                          unions = unions,
                           order_by = order_by,
                           limit = limit,
                           offset = offset,
@@ -3434,6 +5882,14 @@ impl SqlBuilder {
         Ok(sql)
     }
 
+    /// Make query of SELECT statement with or without a table
+    pub(crate) fn query_any(&self) -> Result<String> {
+        match self.statement {
+            Statement::SelectValues => self.query_values(),
+            _ => self.query(),
+        }
+    }
+
     /// SQL command generator for query or subquery without a table.
     ///
     /// ```
@@ -3462,6 +5918,17 @@ impl SqlBuilder {
         Ok(sql)
     }
 
+    /// Get tenant column and value added to INSERT rows
+    pub(crate) fn insert_tenant(&self) -> Result<Option<(String, String)>, SqlBuilderError> {
+        match tenant::tenant_column(&self.table) {
+            Some(column) if !self.fields.contains(&column) => match &self.tenant {
+                Some(tenant) => Ok(Some((column, tenant.clone()))),
+                None => Err(SqlBuilderError::NoTenant(self.table.clone())),
+            },
+            _ => Ok(None),
+        }
+    }
+
     /// Build SQL command for INSERT statement
     fn sql_insert(&self) -> Result<String> {
         // Checks
@@ -3472,8 +5939,15 @@ impl SqlBuilder {
             return Err(SqlBuilderError::NoTableName.into());
         }
 
+        // Make tenant field
+        let tenant = self.insert_tenant()?;
+
         // Make SET part
-        let fields = self.fields.join(", ");
+        let mut fields = self.fields.clone();
+        if let Some((column, _)) = &tenant {
+            fields.push(column.clone());
+        }
+        let fields = fields.join(", ");
 
         // Add values or query
         let sql = match &self.values {
@@ -3484,7 +5958,14 @@ impl SqlBuilder {
                 }
 
                 // Make VALUES part
-                let values = values.join(", ");
+                let values = values
+                    .iter()
+                    .map(|row| match &tenant {
+                        Some((_, tenant)) => format!("({}, {})", row.join(", "), tenant),
+                        None => format!("({})", row.join(", ")),
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", ");
 
                 // Make ON CONFLICT part
                 let on_conflict = if let Some(on_conflict_action) = &self.on_conflict_action {
@@ -3529,6 +6010,10 @@ impl SqlBuilder {
                 )
             }
             Values::Select(query) => {
+                if let Some((column, _)) = tenant {
+                    return Err(SqlBuilderError::NoTenantField(column).into());
+                }
+
                 // Make SQL
                 format!(
                     "INSERT INTO {table} ({fields}) {query};",
@@ -3556,17 +6041,17 @@ impl SqlBuilder {
         }
 
         // Make SET part
-        let sets = self.sets.join(", ");
+        let mut sets = self.sets.clone();
+        if let Some(lock) = &self.version_lock {
+            sets.push(lock.set_expr());
+        }
+        let sets = sets.join(", ");
 
         // Make WHERE part
-        let wheres = SqlBuilder::make_wheres(&self.wheres);
+        let wheres = SqlBuilder::make_wheres(&self.locked_wheres()?);
 
         // Make RETURNING part
-        let returning = if let Some(ret) = &self.returning {
-            format!(" RETURNING {}", ret)
-        } else {
-            "".to_string()
-        };
+        let returning = self.make_returning();
 
         // Make SQL
         let sql = format!(
@@ -3589,18 +6074,82 @@ impl SqlBuilder {
             return Err(SqlBuilderError::NoTableName.into());
         }
 
+        // Soft-delete
+        if !self.unscoped {
+            if let Some(soft_delete) = scope::soft_delete(&self.table) {
+                return self.sql_soft_delete(soft_delete);
+            }
+        }
+
+        // Make WHERE part
+        let wheres = SqlBuilder::make_wheres(&self.locked_wheres()?);
+
+        // Make RETURNING part
+        let returning = self.make_returning();
+
+        // Make SQL
+        let sql = format!(
+            "DELETE FROM {table}{wheres}{returning};",
+            table = &self.table,
+            wheres = wheres,
+            returning = returning,
+        );
+        Ok(sql)
+    }
+
+    /// Build SQL command for soft-deleting DELETE statement
+    fn sql_soft_delete(&self, soft_delete: scope::SoftDelete) -> Result<String> {
+        // Make SET part
+        let mut sets = vec![format!("{} = {}", soft_delete.column, soft_delete.value)];
+        if let Some(lock) = &self.version_lock {
+            sets.push(lock.set_expr());
+        }
+        let sets = sets.join(", ");
+
         // Make WHERE part
-        let wheres = SqlBuilder::make_wheres(&self.wheres);
+        let wheres = SqlBuilder::make_wheres(&self.locked_wheres()?);
+
+        // Make RETURNING part
+        let returning = self.make_returning();
 
         // Make SQL
         let sql = format!(
-            "DELETE FROM {table}{wheres};",
+            "UPDATE {table} SET {sets}{wheres}{returning};",
             table = &self.table,
+            sets = sets,
             wheres = wheres,
+            returning = returning,
         );
         Ok(sql)
     }
 
+    /// Make WHERE conditions with version check, default scopes and tenant filter
+    fn locked_wheres(&self) -> Result<Vec<String>> {
+        let mut wheres = self.wheres.clone();
+        if let Some(lock) = &self.version_lock {
+            wheres.push(lock.where_cond());
+        }
+        if !self.unscoped {
+            wheres.append(&mut scope::scope_conds(&self.table, &self.unscopes));
+        }
+        let (mut tenant_wheres, _) =
+            tenant::tenant_filter(&self.table, &[], self.tenant.as_deref())?;
+        wheres.append(&mut tenant_wheres);
+        Ok(wheres)
+    }
+
+    /// Make RETURNING part
+    fn make_returning(&self) -> String {
+        let returning = match &self.version_lock {
+            Some(lock) => lock.returning(&self.returning),
+            None => self.returning.clone(),
+        };
+        match returning {
+            Some(ret) => format!(" RETURNING {}", ret),
+            None => String::new(),
+        }
+    }
+
     /// Make WHERE part
     fn make_wheres(wheres: &[String]) -> String {
         match wheres.len() {
@@ -3629,6 +6178,35 @@ impl SqlBuilder {
     }
 }
 
+/// Join fields of grouping list
+fn group_list<S, I>(fields: I) -> String
+where
+    S: ToString,
+    I: IntoIterator<Item = S>,
+{
+    fields
+        .into_iter()
+        .map(|f| f.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Find keyword outside of subqueries and quoted literals
+fn find_keyword(text: &str, keyword: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut quoted = false;
+    for (i, c) in text.char_indices() {
+        match c {
+            '\'' => quoted = !quoted,
+            '(' if !quoted => depth += 1,
+            ')' if !quoted => depth -= 1,
+            ' ' if !quoted && depth == 0 && text[i..].starts_with(keyword) => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
 /// Escape string for SQL.
 ///
 /// ```
@@ -3839,7 +6417,7 @@ mod tests {
 
         assert_eq!(
             &sql,
-            "SELECT title, price FROM books WHERE (price > 100) AND (title LIKE 'Harry Potter%');"
+            "SELECT title, price FROM books WHERE (price > 100) AND (title LIKE 'Harry Potter%' ESCAPE '\\');"
         );
 
         Ok(())
@@ -3862,7 +6440,7 @@ mod tests {
 
         assert_eq!(
             &sql,
-            "SELECT title, price FROM books WHERE price < 2 OR price > 1000 OR title = 'Harry Potter and the Philosopher''s Stone' OR price <> 100 OR title LIKE 'Alice''s' OR LOWER(title) NOT LIKE '% the %' OR title IS NULL OR price IS NOT NULL;"
+            "SELECT title, price FROM books WHERE price < 2 OR price > 1000 OR title = 'Harry Potter and the Philosopher''s Stone' OR price <> 100 OR title LIKE 'Alice''s' OR LOWER(title) NOT LIKE '% the %' ESCAPE '\\' OR title IS NULL OR price IS NOT NULL;"
         );
 
         Ok(())
@@ -3879,7 +6457,7 @@ mod tests {
 
         assert_eq!(
             &sql,
-            "SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' ORDER BY price;"
+            "SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' ESCAPE '\\' ORDER BY price;"
         );
 
         let sql = SqlBuilder::select_from("books")
@@ -3891,7 +6469,7 @@ mod tests {
 
         assert_eq!(
             &sql,
-            "SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' ORDER BY price DESC;"
+            "SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' ESCAPE '\\' ORDER BY price DESC;"
         );
 
         let sql = SqlBuilder::select_from("books")
@@ -3902,7 +6480,7 @@ mod tests {
             .order_asc("title")
             .sql()?;
 
-        assert_eq!(&sql, "SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' ORDER BY price DESC, title;");
+        assert_eq!(&sql, "SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' ESCAPE '\\' ORDER BY price DESC, title;");
 
         Ok(())
     }
@@ -3925,7 +6503,7 @@ mod tests {
             .sql()?;
 
         assert_eq!(
-            "SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' UNION SELECT title, price FROM books WHERE price < 100 ORDER BY title;",
+            "(SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' ESCAPE '\\') UNION (SELECT title, price FROM books WHERE price < 100 ORDER BY title) ORDER BY price DESC;",
             &sql
         );
 
@@ -3940,7 +6518,19 @@ mod tests {
             .sql()?;
 
         assert_eq!(
-            "SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' UNION ALL SELECT 'The Great Gatsby', 124;",
+            "(SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' ESCAPE '\\') UNION ALL (SELECT 'The Great Gatsby', 124) ORDER BY price DESC;",
+            &sql
+        );
+
+        let sql = SqlBuilder::select_from("books")
+            .field("title")
+            .union("SELECT title FROM drafts")
+            .limit(10)
+            .dialect(Dialect::Sqlite)
+            .sql()?;
+
+        assert_eq!(
+            "SELECT * FROM (SELECT title FROM books) UNION SELECT * FROM (SELECT title FROM drafts) LIMIT 10;",
             &sql
         );
 
@@ -3957,7 +6547,7 @@ mod tests {
             .limit(3)
             .sql()?;
 
-        assert_eq!(&sql, "SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' ORDER BY title LIMIT 3;");
+        assert_eq!(&sql, "SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' ESCAPE '\\' ORDER BY title LIMIT 3;");
 
         Ok(())
     }
@@ -3972,7 +6562,7 @@ mod tests {
             .offset(2)
             .sql()?;
 
-        assert_eq!(&sql, "SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' ORDER BY title OFFSET 2;");
+        assert_eq!(&sql, "SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' ESCAPE '\\' ORDER BY title OFFSET 2;");
 
         let sql = SqlBuilder::select_from("books")
             .field("title")
@@ -3983,7 +6573,7 @@ mod tests {
             .offset(2)
             .sql()?;
 
-        assert_eq!(&sql, "SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' ORDER BY title LIMIT 3 OFFSET 2;");
+        assert_eq!(&sql, "SELECT title, price FROM books WHERE title LIKE 'Harry Potter%' ESCAPE '\\' ORDER BY title LIMIT 3 OFFSET 2;");
 
         Ok(())
     }
@@ -3996,7 +6586,7 @@ mod tests {
             .sql()?;
 
         assert_eq!(
-            "SELECT title FROM books WHERE title NOT LIKE '%Alice''s%';",
+            "SELECT title FROM books WHERE title NOT LIKE '%Alice''s%' ESCAPE '\\';",
             &sql
         );
 
@@ -4110,7 +6700,7 @@ mod tests {
 
         assert_eq!(
             &sql,
-            "UPDATE books SET price = price * 0.1 WHERE title LIKE 'Harry Potter%' RETURNING id;"
+            "UPDATE books SET price = price * 0.1 WHERE title LIKE 'Harry Potter%' ESCAPE '\\' RETURNING id;"
         );
 
         Ok(())
@@ -4173,7 +6763,7 @@ mod tests {
             .and_where_like_left("title", "Harry Potter")
             .sql()?;
 
-        assert_eq!(&sql, "UPDATE books SET price = 0, title = '[SOLD!]' || title WHERE title LIKE 'Harry Potter%';");
+        assert_eq!(&sql, "UPDATE books SET price = 0, title = '[SOLD!]' || title WHERE title LIKE 'Harry Potter%' ESCAPE '\\';");
 
         Ok(())
     }
@@ -4269,6 +6859,247 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_optimistic_lock() -> Result<()> {
+        let sql = SqlBuilder::update_table("books")
+            .set("price", 150)
+            .and_where_eq("id", 42)
+            .optimistic_lock("version", 7)
+            .or_where_eq("id", 43)
+            .sql()?;
+
+        assert_eq!(
+            &sql,
+            "UPDATE books SET price = 150, version = version + 1 WHERE (id = 42 OR id = 43) AND (version = 7);"
+        );
+
+        let sql = SqlBuilder::delete_from("books")
+            .and_where_eq("id", 42)
+            .optimistic_lock_returning("version", 7)
+            .sql()?;
+
+        assert_eq!(
+            &sql,
+            "DELETE FROM books WHERE (id = 42) AND (version = 7) RETURNING version;"
+        );
+
+        let sql = SqlBuilder::update_table("books")
+            .set("price", 150)
+            .optimistic_lock("version", "7")
+            .sql()?;
+
+        assert_eq!(
+            &sql,
+            "UPDATE books SET price = 150, version = version + 1 WHERE version = '7';"
+        );
+
+        let res = SqlBuilder::select_from("books")
+            .optimistic_lock("version", 7)
+            .sql();
+
+        assert_eq!(
+            &res.unwrap_err().to_string(),
+            "Query is invalid: optimistic lock needs UPDATE or DELETE"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_count_query() -> Result<()> {
+        let sql = SqlBuilder::select_from("books AS b")
+            .field("b.title")
+            .left_join("shops AS s")
+            .on("b.id = s.book")
+            .and_where("s.total > 0")
+            .order_desc("b.price")
+            .count_query()
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT COUNT(*) FROM books AS b LEFT JOIN shops AS s ON b.id = s.book WHERE s.total > 0;"
+        );
+
+        let sql = SqlBuilder::select_from("books")
+            .field("author")
+            .group_by("author")
+            .having("COUNT(*) > 2")
+            .limit(5)
+            .count_query()
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT COUNT(*) FROM (SELECT author FROM books GROUP BY author HAVING COUNT(*) > 2) AS t;"
+        );
+
+        let sql = SqlBuilder::select_from("books")
+            .field("title")
+            .union("SELECT title FROM drafts")
+            .order_asc("title")
+            .count_query()
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT COUNT(*) FROM (SELECT title FROM books UNION SELECT title FROM drafts) AS t;"
+        );
+
+        let res = SqlBuilder::select_from("books")
+            .distinct()
+            .and_where_eq("", 1)
+            .count_query()
+            .sql();
+        assert_eq!(&res.unwrap_err().to_string(), "WHERE field not defined");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_where_subquery() -> Result<()> {
+        let sql = SqlBuilder::select_from("books AS b")
+            .field("b.title")
+            .and_where_not_exists(
+                SqlBuilder::select_from("reviews AS r")
+                    .field("1")
+                    .and_where("r.book_id = b.id"),
+            )
+            .or_where_scalar(
+                "b.price",
+                "<",
+                SqlBuilder::select_from("books").field("MIN(price) * 2"),
+            )
+            .and_where_all("b.year", "<>", "SELECT year FROM bans")
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT b.title FROM books AS b WHERE (NOT EXISTS (SELECT 1 FROM reviews AS r WHERE r.book_id = b.id) OR b.price < (SELECT MIN(price) * 2 FROM books)) AND (b.year <> ALL (SELECT year FROM bans));"
+        );
+
+        let res = SqlBuilder::select_from("books")
+            .and_where_any("price", "; DROP TABLE books; --", "SELECT 1")
+            .sql();
+        assert_eq!(
+            &res.unwrap_err().to_string(),
+            "Comparison operator \"; DROP TABLE books; --\" is not supported"
+        );
+
+        let res = SqlBuilder::select_from("books").and_where_exists("").sql();
+        assert_eq!(
+            &res.unwrap_err().to_string(),
+            "WHERE query for field \"EXISTS\" not defined"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_structured_joins() -> Result<()> {
+        let sql = SqlBuilder::select_from(name!("books"; "b"))
+            .field("b.title")
+            .inner_join(name!("shops"; "s"))
+            .on("b.id = s.book")
+            .on("s.open")
+            .cross_join_lateral(
+                SqlBuilder::select_from("prices AS p")
+                    .field("MAX(p.value) AS top")
+                    .and_where("p.shop_id = s.id")
+                    .aliased("p"),
+            )
+            .full_join("(SELECT 1 FROM a JOIN b ON a.id = b.id) AS x")
+            .on("TRUE")
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT b.title FROM books AS b INNER JOIN shops AS s ON (b.id = s.book) AND (s.open) CROSS JOIN LATERAL (SELECT MAX(p.value) AS top FROM prices AS p WHERE p.shop_id = s.id) AS p FULL OUTER JOIN (SELECT 1 FROM a JOIN b ON a.id = b.id) AS x ON TRUE;"
+        );
+
+        let sql = SqlBuilder::select_from("books AS b")
+            .join("shops AS s")
+            .on("s.a = 1 OR s.b = 2")
+            .and_on("s.c = b.c")
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT * FROM books AS b JOIN shops AS s ON (s.a = 1 OR s.b = 2) AND (s.c = b.c);"
+        );
+
+        let res = SqlBuilder::select_from("books")
+            .left_join(SqlBuilder::select_from("shops").and_where("").aliased("s"))
+            .sql();
+        assert_eq!(&res.unwrap_err().to_string(), "WHERE condition is empty");
+
+        let res = SqlBuilder::select_from("books")
+            .join("shops")
+            .using(Vec::<String>::new())
+            .sql();
+        assert_eq!(&res.unwrap_err().to_string(), "WHERE field not defined");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_having() -> Result<()> {
+        let sql = SqlBuilder::select_from("books")
+            .field("author")
+            .field("GROUPING(author) AS total")
+            .group_by_grouping_sets(vec![vec!["author"], vec![]])
+            .having("COUNT(*) > 1")
+            .and_having_gt("SUM(price)", 100)
+            .or_having_eq("GROUPING(author)", 1)
+            .count_query()
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT COUNT(*) FROM (SELECT author, GROUPING(author) AS total FROM books GROUP BY GROUPING SETS ((author), ()) HAVING (COUNT(*) > 1) AND (SUM(price) > 100 OR GROUPING(author) = 1)) AS t;"
+        );
+
+        let res = SqlBuilder::select_from("books")
+            .group_by_cube(Vec::<String>::new())
+            .sql();
+        assert_eq!(&res.unwrap_err().to_string(), "WHERE field not defined");
+
+        let res = SqlBuilder::select_from("books").or_having_le("", 1).sql();
+        assert_eq!(&res.unwrap_err().to_string(), "WHERE field not defined");
+
+        let sql = SqlBuilder::select_from("sales")
+            .dialect(Dialect::MySql)
+            .field("SUM(total)")
+            .group_by_rollup(["year", "month"])
+            .group_by("region")
+            .and_having_in("COUNT(*)", &[1, 2])
+            .or_having_between("SUM(total)", 10, 20)
+            .or_having_like("MAX(region)", "N%")
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT SUM(total) FROM sales GROUP BY year, month, region WITH ROLLUP HAVING COUNT(*) IN (1, 2) OR SUM(total) BETWEEN 10 AND 20 OR MAX(region) LIKE 'N%';"
+        );
+
+        let sql = SqlBuilder::select_from("sales")
+            .field("SUM(total)")
+            .group_by_rollup(["year"])
+            .having("COUNT(*) > 1")
+            .having("SUM(total) > 10")
+            .clear_group_by()
+            .group_by("region")
+            .group_by_rollup(["year", "month"])
+            .dialect(Dialect::MySql)
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT SUM(total) FROM sales GROUP BY region, year, month WITH ROLLUP HAVING SUM(total) > 10;"
+        );
+
+        let res = SqlBuilder::select_from("books")
+            .and_having_in("COUNT(*)", &[] as &[i32])
+            .sql();
+        assert_eq!(
+            &res.unwrap_err().to_string(),
+            "WHERE list for field \"COUNT(*)\" not defined"
+        );
+
+        Ok(())
+    }
 }
 
 //#[cfg(test)]
@@ -0,0 +1,465 @@
+//! Pattern matching conditions.
+//!
+//! Helpers searching for a prefix, suffix or substring escape `%`, `_`
+//! and `\` in the text and add `ESCAPE '\'`, SQL Server character ranges
+//! starting with `[` are escaped too. MySQL uses backslash as the
+//! default escape character, so the clause is omitted there.
+//! Case-insensitive variants use `ILIKE` in Postgres and
+//! `LOWER(x) LIKE LOWER(y)` elsewhere.
+//!
+//! ```
+//! # use anyhow::Result;
+//! use sql_builder::{Dialect, SqlBuilder};
+//!
+//! # fn main() -> Result<()> {
+//! let sql = SqlBuilder::select_from("offers")
+//!     .and_where_like_any("title", "50%_off")
+//!     .sql()?;
+//!
+//! assert_eq!(r"SELECT * FROM offers WHERE title LIKE '%50\%\_off%' ESCAPE '\';", &sql);
+//!
+//! let sql = SqlBuilder::select_from("offers")
+//!     .and_where_ilike_left("title", "Sale")
+//!     .sql()?;
+//!
+//! assert_eq!(r"SELECT * FROM offers WHERE LOWER(title) LIKE LOWER('Sale%') ESCAPE '\';", &sql);
+//!
+//! let sql = SqlBuilder::select_from("offers")
+//!     .dialect(Dialect::Postgres)
+//!     .and_where_ilike_left("title", "Sale")
+//!     .and_where_regex("code", "^[A-Z]{3}$")
+//!     .sql()?;
+//!
+//! assert_eq!(r"SELECT * FROM offers WHERE (title ILIKE 'Sale%' ESCAPE '\') AND (code ~ '^[A-Z]{3}$');", &sql);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::dialect::Dialect;
+use crate::error::SqlBuilderError;
+use crate::{esc, quote, SqlBuilder};
+
+/// Escape wildcards `%`, `_` and escape character `\` of LIKE pattern.
+/// SQL Server also needs `[` escaped, builder helpers do it for the dialect.
+///
+/// ```
+/// use sql_builder::like::escape_like;
+///
+/// assert_eq!(r"100\% C:\\temp\_dir", escape_like(r"100% C:\temp_dir"));
+/// ```
+pub fn escape_like<S: ToString>(text: S) -> String {
+    let text = text.to_string();
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+impl SqlBuilder {
+    /// Add WHERE case-insensitive LIKE condition.
+    /// The mask is used as is, wildcards are not escaped.
+    pub fn and_where_ilike<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match self.ilike_cond(field, false, quote(mask), "") {
+            Some(cond) => self.and_where(cond),
+            None => self,
+        }
+    }
+
+    /// Add WHERE case-insensitive LIKE %condition.
+    pub fn and_where_ilike_right<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match self.ilike_escaped(field, false, "%", mask, "") {
+            Some(cond) => self.and_where(cond),
+            None => self,
+        }
+    }
+
+    /// Add WHERE case-insensitive LIKE condition%.
+    pub fn and_where_ilike_left<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match self.ilike_escaped(field, false, "", mask, "%") {
+            Some(cond) => self.and_where(cond),
+            None => self,
+        }
+    }
+
+    /// Add WHERE case-insensitive LIKE %condition%.
+    pub fn and_where_ilike_any<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match self.ilike_escaped(field, false, "%", mask, "%") {
+            Some(cond) => self.and_where(cond),
+            None => self,
+        }
+    }
+
+    /// Add WHERE case-insensitive NOT LIKE condition.
+    /// The mask is used as is, wildcards are not escaped.
+    pub fn and_where_not_ilike<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match self.ilike_cond(field, true, quote(mask), "") {
+            Some(cond) => self.and_where(cond),
+            None => self,
+        }
+    }
+
+    /// Add WHERE case-insensitive NOT LIKE %condition.
+    pub fn and_where_not_ilike_right<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match self.ilike_escaped(field, true, "%", mask, "") {
+            Some(cond) => self.and_where(cond),
+            None => self,
+        }
+    }
+
+    /// Add WHERE case-insensitive NOT LIKE condition%.
+    pub fn and_where_not_ilike_left<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match self.ilike_escaped(field, true, "", mask, "%") {
+            Some(cond) => self.and_where(cond),
+            None => self,
+        }
+    }
+
+    /// Add WHERE case-insensitive NOT LIKE %condition%.
+    pub fn and_where_not_ilike_any<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match self.ilike_escaped(field, true, "%", mask, "%") {
+            Some(cond) => self.and_where(cond),
+            None => self,
+        }
+    }
+
+    /// Add OR case-insensitive LIKE condition to the last WHERE condition.
+    /// The mask is used as is, wildcards are not escaped.
+    pub fn or_where_ilike<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match self.ilike_cond(field, false, quote(mask), "") {
+            Some(cond) => self.or_where(cond),
+            None => self,
+        }
+    }
+
+    /// Add OR case-insensitive LIKE %condition to the last WHERE condition.
+    pub fn or_where_ilike_right<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match self.ilike_escaped(field, false, "%", mask, "") {
+            Some(cond) => self.or_where(cond),
+            None => self,
+        }
+    }
+
+    /// Add OR case-insensitive LIKE condition% to the last WHERE condition.
+    pub fn or_where_ilike_left<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match self.ilike_escaped(field, false, "", mask, "%") {
+            Some(cond) => self.or_where(cond),
+            None => self,
+        }
+    }
+
+    /// Add OR case-insensitive LIKE %condition% to the last WHERE condition.
+    pub fn or_where_ilike_any<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match self.ilike_escaped(field, false, "%", mask, "%") {
+            Some(cond) => self.or_where(cond),
+            None => self,
+        }
+    }
+
+    /// Add OR case-insensitive NOT LIKE condition to the last WHERE condition.
+    /// The mask is used as is, wildcards are not escaped.
+    pub fn or_where_not_ilike<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match self.ilike_cond(field, true, quote(mask), "") {
+            Some(cond) => self.or_where(cond),
+            None => self,
+        }
+    }
+
+    /// Add OR case-insensitive NOT LIKE %condition to the last WHERE condition.
+    pub fn or_where_not_ilike_right<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match self.ilike_escaped(field, true, "%", mask, "") {
+            Some(cond) => self.or_where(cond),
+            None => self,
+        }
+    }
+
+    /// Add OR case-insensitive NOT LIKE condition% to the last WHERE condition.
+    pub fn or_where_not_ilike_left<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match self.ilike_escaped(field, true, "", mask, "%") {
+            Some(cond) => self.or_where(cond),
+            None => self,
+        }
+    }
+
+    /// Add OR case-insensitive NOT LIKE %condition% to the last WHERE condition.
+    pub fn or_where_not_ilike_any<S, T>(&mut self, field: S, mask: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match self.ilike_escaped(field, true, "%", mask, "%") {
+            Some(cond) => self.or_where(cond),
+            None => self,
+        }
+    }
+
+    /// Add WHERE SIMILAR TO condition of SQL standard.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .and_where_similar_to("isbn", "[0-9]{9}(X|[0-9])")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM books WHERE isbn SIMILAR TO '[0-9]{9}(X|[0-9])';", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_where_similar_to<S, T>(&mut self, field: S, pattern: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        // Checks
+        let field = field.to_string();
+        if field.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+
+        // Change
+        self.and_where(format!("{} SIMILAR TO {}", field, quote(pattern)))
+    }
+
+    /// Add WHERE regular expression match:
+    /// `~` in Postgres and generic SQL, `REGEXP` in MySQL and SQLite,
+    /// `REGEXP_LIKE` in SQL Server.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{Dialect, SqlBuilder};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .dialect(Dialect::MySql)
+    ///     .and_where_regex("title", "^The ")
+    ///     .and_where_not_regex("title", "[0-9]")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM books WHERE (title REGEXP '^The ') AND (title NOT REGEXP '[0-9]');", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_where_regex<S, T>(&mut self, field: S, pattern: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match self.regex_cond(field, false, pattern) {
+            Some(cond) => self.and_where(cond),
+            None => self,
+        }
+    }
+
+    /// Add WHERE negated regular expression match.
+    pub fn and_where_not_regex<S, T>(&mut self, field: S, pattern: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        match self.regex_cond(field, true, pattern) {
+            Some(cond) => self.and_where(cond),
+            None => self,
+        }
+    }
+
+    /// Make quoted LIKE mask with escaped text between prefix and suffix
+    pub(crate) fn like_mask(&self, prefix: &str, text: &str, suffix: &str) -> String {
+        let (mask, escape) = self.like_parts(prefix, text, suffix);
+        format!("{}{}", mask, escape)
+    }
+
+    /// Make quoted LIKE mask and ESCAPE clause
+    fn like_parts(&self, prefix: &str, text: &str, suffix: &str) -> (String, &'static str) {
+        let mut text = escape_like(text);
+        if self.dialect == Dialect::MsSql {
+            text = text.replace('[', "\\[");
+        }
+        match self.dialect {
+            Dialect::MySql => (
+                format!("'{}{}{}'", prefix, esc(text.replace('\\', "\\\\")), suffix),
+                "",
+            ),
+            _ => (
+                format!("'{}{}{}'", prefix, esc(&text), suffix),
+                " ESCAPE '\\'",
+            ),
+        }
+    }
+
+    /// Make case-insensitive LIKE condition with escaped text between prefix and suffix
+    fn ilike_escaped<S, T>(
+        &mut self,
+        field: S,
+        not: bool,
+        prefix: &str,
+        text: T,
+        suffix: &str,
+    ) -> Option<String>
+    where
+        S: ToString,
+        T: ToString,
+    {
+        let (mask, escape) = self.like_parts(prefix, &text.to_string(), suffix);
+        self.ilike_cond(field, not, mask, escape)
+    }
+
+    /// Make case-insensitive LIKE condition
+    fn ilike_cond<S: ToString>(
+        &mut self,
+        field: S,
+        not: bool,
+        mask: String,
+        escape: &str,
+    ) -> Option<String> {
+        let field = field.to_string();
+        if field.is_empty() {
+            self.set_error(&SqlBuilderError::NoWhereField);
+            return None;
+        }
+        let not = if not { "NOT " } else { "" };
+        Some(match self.dialect {
+            Dialect::Postgres => format!("{} {}ILIKE {}{}", field, not, mask, escape),
+            _ => format!("LOWER({}) {}LIKE LOWER({}){}", field, not, mask, escape),
+        })
+    }
+
+    /// Make regular expression condition
+    fn regex_cond<S, T>(&mut self, field: S, not: bool, pattern: T) -> Option<String>
+    where
+        S: ToString,
+        T: ToString,
+    {
+        let field = field.to_string();
+        if field.is_empty() {
+            self.set_error(&SqlBuilderError::NoWhereField);
+            return None;
+        }
+        let pattern = quote(pattern);
+        Some(match (self.dialect, not) {
+            (Dialect::MySql | Dialect::Sqlite, false) => format!("{} REGEXP {}", field, pattern),
+            (Dialect::MySql | Dialect::Sqlite, true) => format!("{} NOT REGEXP {}", field, pattern),
+            (Dialect::MsSql, false) => format!("REGEXP_LIKE({}, {})", field, pattern),
+            (Dialect::MsSql, true) => format!("NOT REGEXP_LIKE({}, {})", field, pattern),
+            (_, false) => format!("{} ~ {}", field, pattern),
+            (_, true) => format!("{} !~ {}", field, pattern),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    #[test]
+    fn test_like_escape() -> Result<()> {
+        let sql = SqlBuilder::select_from("files")
+            .dialect(Dialect::MySql)
+            .and_where_like_left("path", r"C:\tmp_1")
+            .and_where_not_ilike("name", "%.TMP")
+            .sql()?;
+        assert_eq!(
+            &sql,
+            r"SELECT * FROM files WHERE (path LIKE 'C:\\\\tmp\\_1%') AND (LOWER(name) NOT LIKE LOWER('%.TMP'));"
+        );
+
+        let sql = SqlBuilder::select_from("files")
+            .dialect(Dialect::Postgres)
+            .and_where_not_ilike("name", "%.tmp")
+            .and_where_ilike_right("name", "it's")
+            .or_where_ilike_any("path", "_")
+            .sql()?;
+        assert_eq!(
+            &sql,
+            r"SELECT * FROM files WHERE (name NOT ILIKE '%.tmp') AND (name ILIKE '%it''s' ESCAPE '\' OR path ILIKE '%\_%' ESCAPE '\');"
+        );
+
+        let sql = SqlBuilder::select_from("files")
+            .dialect(Dialect::MsSql)
+            .and_where_not_regex("name", "^~")
+            .and_where_like_any("name", "[draft]")
+            .sql()?;
+        assert_eq!(
+            &sql,
+            r"SELECT * FROM files WHERE (NOT REGEXP_LIKE(name, '^~')) AND (name LIKE '%\[draft]%' ESCAPE '\');"
+        );
+
+        let sql = SqlBuilder::select_from("files")
+            .and_where_not_ilike_left("name", "tmp_")
+            .or_where_ilike("name", "%.log")
+            .or_where_not_ilike_any("path", "%")
+            .sql()?;
+        assert_eq!(
+            &sql,
+            r"SELECT * FROM files WHERE LOWER(name) NOT LIKE LOWER('tmp\_%') ESCAPE '\' OR LOWER(name) LIKE LOWER('%.log') OR LOWER(path) NOT LIKE LOWER('%\%%') ESCAPE '\';"
+        );
+
+        Ok(())
+    }
+}
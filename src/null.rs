@@ -0,0 +1,239 @@
+//! NULL-aware comparisons.
+//!
+//! Plain `=` never matches NULL. Nullable helpers compare with `IS NULL`
+//! when the value is NULL, and `IS [NOT] DISTINCT FROM` treats NULLs as
+//! equal values: it is rendered as `<=>` in MySQL and `IS` in SQLite.
+//!
+//! ```
+//! # use anyhow::Result;
+//! use sql_builder::{Dialect, SqlBuilder};
+//!
+//! # fn main() -> Result<()> {
+//! let author: Option<&str> = None;
+//! let sql = SqlBuilder::select_from("books")
+//!     .and_where_eq_nullable("author", author)
+//!     .and_where_ne_nullable("isbn", Some("0-00"))
+//!     .sql()?;
+//!
+//! assert_eq!("SELECT * FROM books WHERE (author IS NULL) AND (isbn <> '0-00');", &sql);
+//!
+//! let sql = SqlBuilder::select_from("books")
+//!     .dialect(Dialect::MySql)
+//!     .and_where_distinct_from("author", author)
+//!     .sql()?;
+//!
+//! assert_eq!("SELECT * FROM books WHERE NOT (author <=> NULL);", &sql);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::arg::SqlArg;
+use crate::dialect::Dialect;
+use crate::error::SqlBuilderError;
+use crate::page::is_null;
+use crate::SqlBuilder;
+
+impl SqlBuilder {
+    /// Add WHERE field = value, or field IS NULL if value is NULL.
+    pub fn and_where_eq_nullable<S: ToString, T: SqlArg>(
+        &mut self,
+        field: S,
+        value: T,
+    ) -> &mut Self {
+        match self.nullable_cond(field, false, value) {
+            Some(cond) => self.and_where(cond),
+            None => self,
+        }
+    }
+
+    /// Add WHERE field <> value, or field IS NOT NULL if value is NULL.
+    pub fn and_where_ne_nullable<S: ToString, T: SqlArg>(
+        &mut self,
+        field: S,
+        value: T,
+    ) -> &mut Self {
+        match self.nullable_cond(field, true, value) {
+            Some(cond) => self.and_where(cond),
+            None => self,
+        }
+    }
+
+    /// Add OR field = value, or OR field IS NULL if value is NULL, to the last WHERE condition.
+    pub fn or_where_eq_nullable<S: ToString, T: SqlArg>(
+        &mut self,
+        field: S,
+        value: T,
+    ) -> &mut Self {
+        match self.nullable_cond(field, false, value) {
+            Some(cond) => self.or_where(cond),
+            None => self,
+        }
+    }
+
+    /// Add OR field <> value, or OR field IS NOT NULL if value is NULL, to the last WHERE condition.
+    pub fn or_where_ne_nullable<S: ToString, T: SqlArg>(
+        &mut self,
+        field: S,
+        value: T,
+    ) -> &mut Self {
+        match self.nullable_cond(field, true, value) {
+            Some(cond) => self.or_where(cond),
+            None => self,
+        }
+    }
+
+    /// Add WHERE field IS DISTINCT FROM value.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{arg::RawSql, Dialect, SqlBuilder};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::update_table("books")
+    ///     .set("price", "new_price")
+    ///     .and_where_distinct_from("price", RawSql("new_price"))
+    ///     .sql()?;
+    ///
+    /// assert_eq!("UPDATE books SET price = new_price WHERE price IS DISTINCT FROM new_price;", &sql);
+    ///
+    /// let sql = SqlBuilder::update_table("books")
+    ///     .dialect(Dialect::Sqlite)
+    ///     .set("price", "new_price")
+    ///     .and_where_distinct_from("price", RawSql("new_price"))
+    ///     .sql()?;
+    ///
+    /// assert_eq!("UPDATE books SET price = new_price WHERE price IS NOT new_price;", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_where_distinct_from<S: ToString, T: SqlArg>(
+        &mut self,
+        field: S,
+        value: T,
+    ) -> &mut Self {
+        match self.distinct_cond(field, true, value) {
+            Some(cond) => self.and_where(cond),
+            None => self,
+        }
+    }
+
+    /// Add WHERE field IS NOT DISTINCT FROM value.
+    pub fn and_where_not_distinct_from<S: ToString, T: SqlArg>(
+        &mut self,
+        field: S,
+        value: T,
+    ) -> &mut Self {
+        match self.distinct_cond(field, false, value) {
+            Some(cond) => self.and_where(cond),
+            None => self,
+        }
+    }
+
+    /// Add OR field IS DISTINCT FROM value to the last WHERE condition.
+    pub fn or_where_distinct_from<S: ToString, T: SqlArg>(
+        &mut self,
+        field: S,
+        value: T,
+    ) -> &mut Self {
+        match self.distinct_cond(field, true, value) {
+            Some(cond) => self.or_where(cond),
+            None => self,
+        }
+    }
+
+    /// Add OR field IS NOT DISTINCT FROM value to the last WHERE condition.
+    pub fn or_where_not_distinct_from<S: ToString, T: SqlArg>(
+        &mut self,
+        field: S,
+        value: T,
+    ) -> &mut Self {
+        match self.distinct_cond(field, false, value) {
+            Some(cond) => self.or_where(cond),
+            None => self,
+        }
+    }
+
+    /// Check field name of comparison
+    fn compared_field<S: ToString>(&mut self, field: S) -> Option<String> {
+        let field = field.to_string();
+        if field.is_empty() {
+            self.set_error(&SqlBuilderError::NoWhereField);
+            return None;
+        }
+        Some(field)
+    }
+
+    /// Make equality condition with IS NULL for NULL value
+    fn nullable_cond<S: ToString, T: SqlArg>(
+        &mut self,
+        field: S,
+        not: bool,
+        value: T,
+    ) -> Option<String> {
+        let field = self.compared_field(field)?;
+        let value = value.sql_arg();
+        Some(match (is_null(&value), not) {
+            (true, false) => format!("{} IS NULL", field),
+            (true, true) => format!("{} IS NOT NULL", field),
+            (false, false) => format!("{} = {}", field, value),
+            (false, true) => format!("{} <> {}", field, value),
+        })
+    }
+
+    /// Make IS [NOT] DISTINCT FROM condition for the dialect
+    fn distinct_cond<S: ToString, T: SqlArg>(
+        &mut self,
+        field: S,
+        distinct: bool,
+        value: T,
+    ) -> Option<String> {
+        let field = self.compared_field(field)?;
+        let value = value.sql_arg();
+        Some(match (self.dialect, distinct) {
+            (Dialect::MySql, true) => format!("NOT ({} <=> {})", field, value),
+            (Dialect::MySql, false) => format!("{} <=> {}", field, value),
+            (Dialect::Sqlite, true) => format!("{} IS NOT {}", field, value),
+            (Dialect::Sqlite, false) => format!("{} IS {}", field, value),
+            (_, true) => format!("{} IS DISTINCT FROM {}", field, value),
+            (_, false) => format!("{} IS NOT DISTINCT FROM {}", field, value),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Dialect, SqlBuilder};
+    use anyhow::Result;
+
+    #[test]
+    fn test_null_safe() -> Result<()> {
+        let sql = SqlBuilder::delete_from("books")
+            .dialect(Dialect::Sqlite)
+            .and_where_not_distinct_from("author", "Tolkien")
+            .or_where_eq_nullable("author", None::<String>)
+            .and_where_ne_nullable("year", None::<i32>)
+            .or_where_ne_nullable("year", 1954)
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "DELETE FROM books WHERE (author IS 'Tolkien' OR author IS NULL) AND (year IS NOT NULL OR year <> 1954);"
+        );
+
+        let sql = SqlBuilder::select_from("books")
+            .and_where_eq_nullable("year", Some(1954))
+            .or_where_distinct_from("author", Some("Tolkien"))
+            .and_where_not_distinct_from("editor", None::<&str>)
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT * FROM books WHERE (year = 1954 OR author IS DISTINCT FROM 'Tolkien') AND (editor IS NOT DISTINCT FROM NULL);"
+        );
+
+        let res = SqlBuilder::select_from("books")
+            .and_where_eq_nullable("", 1)
+            .sql();
+        assert_eq!(&res.unwrap_err().to_string(), "WHERE field not defined");
+
+        Ok(())
+    }
+}
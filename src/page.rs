@@ -0,0 +1,698 @@
+//! Safe parsing of sort and pagination request parameters.
+//!
+//! ```
+//! # use anyhow::Result;
+//! use sql_builder::{page::PageParams, SqlBuilder};
+//!
+//! # fn main() -> Result<()> {
+//! let params = PageParams::new()
+//!     .sortable("created_at")
+//!     .sortable_as("name", "title")
+//!     .max_size(50)
+//!     .clone();
+//!
+//! let sort = params.parse_sort("-created_at,name")?;
+//! let page = params.parse_page(Some("3"), Some("500"))?;
+//!
+//! let sql = SqlBuilder::select_from("books")
+//!     .field("title")
+//!     .order_by_keys(&sort)
+//!     .paginate(&page)
+//!     .sql()?;
+//!
+//! assert_eq!("SELECT title FROM books ORDER BY created_at DESC, title LIMIT 50 OFFSET 100;", &sql);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::arg::SqlArgs;
+use crate::dialect::Dialect;
+use crate::error::SqlBuilderError;
+use crate::select::SelectBuilder;
+use crate::SqlBuilder;
+use std::collections::HashMap;
+
+/// Placement of NULLs in sort order
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Nulls {
+    /// Column is not nullable, database default placement is used
+    #[default]
+    Default,
+    First,
+    Last,
+}
+
+/// Sort key of validated column
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SortKey {
+    pub column: String,
+    pub desc: bool,
+    pub nulls: Nulls,
+}
+
+impl SortKey {
+    /// Ascending sort key
+    pub fn asc<S: ToString>(column: S) -> Self {
+        Self {
+            column: column.to_string(),
+            desc: false,
+            nulls: Nulls::Default,
+        }
+    }
+
+    /// Descending sort key
+    pub fn desc<S: ToString>(column: S) -> Self {
+        Self {
+            column: column.to_string(),
+            desc: true,
+            nulls: Nulls::Default,
+        }
+    }
+
+    /// Place NULLs before values
+    pub fn nulls_first(mut self) -> Self {
+        self.nulls = Nulls::First;
+        self
+    }
+
+    /// Place NULLs after values
+    pub fn nulls_last(mut self) -> Self {
+        self.nulls = Nulls::Last;
+        self
+    }
+
+    /// Sort key of reversed order
+    pub fn reversed(&self) -> Self {
+        Self {
+            column: self.column.clone(),
+            desc: !self.desc,
+            nulls: match self.nulls {
+                Nulls::Default => Nulls::Default,
+                Nulls::First => Nulls::Last,
+                Nulls::Last => Nulls::First,
+            },
+        }
+    }
+
+    /// Make ORDER BY terms for the key
+    pub(crate) fn order_terms(&self, dialect: Dialect) -> Vec<String> {
+        let order = if self.desc {
+            format!("{} DESC", self.column)
+        } else {
+            self.column.clone()
+        };
+        match self.nulls {
+            Nulls::Default => vec![order],
+            Nulls::First if dialect.nulls_order() => vec![format!("{} NULLS FIRST", order)],
+            Nulls::Last if dialect.nulls_order() => vec![format!("{} NULLS LAST", order)],
+            Nulls::First => vec![
+                format!("CASE WHEN {} IS NULL THEN 0 ELSE 1 END", self.column),
+                order,
+            ],
+            Nulls::Last => vec![
+                format!("CASE WHEN {} IS NULL THEN 1 ELSE 0 END", self.column),
+                order,
+            ],
+        }
+    }
+}
+
+/// Direction of keyset pagination
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// Rows after the values in sort order
+    After,
+    /// Rows before the values, selected in reversed sort order
+    Before,
+}
+
+/// Page number (from 1) and page size
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Page {
+    pub number: u64,
+    pub size: u64,
+}
+
+impl Page {
+    /// Number of rows before the page, page 0 is treated as the first one.
+    ///
+    /// ```
+    /// use sql_builder::page::Page;
+    ///
+    /// assert_eq!(20, Page { number: 3, size: 10 }.offset());
+    /// assert_eq!(0, Page { number: 0, size: 10 }.offset());
+    /// assert_eq!(u64::MAX, Page { number: u64::MAX, size: 10 }.offset());
+    /// ```
+    pub fn offset(&self) -> u64 {
+        self.number.saturating_sub(1).saturating_mul(self.size)
+    }
+}
+
+/// Allowed sort columns and page size limits
+#[derive(Clone)]
+pub struct PageParams {
+    sortable: HashMap<String, String>,
+    default_sort: Vec<SortKey>,
+    default_size: u64,
+    max_size: u64,
+}
+
+impl Default for PageParams {
+    fn default() -> Self {
+        Self {
+            sortable: HashMap::new(),
+            default_sort: Vec::new(),
+            default_size: 20,
+            max_size: 100,
+        }
+    }
+}
+
+impl PageParams {
+    /// Create parameters with page size 20 limited by 100
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow sorting by column with the same name
+    pub fn sortable<S: ToString>(&mut self, name: S) -> &mut Self {
+        let name = name.to_string();
+        self.sortable.insert(name.clone(), name);
+        self
+    }
+
+    /// Allow sorting by name mapped to the column
+    pub fn sortable_as<S, C>(&mut self, name: S, column: C) -> &mut Self
+    where
+        S: ToString,
+        C: ToString,
+    {
+        self.sortable.insert(name.to_string(), column.to_string());
+        self
+    }
+
+    /// Set sort keys used for empty sort string
+    pub fn default_sort(&mut self, keys: &[SortKey]) -> &mut Self {
+        self.default_sort = keys.to_vec();
+        self
+    }
+
+    /// Set page size used when size is not specified
+    pub fn default_size(&mut self, size: u64) -> &mut Self {
+        self.default_size = size;
+        self
+    }
+
+    /// Set maximum page size
+    pub fn max_size(&mut self, size: u64) -> &mut Self {
+        self.max_size = size;
+        self
+    }
+
+    /// Parse comma separated sort string.
+    /// Names prefixed with `-` are sorted in descending order,
+    /// repeated names are rejected.
+    ///
+    /// ```
+    /// use sql_builder::{page::{PageParams, SortKey}, SqlBuilderError};
+    ///
+    /// let params = PageParams::new().sortable("price").sortable("title").clone();
+    ///
+    /// assert_eq!(Ok(vec![SortKey::desc("price"), SortKey::asc("title")]), params.parse_sort("-price, +title"));
+    /// assert_eq!(Err(SqlBuilderError::UnknownSortField("price; DROP TABLE books".to_string())), params.parse_sort("price; DROP TABLE books"));
+    /// ```
+    pub fn parse_sort(&self, sort: &str) -> Result<Vec<SortKey>, SqlBuilderError> {
+        if sort.trim().is_empty() {
+            return Ok(self.default_sort.clone());
+        }
+
+        let mut keys: Vec<SortKey> = Vec::new();
+        for item in sort.split(',') {
+            let item = item.trim();
+            let (name, desc) = match item.strip_prefix('-') {
+                Some(name) => (name, true),
+                None => (item.strip_prefix('+').unwrap_or(item), false),
+            };
+            let column = self
+                .sortable
+                .get(name)
+                .ok_or_else(|| SqlBuilderError::UnknownSortField(item.to_string()))?;
+            if keys.iter().any(|key| &key.column == column) {
+                return Err(SqlBuilderError::DuplicateSortField(item.to_string()));
+            }
+            keys.push(SortKey {
+                column: column.clone(),
+                desc,
+                nulls: Nulls::Default,
+            });
+        }
+        Ok(keys)
+    }
+
+    /// Parse page number and page size.
+    /// Page size is clamped to the maximum.
+    ///
+    /// ```
+    /// use sql_builder::{page::{Page, PageParams}, SqlBuilderError};
+    ///
+    /// let params = PageParams::new();
+    ///
+    /// assert_eq!(Ok(Page { number: 1, size: 20 }), params.parse_page(None, None));
+    /// assert_eq!(Ok(Page { number: 2, size: 100 }), params.parse_page(Some("2"), Some("1000")));
+    /// assert_eq!(Err(SqlBuilderError::InvalidPageParam("0".to_string())), params.parse_page(Some("0"), None));
+    /// ```
+    pub fn parse_page(
+        &self,
+        page: Option<&str>,
+        size: Option<&str>,
+    ) -> Result<Page, SqlBuilderError> {
+        let number = match page {
+            Some(page) => parse_positive(page)?,
+            None => 1,
+        };
+        let size = match size {
+            Some(size) => parse_positive(size)?,
+            None => self.default_size,
+        };
+        let size = size.clamp(1, self.max_size.max(1));
+
+        // Offset must fit into u64
+        if (number - 1).checked_mul(size).is_none() {
+            return Err(SqlBuilderError::InvalidPageParam(number.to_string()));
+        }
+        Ok(Page { number, size })
+    }
+}
+
+/// Parse positive integer parameter
+fn parse_positive(param: &str) -> Result<u64, SqlBuilderError> {
+    match param.trim().parse::<u64>() {
+        Ok(value) if value > 0 => Ok(value),
+        _ => Err(SqlBuilderError::InvalidPageParam(param.to_string())),
+    }
+}
+
+/// Make keyset condition for rows after the values in sort order.
+/// Row values are used if the dialect supports them and no NULLs are sorted.
+pub(crate) fn keyset_cond(
+    keys: &[SortKey],
+    values: &[String],
+    dialect: Dialect,
+) -> Result<String, SqlBuilderError> {
+    if keys.is_empty() {
+        return Err(SqlBuilderError::NoWhereField);
+    }
+    if let Some(key) = keys.get(values.len()) {
+        return Err(SqlBuilderError::NoWhereValue(key.column.clone()));
+    }
+    if values.len() > keys.len() {
+        return Err(SqlBuilderError::InvalidRowLength(keys.len(), values.len()));
+    }
+    for (key, value) in keys.iter().zip(values) {
+        if key.nulls == Nulls::Default && is_null(value) {
+            return Err(SqlBuilderError::NullSortValue(key.column.clone()));
+        }
+    }
+
+    // Row values comparison
+    let same_order = keys.iter().all(|key| key.desc == keys[0].desc);
+    let nullable = keys.iter().any(|key| key.nulls != Nulls::Default);
+    if keys.len() > 1 && same_order && !nullable && dialect.row_values() {
+        let op = if keys[0].desc { "<" } else { ">" };
+        let columns: Vec<&str> = keys.iter().map(|key| key.column.as_str()).collect();
+        return Ok(format!(
+            "({}) {} ({})",
+            columns.join(", "),
+            op,
+            values.join(", ")
+        ));
+    }
+
+    // Expanded OR-chain
+    let mut alts = Vec::with_capacity(keys.len());
+    for (i, key) in keys.iter().enumerate() {
+        let Some(after) = after_cond(key, &values[i]) else {
+            continue;
+        };
+        let mut conds: Vec<String> = keys[..i]
+            .iter()
+            .zip(values)
+            .map(|(key, value)| equal_cond(key, value))
+            .collect();
+        conds.push(after);
+        alts.push(conds.join(" AND "));
+    }
+    match alts.len() {
+        0 => Ok("1 = 0".to_string()),
+        1 => Ok(alts.remove(0)),
+        _ => Ok(alts
+            .iter()
+            .map(|alt| format!("({})", alt))
+            .collect::<Vec<String>>()
+            .join(" OR ")),
+    }
+}
+
+/// Check if rendered value is NULL
+pub(crate) fn is_null(value: &str) -> bool {
+    value.eq_ignore_ascii_case("NULL")
+}
+
+/// Make condition for values equal to the key value
+fn equal_cond(key: &SortKey, value: &str) -> String {
+    if is_null(value) {
+        format!("{} IS NULL", key.column)
+    } else {
+        format!("{} = {}", key.column, value)
+    }
+}
+
+/// Make condition for values after the key value, None if there are no such values
+fn after_cond(key: &SortKey, value: &str) -> Option<String> {
+    let op = if key.desc { "<" } else { ">" };
+    match (is_null(value), key.nulls) {
+        (true, Nulls::First) => Some(format!("{} IS NOT NULL", key.column)),
+        (true, _) => None,
+        (false, Nulls::Last) => Some(format!(
+            "({col} {op} {value} OR {col} IS NULL)",
+            col = key.column,
+            op = op,
+            value = value
+        )),
+        (false, _) => Some(format!("{} {} {}", key.column, op, value)),
+    }
+}
+
+/// Make keyset sort keys and condition for the direction.
+/// Float NaN and infinity are rejected, SQL takes them for identifiers.
+pub(crate) fn seek_parts(
+    keys: &[SortKey],
+    values: &[String],
+    direction: Direction,
+    dialect: Dialect,
+) -> Result<(Vec<SortKey>, String), SqlBuilderError> {
+    let keys: Vec<SortKey> = match direction {
+        Direction::After => keys.to_vec(),
+        Direction::Before => keys.iter().map(SortKey::reversed).collect(),
+    };
+    if values
+        .iter()
+        .any(|value| matches!(value.as_str(), "NaN" | "inf" | "-inf"))
+    {
+        return Err(SqlBuilderError::InvalidCursor);
+    }
+    let cond = keyset_cond(&keys, values, dialect)?;
+    Ok((keys, cond))
+}
+
+impl SqlBuilder {
+    /// Add ORDER BY for sort keys.
+    /// NULLs placement depends on dialect, so set dialect before.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{page::SortKey, SqlBuilder};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .order_by_keys(&[SortKey::desc("price"), SortKey::asc("title")])
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM books ORDER BY price DESC, title;", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn order_by_keys(&mut self, keys: &[SortKey]) -> &mut Self {
+        for key in keys {
+            for term in key.order_terms(self.dialect) {
+                self.order_by(term);
+            }
+        }
+        self
+    }
+
+    /// Set LIMIT and OFFSET for the page.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{page::Page, SqlBuilder};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .paginate(&Page { number: 3, size: 10 })
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM books LIMIT 10 OFFSET 20;", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn paginate(&mut self, page: &Page) -> &mut Self {
+        self.limit(page.size);
+        if page.number > 1 {
+            self.offset(page.offset());
+        }
+        self
+    }
+
+    /// Add WHERE condition for rows after the values in sort order.
+    /// Row values are used depending on dialect, so set dialect before.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{page::SortKey, SqlBuilder};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sort = [SortKey::desc("price"), SortKey::asc("id")];
+    ///
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .and_where_after(&sort, (150, 42))
+    ///     .order_by_keys(&sort)
+    ///     .limit(20)
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM books WHERE (price < 150) OR (price = 150 AND id > 42) ORDER BY price DESC, id LIMIT 20;", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_where_after<V: SqlArgs>(&mut self, keys: &[SortKey], values: V) -> &mut Self {
+        match keyset_cond(keys, &values.sql_args(), self.dialect) {
+            Ok(cond) => self.and_where(cond),
+            Err(err) => self.set_error(&err),
+        }
+    }
+
+    /// Use keyset pagination: add WHERE condition for rows after or before
+    /// the values and ORDER BY for the direction.
+    /// Rows before the values are selected in reversed order.
+    /// Condition and order depend on dialect, so set dialect before.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{page::{Direction, SortKey}, Dialect, SqlBuilder};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sort = [SortKey::desc("created_at"), SortKey::desc("id")];
+    ///
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .dialect(Dialect::Postgres)
+    ///     .seek(&sort, ("2024-01-01", 42), Direction::After)
+    ///     .limit(20)
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM books WHERE (created_at, id) < ('2024-01-01', 42) ORDER BY created_at DESC, id DESC LIMIT 20;", &sql);
+    ///
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .seek(&sort, ("2024-01-01", 42), Direction::Before)
+    ///     .limit(20)
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM books WHERE (created_at > '2024-01-01') OR (created_at = '2024-01-01' AND id > 42) ORDER BY created_at, id LIMIT 20;", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn seek<V: SqlArgs>(
+        &mut self,
+        keys: &[SortKey],
+        values: V,
+        direction: Direction,
+    ) -> &mut Self {
+        match seek_parts(keys, &values.sql_args(), direction, self.dialect) {
+            Ok((keys, cond)) => self.and_where(cond).order_by_keys(&keys),
+            Err(err) => self.set_error(&err),
+        }
+    }
+}
+
+impl SelectBuilder {
+    /// Add ORDER BY for sort keys.
+    /// NULLs placement depends on dialect, so set dialect before.
+    pub fn order_by_keys(&mut self, keys: &[SortKey]) -> &mut Self {
+        for key in keys {
+            for term in key.order_terms(self.dialect) {
+                self.order_by(term);
+            }
+        }
+        self
+    }
+
+    /// Set LIMIT and OFFSET for the page.
+    pub fn paginate(&mut self, page: &Page) -> &mut Self {
+        self.limit(page.size);
+        if page.number > 1 {
+            self.offset(page.offset());
+        }
+        self
+    }
+
+    /// Add WHERE condition for rows after the values in sort order.
+    /// Row values are used depending on dialect, so set dialect before.
+    pub fn and_where_after<V: SqlArgs>(&mut self, keys: &[SortKey], values: V) -> &mut Self {
+        match keyset_cond(keys, &values.sql_args(), self.dialect) {
+            Ok(cond) => self.and_where(cond),
+            Err(err) => self.set_error(&err),
+        }
+    }
+
+    /// Use keyset pagination: add WHERE condition for rows after or before
+    /// the values and ORDER BY for the direction.
+    /// Condition and order depend on dialect, so set dialect before.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{page::{Direction, SortKey}, select::SelectBuilder, Dialect};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sort = [SortKey::asc("title"), SortKey::asc("id")];
+    ///
+    /// let sql = SelectBuilder::from_table("books")
+    ///     .dialect(Dialect::Postgres)
+    ///     .seek(&sort, ("Dune", 42), Direction::After)
+    ///     .limit(10)
+    ///     .build()?;
+    ///
+    /// assert_eq!("SELECT * FROM books WHERE (title, id) > ('Dune', 42) ORDER BY title, id LIMIT 10;", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn seek<V: SqlArgs>(
+        &mut self,
+        keys: &[SortKey],
+        values: V,
+        direction: Direction,
+    ) -> &mut Self {
+        match seek_parts(keys, &values.sql_args(), direction, self.dialect) {
+            Ok((keys, cond)) => self.and_where(cond).order_by_keys(&keys),
+            Err(err) => self.set_error(&err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    #[test]
+    fn test_parse_sort() {
+        let params = PageParams::new()
+            .sortable("price")
+            .sortable_as("name", "b.title")
+            .default_sort(&[SortKey::asc("id")])
+            .clone();
+
+        assert_eq!(params.parse_sort(""), Ok(vec![SortKey::asc("id")]));
+        assert_eq!(
+            params.parse_sort("name,-price"),
+            Ok(vec![SortKey::asc("b.title"), SortKey::desc("price")])
+        );
+        assert_eq!(
+            params.parse_sort("price,-price"),
+            Err(SqlBuilderError::DuplicateSortField("-price".to_string()))
+        );
+        assert_eq!(
+            params.parse_sort("price,"),
+            Err(SqlBuilderError::UnknownSortField("".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_page() {
+        let params = PageParams::new().default_size(10).max_size(50).clone();
+
+        assert_eq!(
+            params.parse_page(None, None),
+            Ok(Page {
+                number: 1,
+                size: 10
+            })
+        );
+        assert_eq!(
+            params.parse_page(Some(" 4 "), Some("25")),
+            Ok(Page {
+                number: 4,
+                size: 25
+            })
+        );
+        assert_eq!(
+            params.parse_page(Some("-1"), None),
+            Err(SqlBuilderError::InvalidPageParam("-1".to_string()))
+        );
+        assert_eq!(
+            params.parse_page(None, Some("ten")),
+            Err(SqlBuilderError::InvalidPageParam("ten".to_string()))
+        );
+        assert_eq!(
+            params.parse_page(Some(&u64::MAX.to_string()), None),
+            Err(SqlBuilderError::InvalidPageParam(u64::MAX.to_string()))
+        );
+    }
+
+    #[test]
+    fn test_keyset() -> Result<()> {
+        let sort = [SortKey::asc("a"), SortKey::desc("b"), SortKey::asc("c")];
+        let sql = SqlBuilder::select_from("t")
+            .and_where_after(&sort, (1, "x", 3))
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT * FROM t WHERE (a > 1) OR (a = 1 AND b < 'x') OR (a = 1 AND b = 'x' AND c > 3);"
+        );
+
+        let res = SqlBuilder::select_from("t")
+            .and_where_after(&sort, (1,))
+            .sql();
+        assert_eq!(
+            &res.unwrap_err().to_string(),
+            "WHERE value for field \"b\" not defined"
+        );
+
+        let res = SqlBuilder::select_from("t")
+            .and_where_after(&sort, (1, "x", 3, 4))
+            .sql();
+        assert_eq!(
+            &res.unwrap_err().to_string(),
+            "Row has 4 values instead of 3"
+        );
+
+        // Dialect is used when the condition is added
+        let sort = [SortKey::asc("a").nulls_last(), SortKey::asc("b")];
+        let sql = SqlBuilder::select_from("t")
+            .seek(&sort, (1, 2), Direction::After)
+            .dialect(Dialect::MySql)
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT * FROM t WHERE ((a > 1 OR a IS NULL)) OR (a = 1 AND b > 2) ORDER BY a NULLS LAST, b;"
+        );
+        let sql = SelectBuilder::from_table("t")
+            .dialect(Dialect::MySql)
+            .seek(&sort, (1, 2), Direction::After)
+            .build()?;
+        assert_eq!(
+            &sql,
+            "SELECT * FROM t WHERE ((a > 1 OR a IS NULL)) OR (a = 1 AND b > 2) ORDER BY CASE WHEN a IS NULL THEN 1 ELSE 0 END, a, b;"
+        );
+
+        Ok(())
+    }
+}
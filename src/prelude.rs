@@ -1,7 +1,15 @@
 pub use crate::arg::*;
+pub use crate::array::*;
 pub use crate::bind::*;
+pub use crate::bulk::*;
+pub use crate::case::*;
+pub use crate::chunk::*;
+pub use crate::compound::*;
+pub use crate::dialect::*;
 pub use crate::insert::*;
+pub use crate::json::*;
 pub use crate::name::*;
+pub use crate::query::*;
 pub use crate::select::*;
 pub use crate::update::*;
 pub use crate::{quote, SqlBuilder};
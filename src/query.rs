@@ -0,0 +1,356 @@
+//! Queries nested into other statements.
+//!
+//! Text queries are used as is. Builders are built in place, so their
+//! errors are reported by the outer builder and `?` placeholders of the
+//! nested query stay in order of the resulting SQL for binding.
+//! Builders without own tenant take the tenant of the outer builder.
+//!
+//! ```
+//! # use anyhow::Result;
+//! use sql_builder::SqlBuilder;
+//!
+//! # fn main() -> Result<()> {
+//! let sql = SqlBuilder::select_from("books")
+//!     .field("title")
+//!     .and_where_in_query("author_id", SqlBuilder::select_from("authors").field("id").and_where("country = ?"))
+//!     .sql()?;
+//!
+//! assert_eq!("SELECT title FROM books WHERE author_id IN (SELECT id FROM authors WHERE country = ?);", &sql);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::compound::CompoundBuilder;
+use crate::error::SqlBuilderError;
+use crate::select::SelectBuilder;
+use crate::SqlBuilder;
+use anyhow::Result;
+
+/// Query or table nested into statement
+pub trait SqlQuery {
+    /// Query text.
+    fn sql_query(&self) -> Result<String>;
+
+    /// Table text for FROM or JOIN part.
+    /// Builder queries need alias there, so they return an error without one.
+    fn sql_table(&self) -> Result<String>;
+
+    /// Table text to be aliased. Builder queries are parenthesized.
+    fn sql_source(&self) -> Result<String> {
+        self.sql_table()
+    }
+
+    /// Query text nested into builder with the tenant.
+    fn sql_query_for(&self, _tenant: Option<&str>) -> Result<String> {
+        self.sql_query()
+    }
+
+    /// Table text nested into builder with the tenant.
+    fn sql_table_for(&self, _tenant: Option<&str>) -> Result<String> {
+        self.sql_table()
+    }
+
+    /// Table text to be aliased nested into builder with the tenant.
+    fn sql_source_for(&self, _tenant: Option<&str>) -> Result<String> {
+        self.sql_source()
+    }
+
+    /// Use query or table with alias in FROM or JOIN part.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{SqlBuilder, SqlQuery};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let totals = SqlBuilder::select_from("orders")
+    ///     .field("book_id")
+    ///     .field("SUM(total) AS total")
+    ///     .group_by("book_id")
+    ///     .clone();
+    ///
+    /// let sql = SqlBuilder::select_from("books".aliased("b"))
+    ///     .field("b.title")
+    ///     .field("t.total")
+    ///     .left_join(totals.aliased("t"))
+    ///     .on_eq("t.book_id", "b.id")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT b.title, t.total FROM books AS b LEFT JOIN (SELECT book_id, SUM(total) AS total FROM orders GROUP BY book_id) AS t ON t.book_id = b.id;", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn aliased<A: ToString>(self, alias: A) -> Aliased<Self>
+    where
+        Self: Sized,
+    {
+        Aliased {
+            query: self,
+            alias: alias.to_string(),
+        }
+    }
+}
+
+/// Query or table with alias
+pub struct Aliased<Q> {
+    query: Q,
+    alias: String,
+}
+
+impl<Q: SqlQuery> SqlQuery for Aliased<Q> {
+    fn sql_query(&self) -> Result<String> {
+        self.query.sql_query()
+    }
+
+    fn sql_table(&self) -> Result<String> {
+        Ok(format!("{} AS {}", self.query.sql_source()?, self.alias))
+    }
+
+    fn sql_query_for(&self, tenant: Option<&str>) -> Result<String> {
+        self.query.sql_query_for(tenant)
+    }
+
+    fn sql_table_for(&self, tenant: Option<&str>) -> Result<String> {
+        Ok(format!(
+            "{} AS {}",
+            self.query.sql_source_for(tenant)?,
+            self.alias
+        ))
+    }
+}
+
+impl<T: ToString> SqlQuery for T {
+    fn sql_query(&self) -> Result<String> {
+        Ok(self.to_string())
+    }
+
+    fn sql_table(&self) -> Result<String> {
+        Ok(self.to_string())
+    }
+}
+
+impl SqlQuery for SqlBuilder {
+    fn sql_query(&self) -> Result<String> {
+        self.query_any()
+    }
+
+    fn sql_table(&self) -> Result<String> {
+        Err(no_alias())
+    }
+
+    fn sql_source(&self) -> Result<String> {
+        Ok(format!("({})", self.query_any()?))
+    }
+
+    fn sql_query_for(&self, tenant: Option<&str>) -> Result<String> {
+        self.inherit_tenant(tenant).query_any()
+    }
+
+    fn sql_source_for(&self, tenant: Option<&str>) -> Result<String> {
+        Ok(format!("({})", self.inherit_tenant(tenant).query_any()?))
+    }
+}
+
+impl SqlQuery for &SqlBuilder {
+    fn sql_query(&self) -> Result<String> {
+        (*self).sql_query()
+    }
+
+    fn sql_table(&self) -> Result<String> {
+        (*self).sql_table()
+    }
+    fn sql_source(&self) -> Result<String> {
+        (*self).sql_source()
+    }
+
+    fn sql_query_for(&self, tenant: Option<&str>) -> Result<String> {
+        (*self).sql_query_for(tenant)
+    }
+
+    fn sql_source_for(&self, tenant: Option<&str>) -> Result<String> {
+        (*self).sql_source_for(tenant)
+    }
+}
+
+impl SqlQuery for &mut SqlBuilder {
+    fn sql_query(&self) -> Result<String> {
+        (**self).sql_query()
+    }
+
+    fn sql_table(&self) -> Result<String> {
+        (**self).sql_table()
+    }
+    fn sql_source(&self) -> Result<String> {
+        (**self).sql_source()
+    }
+
+    fn sql_query_for(&self, tenant: Option<&str>) -> Result<String> {
+        (**self).sql_query_for(tenant)
+    }
+
+    fn sql_source_for(&self, tenant: Option<&str>) -> Result<String> {
+        (**self).sql_source_for(tenant)
+    }
+}
+
+impl SqlQuery for SelectBuilder {
+    fn sql_query(&self) -> Result<String> {
+        self.query()
+    }
+
+    fn sql_table(&self) -> Result<String> {
+        Err(no_alias())
+    }
+
+    fn sql_source(&self) -> Result<String> {
+        Ok(format!("({})", self.query()?))
+    }
+    fn sql_query_for(&self, tenant: Option<&str>) -> Result<String> {
+        self.inherit_tenant(tenant).query()
+    }
+
+    fn sql_source_for(&self, tenant: Option<&str>) -> Result<String> {
+        Ok(format!("({})", self.inherit_tenant(tenant).query()?))
+    }
+}
+
+impl SqlQuery for &SelectBuilder {
+    fn sql_query(&self) -> Result<String> {
+        (*self).sql_query()
+    }
+
+    fn sql_table(&self) -> Result<String> {
+        (*self).sql_table()
+    }
+    fn sql_source(&self) -> Result<String> {
+        (*self).sql_source()
+    }
+
+    fn sql_query_for(&self, tenant: Option<&str>) -> Result<String> {
+        (*self).sql_query_for(tenant)
+    }
+
+    fn sql_source_for(&self, tenant: Option<&str>) -> Result<String> {
+        (*self).sql_source_for(tenant)
+    }
+}
+
+impl SqlQuery for &mut SelectBuilder {
+    fn sql_query(&self) -> Result<String> {
+        (**self).sql_query()
+    }
+
+    fn sql_table(&self) -> Result<String> {
+        (**self).sql_table()
+    }
+    fn sql_source(&self) -> Result<String> {
+        (**self).sql_source()
+    }
+
+    fn sql_query_for(&self, tenant: Option<&str>) -> Result<String> {
+        (**self).sql_query_for(tenant)
+    }
+
+    fn sql_source_for(&self, tenant: Option<&str>) -> Result<String> {
+        (**self).sql_source_for(tenant)
+    }
+}
+
+impl SqlQuery for CompoundBuilder {
+    fn sql_query(&self) -> Result<String> {
+        self.query()
+    }
+
+    fn sql_table(&self) -> Result<String> {
+        Err(no_alias())
+    }
+
+    fn sql_source(&self) -> Result<String> {
+        Ok(format!("({})", self.query()?))
+    }
+}
+
+impl SqlQuery for &CompoundBuilder {
+    fn sql_query(&self) -> Result<String> {
+        (*self).sql_query()
+    }
+
+    fn sql_table(&self) -> Result<String> {
+        (*self).sql_table()
+    }
+    fn sql_source(&self) -> Result<String> {
+        (*self).sql_source()
+    }
+}
+
+impl SqlQuery for &mut CompoundBuilder {
+    fn sql_query(&self) -> Result<String> {
+        (**self).sql_query()
+    }
+
+    fn sql_table(&self) -> Result<String> {
+        (**self).sql_table()
+    }
+    fn sql_source(&self) -> Result<String> {
+        (**self).sql_source()
+    }
+}
+
+/// Error of subquery used in FROM or JOIN part without alias
+fn no_alias() -> anyhow::Error {
+    SqlBuilderError::InvalidQuery("subquery in FROM or JOIN needs alias".to_string()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nested_query() -> Result<()> {
+        let sql = SqlBuilder::insert_into("archive")
+            .fields(["title", "price"])
+            .select(
+                SqlBuilder::select_from("books")
+                    .fields(["title", "price"])
+                    .and_where("year < ?"),
+            )
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "INSERT INTO archive (title, price) SELECT title, price FROM books WHERE year < ?;"
+        );
+
+        let drafts = SelectBuilder::from_table("drafts").field("title").clone();
+        let sql = SqlBuilder::select_from((&drafts).aliased("d"))
+            .field("title")
+            .union(&drafts)
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT title FROM (SELECT title FROM drafts) AS d UNION SELECT title FROM drafts;"
+        );
+
+        let res = SqlBuilder::select_from(&drafts).field("title").sql();
+        assert_eq!(
+            &res.unwrap_err().to_string(),
+            "Query is invalid: subquery in FROM or JOIN needs alias"
+        );
+
+        let res = SqlBuilder::select_from("books")
+            .or_where_not_in_query("id", SqlBuilder::select_from("sales").and_where_eq("", 1))
+            .sql();
+        assert_eq!(&res.unwrap_err().to_string(), "WHERE field not defined");
+
+        let res = SqlBuilder::select_from(
+            SqlBuilder::select_from("books")
+                .and_where_in("id", &[] as &[i32])
+                .aliased("b"),
+        )
+        .sql();
+        assert_eq!(
+            &res.unwrap_err().to_string(),
+            "WHERE list for field \"id\" not defined"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,452 @@
+//! Joins resolved from declared foreign keys.
+//!
+//! Foreign keys form a graph of tables. A related table is joined through
+//! the shortest path from the first table of FROM part, tables joined
+//! already are reused and new tables get unique aliases.
+//!
+//! ```
+//! # use anyhow::Result;
+//! use sql_builder::{relation, SqlBuilder};
+//!
+//! # fn main() -> Result<()> {
+//! relation::register_foreign_key("books", "author_id", "authors", "id");
+//! relation::register_foreign_key("authors", "country_id", "countries", "id");
+//!
+//! let sql = SqlBuilder::select_from("books AS b")
+//!     .field("b.title")
+//!     .field("countries.name")
+//!     .join_related("countries")
+//!     .sql()?;
+//!
+//! assert_eq!("SELECT b.title, countries.name FROM books AS b JOIN authors ON b.author_id = authors.id JOIN countries ON authors.country_id = countries.id;", &sql);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::SqlBuilderError;
+use crate::scope::table_names;
+use crate::{Join, SqlBuilder};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{OnceLock, RwLock};
+
+/// Foreign key from table column to referenced table column
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct ForeignKey {
+    table: String,
+    column: String,
+    ref_table: String,
+    ref_column: String,
+}
+
+/// Global registry of foreign keys
+fn registry() -> &'static RwLock<Vec<ForeignKey>> {
+    static REGISTRY: OnceLock<RwLock<Vec<ForeignKey>>> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// Register foreign key from the table column to the referenced table column.
+pub fn register_foreign_key<S, C, T, K>(table: S, column: C, ref_table: T, ref_column: K)
+where
+    S: ToString,
+    C: ToString,
+    T: ToString,
+    K: ToString,
+{
+    let key = ForeignKey {
+        table: table.to_string(),
+        column: column.to_string(),
+        ref_table: ref_table.to_string(),
+        ref_column: ref_column.to_string(),
+    };
+    let mut registry = registry().write().unwrap_or_else(|e| e.into_inner());
+    if !registry.contains(&key) {
+        registry.push(key);
+    }
+}
+
+/// Remove all foreign keys of the table.
+pub fn remove_foreign_keys<S: ToString>(table: S) {
+    let table = table.to_string();
+    let mut registry = registry().write().unwrap_or_else(|e| e.into_inner());
+    registry.retain(|key| key.table != table);
+}
+
+/// Step of join path: joined table with its column and column of the previous table
+struct Hop {
+    table: String,
+    column: String,
+    prev_column: String,
+}
+
+/// Find the shortest path of foreign keys between tables
+fn find_path(from: &str, to: &str) -> Option<Vec<Hop>> {
+    let registry = registry().read().unwrap_or_else(|e| e.into_inner());
+
+    let mut prev: HashMap<String, (String, Hop)> = HashMap::new();
+    let mut queue = VecDeque::from([from.to_string()]);
+    while let Some(table) = queue.pop_front() {
+        if table == to {
+            break;
+        }
+        for key in registry.iter() {
+            let hop = if key.table == table {
+                Hop {
+                    table: key.ref_table.clone(),
+                    column: key.ref_column.clone(),
+                    prev_column: key.column.clone(),
+                }
+            } else if key.ref_table == table {
+                Hop {
+                    table: key.table.clone(),
+                    column: key.column.clone(),
+                    prev_column: key.ref_column.clone(),
+                }
+            } else {
+                continue;
+            };
+            if hop.table == from || prev.contains_key(&hop.table) {
+                continue;
+            }
+            queue.push_back(hop.table.clone());
+            prev.insert(hop.table.clone(), (table.clone(), hop));
+        }
+    }
+
+    let mut path = Vec::new();
+    let mut table = to.to_string();
+    while table != from {
+        let (prev_table, hop) = prev.remove(&table)?;
+        path.push(hop);
+        table = prev_table;
+    }
+    path.reverse();
+    Some(path)
+}
+
+/// Find hops to the target through foreign key column from one of the tables.
+/// Self-referential key goes from the referencing column to a copy of the table.
+fn find_via(tables: &[String], to: &str, column: &str) -> Vec<(String, Hop)> {
+    let registry = registry().read().unwrap_or_else(|e| e.into_inner());
+    let mut hops = Vec::new();
+    for key in registry.iter().filter(|key| key.column == column) {
+        if key.ref_table == to && tables.contains(&key.table) {
+            let hop = Hop {
+                table: to.to_string(),
+                column: key.ref_column.clone(),
+                prev_column: key.column.clone(),
+            };
+            hops.push((key.table.clone(), hop));
+        } else if key.table == to && key.ref_table != to && tables.contains(&key.ref_table) {
+            let hop = Hop {
+                table: to.to_string(),
+                column: key.column.clone(),
+                prev_column: key.ref_column.clone(),
+            };
+            hops.push((key.ref_table.clone(), hop));
+        }
+    }
+    hops
+}
+
+/// Find hops from the table to its copy through self-referential foreign keys
+fn self_hops(table: &str) -> Vec<Hop> {
+    let registry = registry().read().unwrap_or_else(|e| e.into_inner());
+    registry
+        .iter()
+        .filter(|key| key.table == table && key.ref_table == table)
+        .map(|key| Hop {
+            table: table.to_string(),
+            column: key.ref_column.clone(),
+            prev_column: key.column.clone(),
+        })
+        .collect()
+}
+
+/// Count foreign keys between the tables in any direction
+fn count_keys(a: &str, b: &str) -> usize {
+    let registry = registry().read().unwrap_or_else(|e| e.into_inner());
+    registry
+        .iter()
+        .filter(|key| {
+            (key.table == a && key.ref_table == b) || (key.table == b && key.ref_table == a)
+        })
+        .count()
+}
+
+impl SqlBuilder {
+    /// Join related table through declared foreign keys.
+    /// Tables already joined are reused, several foreign keys between
+    /// tables to join are ambiguous, use [`join_related_via`](Self::join_related_via) then.
+    /// Table with self-referential foreign key is joined as a copy with alias.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{relation, SqlBuilder};
+    ///
+    /// # fn main() -> Result<()> {
+    /// relation::register_foreign_key("posts", "author_id", "users", "id");
+    /// relation::register_foreign_key("posts", "editor_id", "users", "id");
+    ///
+    /// let sql = SqlBuilder::select_from("posts")
+    ///     .field("posts.title")
+    ///     .join("users ON posts.editor_id = users.id")
+    ///     .left_join_related("users")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT posts.title FROM posts JOIN users ON posts.editor_id = users.id;", &sql);
+    ///
+    /// let res = SqlBuilder::select_from("posts")
+    ///     .join_related("payments")
+    ///     .sql();
+    ///
+    /// assert_eq!("Relation to table \"payments\" not defined", &res.unwrap_err().to_string());
+    ///
+    /// let res = SqlBuilder::select_from("posts")
+    ///     .join_related("users")
+    ///     .sql();
+    ///
+    /// assert_eq!("Relation to table \"users\" is ambiguous", &res.unwrap_err().to_string());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn join_related<S: ToString>(&mut self, table: S) -> &mut Self {
+        self.add_related("JOIN", &table.to_string())
+    }
+
+    /// Join related table through declared foreign keys with LEFT JOIN.
+    pub fn left_join_related<S: ToString>(&mut self, table: S) -> &mut Self {
+        self.add_related("LEFT JOIN", &table.to_string())
+    }
+
+    /// Join related table through the foreign key column of the table
+    /// or of one of the joined tables.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{relation, SqlBuilder};
+    ///
+    /// # fn main() -> Result<()> {
+    /// relation::register_foreign_key("articles", "author_id", "people", "id");
+    /// relation::register_foreign_key("articles", "editor_id", "people", "id");
+    /// relation::register_foreign_key("people", "manager_id", "people", "id");
+    ///
+    /// let sql = SqlBuilder::select_from("articles")
+    ///     .join_related_via("people", "editor_id")
+    ///     .left_join_related_via("people", "manager_id")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM articles JOIN people ON articles.editor_id = people.id LEFT JOIN people AS people_2 ON people.manager_id = people_2.id;", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn join_related_via<S, C>(&mut self, table: S, column: C) -> &mut Self
+    where
+        S: ToString,
+        C: ToString,
+    {
+        self.add_related_via("JOIN", &table.to_string(), &column.to_string())
+    }
+
+    /// Join related table through the foreign key column with LEFT JOIN.
+    pub fn left_join_related_via<S, C>(&mut self, table: S, column: C) -> &mut Self
+    where
+        S: ToString,
+        C: ToString,
+    {
+        self.add_related_via("LEFT JOIN", &table.to_string(), &column.to_string())
+    }
+
+    /// Get names of tables in FROM and JOIN parts with their references
+    fn related_refs(&self) -> Option<Vec<(String, String)>> {
+        let mut refs = Vec::new();
+        for (table, alias) in table_names(&self.table) {
+            refs.push((table.clone(), alias.unwrap_or(table)));
+        }
+        for (table, alias) in self.joins.iter().filter_map(|join| join.target()) {
+            refs.push((table.clone(), alias.unwrap_or(table)));
+        }
+        if refs.is_empty() {
+            None
+        } else {
+            Some(refs)
+        }
+    }
+
+    /// Add JOIN part through the foreign key column
+    fn add_related_via(&mut self, kind: &str, target: &str, column: &str) -> &mut Self {
+        let Some(mut refs) = self.related_refs() else {
+            return self.set_error(&SqlBuilderError::NoTableName);
+        };
+        let tables: Vec<String> = refs.iter().map(|(table, _)| table.clone()).collect();
+        let mut hops = find_via(&tables, target, column);
+        if hops.len() > 1 {
+            return self.set_error(&SqlBuilderError::AmbiguousRelation(target.to_string()));
+        }
+        let Some((prev_table, hop)) = hops.pop() else {
+            return self.set_error(&SqlBuilderError::NoRelation(target.to_string()));
+        };
+        let prev_ref = refs
+            .iter()
+            .rev()
+            .find(|(table, _)| table == &prev_table)
+            .map(|(_, name)| name.clone())
+            .unwrap_or(prev_table);
+        self.push_related(kind, &mut refs, &prev_ref, hop);
+        self
+    }
+
+    /// Add JOIN part of the hop with alias unique among used names
+    fn push_related(
+        &mut self,
+        kind: &str,
+        refs: &mut Vec<(String, String)>,
+        prev_ref: &str,
+        hop: Hop,
+    ) -> String {
+        let mut name = hop.table.clone();
+        let mut n = 1;
+        while refs.iter().any(|(_, used)| used == &name) {
+            n += 1;
+            name = format!("{}_{}", hop.table, n);
+        }
+        let target = if name == hop.table {
+            hop.table.clone()
+        } else {
+            format!("{} AS {}", hop.table, name)
+        };
+
+        let mut join = Join::new(kind, &target);
+        join.and_on(format!(
+            "{}.{} = {}.{}",
+            prev_ref, hop.prev_column, name, hop.column
+        ));
+        self.joins.push(join);
+        refs.push((hop.table, name.clone()));
+        name
+    }
+
+    /// Add JOIN parts of the path to related table
+    fn add_related(&mut self, kind: &str, target: &str) -> &mut Self {
+        let Some(mut refs) = self.related_refs() else {
+            return self.set_error(&SqlBuilderError::NoTableName);
+        };
+        let (base, base_ref) = refs[0].clone();
+
+        // Self-referential foreign key joins a copy of the table
+        if base == target {
+            let mut hops = self_hops(&base);
+            if hops.len() > 1 {
+                return self.set_error(&SqlBuilderError::AmbiguousRelation(base));
+            }
+            let Some(hop) = hops.pop() else {
+                return self.set_error(&SqlBuilderError::NoRelation(base));
+            };
+            self.push_related(kind, &mut refs, &base_ref, hop);
+            return self;
+        }
+
+        let Some(path) = find_path(&base, target) else {
+            return self.set_error(&SqlBuilderError::NoRelation(target.to_string()));
+        };
+
+        let (mut prev_table, mut prev_ref) = (base, base_ref);
+        for hop in path {
+            if let Some((table, name)) = refs.iter().find(|(table, _)| table == &hop.table) {
+                prev_table = table.clone();
+                prev_ref = name.clone();
+                continue;
+            }
+            if count_keys(&prev_table, &hop.table) > 1 {
+                return self.set_error(&SqlBuilderError::AmbiguousRelation(hop.table));
+            }
+            prev_table = hop.table.clone();
+            prev_ref = self.push_related(kind, &mut refs, &prev_ref, hop);
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    #[test]
+    fn test_join_related() -> Result<()> {
+        register_foreign_key("rel_orders", "customer_id", "rel_customers", "id");
+        register_foreign_key("rel_customers", "city_id", "rel_cities", "id");
+        register_foreign_key("rel_shops", "city_id", "rel_cities", "id");
+
+        let sql = SqlBuilder::select_from("rel_shops")
+            .field("COUNT(*)")
+            .join_related("rel_orders")
+            .left_join_related("rel_cities")
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT COUNT(*) FROM rel_shops JOIN rel_cities ON rel_shops.city_id = rel_cities.id JOIN rel_customers ON rel_cities.id = rel_customers.city_id JOIN rel_orders ON rel_customers.id = rel_orders.customer_id;"
+        );
+
+        let sql = SqlBuilder::select_from("rel_orders AS o, legacy AS rel_customers")
+            .join_related("rel_customers")
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT * FROM rel_orders AS o, legacy AS rel_customers JOIN rel_customers AS rel_customers_2 ON o.customer_id = rel_customers_2.id;"
+        );
+
+        remove_foreign_keys("rel_shops");
+        let res = SqlBuilder::select_from("rel_shops")
+            .join_related("rel_cities")
+            .sql();
+        assert_eq!(
+            &res.unwrap_err().to_string(),
+            "Relation to table \"rel_cities\" not defined"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_join_related_self_and_ambiguous() -> Result<()> {
+        register_foreign_key("rel_staff", "manager_id", "rel_staff", "id");
+        register_foreign_key("rel_tasks", "owner_id", "rel_staff", "id");
+        register_foreign_key("rel_tasks", "reviewer_id", "rel_staff", "id");
+
+        let sql = SqlBuilder::select_from("rel_staff")
+            .left_join_related("rel_staff")
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT * FROM rel_staff LEFT JOIN rel_staff AS rel_staff_2 ON rel_staff.manager_id = rel_staff_2.id;"
+        );
+
+        let res = SqlBuilder::select_from("rel_tasks")
+            .join_related("rel_staff")
+            .sql();
+        assert_eq!(
+            &res.unwrap_err().to_string(),
+            "Relation to table \"rel_staff\" is ambiguous"
+        );
+
+        let sql = SqlBuilder::select_from("rel_staff AS s")
+            .join_related_via("rel_tasks", "reviewer_id")
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT * FROM rel_staff AS s JOIN rel_tasks ON s.id = rel_tasks.reviewer_id;"
+        );
+
+        remove_foreign_keys("rel_staff");
+        remove_foreign_keys("rel_tasks");
+        let res = SqlBuilder::select_from("rel_staff")
+            .join_related("rel_staff")
+            .sql();
+        assert_eq!(
+            &res.unwrap_err().to_string(),
+            "Relation to table \"rel_staff\" not defined"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,386 @@
+//! Row-value conditions for composite keys.
+//!
+//! Conditions like `(a, b) IN ((1, 2), (3, 4))` and `(a, b) > (1, 2)` are
+//! rendered as row values if the dialect supports them, otherwise they are
+//! expanded into equivalent OR and AND chains.
+//!
+//! ```
+//! # use anyhow::Result;
+//! use sql_builder::{Dialect, SqlBuilder};
+//!
+//! # fn main() -> Result<()> {
+//! let sql = SqlBuilder::select_from("order_items")
+//!     .dialect(Dialect::Postgres)
+//!     .and_where_row_in(&["order_id", "line"], &[(1, 2), (3, 4)])
+//!     .sql()?;
+//!
+//! assert_eq!("SELECT * FROM order_items WHERE (order_id, line) IN ((1, 2), (3, 4));", &sql);
+//!
+//! let sql = SqlBuilder::select_from("order_items")
+//!     .dialect(Dialect::MsSql)
+//!     .and_where_row_in(&["order_id", "line"], &[(1, 2), (3, 4)])
+//!     .sql()?;
+//!
+//! assert_eq!("SELECT * FROM order_items WHERE (order_id = 1 AND line = 2) OR (order_id = 3 AND line = 4);", &sql);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::arg::SqlArgs;
+use crate::error::SqlBuilderError;
+use crate::page::{keyset_cond, SortKey};
+use crate::SqlBuilder;
+
+/// Join alternatives with OR, parenthesized if there are several of them
+fn any_of(alts: Vec<String>) -> String {
+    if alts.len() == 1 {
+        return alts.into_iter().next().unwrap_or_default();
+    }
+    alts.iter()
+        .map(|alt| format!("({})", alt))
+        .collect::<Vec<String>>()
+        .join(" OR ")
+}
+
+/// Make equality of columns and values joined with AND
+fn all_equal(columns: &[String], values: &[String]) -> String {
+    columns
+        .iter()
+        .zip(values)
+        .map(|(column, value)| format!("{} = {}", column, value))
+        .collect::<Vec<String>>()
+        .join(" AND ")
+}
+
+impl SqlBuilder {
+    /// Add WHERE (columns) IN (rows).
+    pub fn and_where_row_in<S, T>(&mut self, columns: &[S], rows: &[T]) -> &mut Self
+    where
+        S: ToString,
+        T: SqlArgs,
+    {
+        match self.row_in(columns, rows, false) {
+            Some(cond) => self.and_where(cond),
+            None => self,
+        }
+    }
+
+    /// Add WHERE (columns) NOT IN (rows).
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::SqlBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::delete_from("order_items")
+    ///     .and_where_row_not_in(&["order_id", "line"], &[(1, 2)])
+    ///     .sql()?;
+    ///
+    /// assert_eq!("DELETE FROM order_items WHERE NOT (order_id = 1 AND line = 2);", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_where_row_not_in<S, T>(&mut self, columns: &[S], rows: &[T]) -> &mut Self
+    where
+        S: ToString,
+        T: SqlArgs,
+    {
+        match self.row_in(columns, rows, true) {
+            Some(cond) => self.and_where(cond),
+            None => self,
+        }
+    }
+
+    /// Add OR (columns) IN (rows) to the last WHERE condition.
+    pub fn or_where_row_in<S, T>(&mut self, columns: &[S], rows: &[T]) -> &mut Self
+    where
+        S: ToString,
+        T: SqlArgs,
+    {
+        match self.row_in(columns, rows, false) {
+            Some(cond) => self.or_where(cond),
+            None => self,
+        }
+    }
+
+    /// Add WHERE (columns) = (values).
+    pub fn and_where_row_eq<S: ToString, T: SqlArgs>(
+        &mut self,
+        columns: &[S],
+        values: T,
+    ) -> &mut Self {
+        self.and_where_row_cmp(columns, "=", values)
+    }
+
+    /// Add WHERE (columns) <> (values).
+    pub fn and_where_row_ne<S: ToString, T: SqlArgs>(
+        &mut self,
+        columns: &[S],
+        values: T,
+    ) -> &mut Self {
+        self.and_where_row_cmp(columns, "<>", values)
+    }
+
+    /// Add WHERE (columns) > (values).
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{Dialect, SqlBuilder};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("events")
+    ///     .and_where_row_gt(&["day", "seq"], ("2024-01-01", 10))
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM events WHERE (day > '2024-01-01') OR (day = '2024-01-01' AND seq > 10);", &sql);
+    ///
+    /// let sql = SqlBuilder::select_from("events")
+    ///     .dialect(Dialect::Sqlite)
+    ///     .and_where_row_gt(&["day", "seq"], ("2024-01-01", 10))
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM events WHERE (day, seq) > ('2024-01-01', 10);", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_where_row_gt<S: ToString, T: SqlArgs>(
+        &mut self,
+        columns: &[S],
+        values: T,
+    ) -> &mut Self {
+        self.and_where_row_cmp(columns, ">", values)
+    }
+
+    /// Add WHERE (columns) >= (values).
+    pub fn and_where_row_ge<S: ToString, T: SqlArgs>(
+        &mut self,
+        columns: &[S],
+        values: T,
+    ) -> &mut Self {
+        self.and_where_row_cmp(columns, ">=", values)
+    }
+
+    /// Add WHERE (columns) < (values).
+    pub fn and_where_row_lt<S: ToString, T: SqlArgs>(
+        &mut self,
+        columns: &[S],
+        values: T,
+    ) -> &mut Self {
+        self.and_where_row_cmp(columns, "<", values)
+    }
+
+    /// Add WHERE (columns) <= (values).
+    pub fn and_where_row_le<S: ToString, T: SqlArgs>(
+        &mut self,
+        columns: &[S],
+        values: T,
+    ) -> &mut Self {
+        self.and_where_row_cmp(columns, "<=", values)
+    }
+
+    /// Add WHERE comparison of columns with values by the operator.
+    pub fn and_where_row_cmp<S: ToString, T: SqlArgs>(
+        &mut self,
+        columns: &[S],
+        op: &str,
+        values: T,
+    ) -> &mut Self {
+        match self.row_cmp(columns, op, &values.sql_args()) {
+            Some(cond) => self.and_where(cond),
+            None => self,
+        }
+    }
+
+    /// Check columns and values of row
+    fn row_columns<S: ToString>(
+        &mut self,
+        columns: &[S],
+        values: &[String],
+    ) -> Option<Vec<String>> {
+        let columns: Vec<String> = columns.iter().map(|c| c.to_string()).collect();
+        if columns.is_empty() || columns.iter().any(|c| c.is_empty()) {
+            self.set_error(&SqlBuilderError::NoWhereField);
+            return None;
+        }
+        if columns.len() != values.len() {
+            self.set_error(&SqlBuilderError::InvalidRowLength(
+                columns.len(),
+                values.len(),
+            ));
+            return None;
+        }
+        Some(columns)
+    }
+
+    /// Make row-value IN condition
+    fn row_in<S: ToString, T: SqlArgs>(
+        &mut self,
+        columns: &[S],
+        rows: &[T],
+        not: bool,
+    ) -> Option<String> {
+        let rows: Vec<Vec<String>> = rows.iter().map(|row| row.sql_args()).collect();
+        if rows.is_empty() {
+            let columns: Vec<String> = columns.iter().map(|c| c.to_string()).collect();
+            self.set_error(&SqlBuilderError::NoWhereList(columns.join(", ")));
+            return None;
+        }
+        let mut checked = None;
+        for row in &rows {
+            checked = Some(self.row_columns(columns, row)?);
+        }
+        let columns = checked?;
+        let op = if not { "NOT IN" } else { "IN" };
+
+        if columns.len() == 1 {
+            let list: Vec<&str> = rows.iter().map(|row| row[0].as_str()).collect();
+            return Some(format!("{} {} ({})", columns[0], op, list.join(", ")));
+        }
+        if self.dialect.row_values() {
+            let list: Vec<String> = rows
+                .iter()
+                .map(|row| format!("({})", row.join(", ")))
+                .collect();
+            return Some(format!(
+                "({}) {} ({})",
+                columns.join(", "),
+                op,
+                list.join(", ")
+            ));
+        }
+
+        let cond = any_of(rows.iter().map(|row| all_equal(&columns, row)).collect());
+        if not {
+            Some(format!("NOT ({})", cond))
+        } else {
+            Some(cond)
+        }
+    }
+
+    /// Make row-value comparison
+    fn row_cmp<S: ToString>(
+        &mut self,
+        columns: &[S],
+        op: &str,
+        values: &[String],
+    ) -> Option<String> {
+        if !["=", "<>", "!=", "<", "<=", ">", ">="].contains(&op) {
+            self.set_error(&SqlBuilderError::UnknownOperator(op.to_string()));
+            return None;
+        }
+        let columns = self.row_columns(columns, values)?;
+
+        if columns.len() == 1 {
+            return Some(format!("{} {} {}", columns[0], op, values[0]));
+        }
+        if self.dialect.row_values() {
+            return Some(format!(
+                "({}) {} ({})",
+                columns.join(", "),
+                op,
+                values.join(", ")
+            ));
+        }
+
+        let cond = match op {
+            "=" => all_equal(&columns, values),
+            "<>" | "!=" => columns
+                .iter()
+                .zip(values)
+                .map(|(column, value)| format!("{} {} {}", column, op, value))
+                .collect::<Vec<String>>()
+                .join(" OR "),
+            _ => {
+                // Keyset condition of rows after the values in sort order
+                let keys: Vec<SortKey> = columns
+                    .iter()
+                    .map(|column| {
+                        if op.starts_with('>') {
+                            SortKey::asc(column)
+                        } else {
+                            SortKey::desc(column)
+                        }
+                    })
+                    .collect();
+                let cond = match keyset_cond(&keys, values, self.dialect) {
+                    Ok(cond) => cond,
+                    Err(err) => {
+                        self.set_error(&err);
+                        return None;
+                    }
+                };
+                if op.ends_with('=') {
+                    format!("{} OR ({})", cond, all_equal(&columns, values))
+                } else {
+                    cond
+                }
+            }
+        };
+        Some(cond)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Dialect, SqlBuilder};
+    use anyhow::Result;
+
+    #[test]
+    fn test_row_values() -> Result<()> {
+        let sql = SqlBuilder::select_from("t")
+            .and_where_row_le(&["a", "b", "c"], (1, 2, 3))
+            .and_where_row_ne(&["a", "b"], (4, 5))
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT * FROM t WHERE ((a < 1) OR (a = 1 AND b < 2) OR (a = 1 AND b = 2 AND c < 3) OR (a = 1 AND b = 2 AND c = 3)) AND (a <> 4 OR b <> 5);"
+        );
+
+        let sql = SqlBuilder::select_from("t")
+            .dialect(Dialect::MySql)
+            .and_where_row_not_in(&["a", "b"], &[("x", 1), ("y", 2)])
+            .or_where_row_in(&["a"], &[("z",)])
+            .and_where_row_eq(&["a", "b"], ("x", 1))
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT * FROM t WHERE ((a, b) NOT IN (('x', 1), ('y', 2)) OR a IN ('z')) AND ((a, b) = ('x', 1));"
+        );
+
+        let sql = SqlBuilder::select_from("t")
+            .dialect(Dialect::MySql)
+            .and_where_row_not_in(&["IF(c IN (1), a, b)", "d"], &[(1, 2)])
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT * FROM t WHERE (IF(c IN (1), a, b), d) NOT IN ((1, 2));"
+        );
+
+        let sql = SqlBuilder::select_from("t")
+            .and_where_row_not_in(&["a"], &[(1,), (2,)])
+            .and_where_row_not_in(&["a", "b"], &[(1, 2), (3, 4)])
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT * FROM t WHERE (a NOT IN (1, 2)) AND (NOT ((a = 1 AND b = 2) OR (a = 3 AND b = 4)));"
+        );
+
+        let res = SqlBuilder::select_from("t")
+            .and_where_row_in(&["a", "b"], &[(1, 2, 3)])
+            .sql();
+        assert_eq!(
+            &res.unwrap_err().to_string(),
+            "Row has 3 values instead of 2"
+        );
+
+        let res = SqlBuilder::select_from("t")
+            .and_where_row_cmp(&["a", "b"], "LIKE", (1, 2))
+            .sql();
+        assert_eq!(
+            &res.unwrap_err().to_string(),
+            "Comparison operator \"LIKE\" is not supported"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,385 @@
+//! Default scopes and soft-delete registered per table.
+//!
+//! Scopes are named condition sets which are automatically added to every
+//! SELECT, UPDATE and DELETE of the table. Soft-delete also turns DELETE
+//! into UPDATE. In queries with several tables, scoped tables of FROM and
+//! JOIN parts are replaced with filtered subqueries, so unqualified columns
+//! of conditions stay bound to their table.
+//!
+//! ```
+//! # use anyhow::Result;
+//! use sql_builder::{scope, SqlBuilder};
+//!
+//! # fn main() -> Result<()> {
+//! scope::register_scope("articles", "published", &["published"]);
+//! scope::register_soft_delete("articles", "deleted_at");
+//!
+//! let sql = SqlBuilder::select_from("articles")
+//!     .field("title")
+//!     .sql()?;
+//!
+//! assert_eq!("SELECT title FROM articles WHERE (published) AND (deleted_at IS NULL);", &sql);
+//!
+//! let sql = SqlBuilder::delete_from("articles")
+//!     .and_where_eq("id", 42)
+//!     .sql()?;
+//!
+//! assert_eq!("UPDATE articles SET deleted_at = now() WHERE (id = 42) AND (published) AND (deleted_at IS NULL);", &sql);
+//!
+//! let sql = SqlBuilder::select_from("articles AS a")
+//!     .field("a.title")
+//!     .join("authors AS u")
+//!     .on("u.id = a.author_id")
+//!     .sql()?;
+//!
+//! assert_eq!("SELECT a.title FROM (SELECT * FROM articles WHERE (published) AND (deleted_at IS NULL)) AS a JOIN authors AS u ON u.id = a.author_id;", &sql);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::Join;
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+/// Name of the scope created by soft-delete registration
+pub const SOFT_DELETE_SCOPE: &str = "soft_delete";
+
+/// Scopes of one table
+#[derive(Clone, Default)]
+struct TableScopes {
+    scopes: Vec<(String, Vec<String>)>,
+    soft_delete: Option<SoftDelete>,
+}
+
+/// Soft-delete column and value
+#[derive(Clone)]
+pub(crate) struct SoftDelete {
+    pub(crate) column: String,
+    pub(crate) value: String,
+}
+
+/// Global scope registry
+fn registry() -> &'static RwLock<HashMap<String, TableScopes>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<String, TableScopes>>> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// Register named scope for the table.
+/// Scope with the same name is replaced.
+///
+/// ```
+/// # use anyhow::Result;
+/// use sql_builder::{scope, SqlBuilder};
+///
+/// # fn main() -> Result<()> {
+/// scope::register_scope("posts", "visible", &["NOT hidden", "score > 0"]);
+///
+/// let sql = SqlBuilder::select_from("posts").sql()?;
+///
+/// assert_eq!("SELECT * FROM posts WHERE (NOT hidden) AND (score > 0);", &sql);
+/// # Ok(())
+/// # }
+/// ```
+pub fn register_scope<S, N, C, I>(table: S, name: N, conds: I)
+where
+    S: ToString,
+    N: ToString,
+    C: ToString,
+    I: IntoIterator<Item = C>,
+{
+    let name = name.to_string();
+    let conds = conds.into_iter().map(|c| c.to_string()).collect();
+    let mut registry = registry().write().unwrap_or_else(|e| e.into_inner());
+    let scopes = &mut registry.entry(table.to_string()).or_default().scopes;
+    match scopes.iter_mut().find(|(n, _)| n == &name) {
+        Some(scope) => scope.1 = conds,
+        None => scopes.push((name, conds)),
+    }
+}
+
+/// Register soft-delete column for the table.
+/// Deleted rows are marked with `now()`.
+pub fn register_soft_delete<S, C>(table: S, column: C)
+where
+    S: ToString,
+    C: ToString,
+{
+    register_soft_delete_value(table, column, "now()")
+}
+
+/// Register soft-delete column for the table with the value expression.
+///
+/// ```
+/// # use anyhow::Result;
+/// use sql_builder::{scope, SqlBuilder};
+///
+/// # fn main() -> Result<()> {
+/// scope::register_soft_delete_value("comments", "deleted_at", "CURRENT_TIMESTAMP");
+///
+/// let sql = SqlBuilder::delete_from("comments")
+///     .and_where_eq("id", 42)
+///     .sql()?;
+///
+/// assert_eq!("UPDATE comments SET deleted_at = CURRENT_TIMESTAMP WHERE (id = 42) AND (deleted_at IS NULL);", &sql);
+/// # Ok(())
+/// # }
+/// ```
+pub fn register_soft_delete_value<S, C, V>(table: S, column: C, value: V)
+where
+    S: ToString,
+    C: ToString,
+    V: ToString,
+{
+    let table = table.to_string();
+    let column = column.to_string();
+    register_scope(&table, SOFT_DELETE_SCOPE, &[format!("{} IS NULL", column)]);
+
+    let mut registry = registry().write().unwrap_or_else(|e| e.into_inner());
+    registry.entry(table).or_default().soft_delete = Some(SoftDelete {
+        column,
+        value: value.to_string(),
+    });
+}
+
+/// Remove all scopes and soft-delete of the table.
+pub fn remove_scopes<S: ToString>(table: S) {
+    let mut registry = registry().write().unwrap_or_else(|e| e.into_inner());
+    registry.remove(&table.to_string());
+}
+
+/// Make scope conditions for all tables of FROM part, except skipped scopes
+pub(crate) fn scope_conds(from: &str, skip: &[String]) -> Vec<String> {
+    let registry = registry().read().unwrap_or_else(|e| e.into_inner());
+    if registry.is_empty() {
+        return Vec::new();
+    }
+
+    let mut conds = Vec::new();
+    for (table, alias) in table_names(from) {
+        if let Some(table_scopes) = registry.get(&table) {
+            conds.append(&mut table_scopes.conds(&alias, skip));
+        }
+    }
+    conds
+}
+
+/// Apply scopes to FROM part and JOIN parts, except skipped scopes.
+/// Conditions of single table are returned for WHERE part, tables of
+/// queries with several tables are filtered in place.
+pub(crate) fn scope_sources(
+    from: &str,
+    joins: &mut [Join],
+    skip: &[String],
+) -> (String, Vec<String>) {
+    let parts = split_from(from);
+    if parts.len() == 1 && joins.is_empty() {
+        return (from.to_string(), scope_conds(from, skip));
+    }
+
+    let registry = registry().read().unwrap_or_else(|e| e.into_inner());
+    if registry.is_empty() {
+        return (from.to_string(), Vec::new());
+    }
+    let conds_of = |source: &str| {
+        part_name(source)
+            .and_then(|(table, _)| registry.get(&table))
+            .map(|table_scopes| table_scopes.conds(&None, skip))
+            .unwrap_or_default()
+    };
+
+    for join in joins.iter_mut() {
+        join.filters.append(&mut conds_of(&join.table));
+    }
+
+    let mut changed = false;
+    let parts: Vec<String> = parts
+        .into_iter()
+        .map(|part| {
+            let conds = conds_of(part);
+            if conds.is_empty() {
+                part.trim().to_string()
+            } else {
+                changed = true;
+                filtered_table(part, &conds)
+            }
+        })
+        .collect();
+    if changed {
+        (parts.join(", "), Vec::new())
+    } else {
+        (from.to_string(), Vec::new())
+    }
+}
+
+/// Make subquery of table source filtered with the conditions,
+/// named as alias of the source or as the table
+pub(crate) fn filtered_table(source: &str, conds: &[String]) -> String {
+    let words: Vec<&str> = source.split_whitespace().collect();
+    let table = words.first().copied().unwrap_or_default();
+    let alias = match words.last() {
+        Some(alias) if words.len() > 1 => alias,
+        _ => table.rsplit('.').next().unwrap_or(table),
+    };
+    let conds = match conds.len() {
+        1 => conds[0].clone(),
+        _ => conds
+            .iter()
+            .map(|c| format!("({})", c))
+            .collect::<Vec<String>>()
+            .join(" AND "),
+    };
+    format!("(SELECT * FROM {} WHERE {}) AS {}", table, conds, alias)
+}
+
+impl TableScopes {
+    /// Make conditions of the scopes, soft-delete column is qualified by alias
+    fn conds(&self, alias: &Option<String>, skip: &[String]) -> Vec<String> {
+        let mut conds = Vec::new();
+        for (name, scope_conds) in &self.scopes {
+            if skip.contains(name) {
+                continue;
+            }
+            if name == SOFT_DELETE_SCOPE {
+                if let Some(soft_delete) = &self.soft_delete {
+                    conds.push(qualified_is_null(&soft_delete.column, alias));
+                    continue;
+                }
+            }
+            conds.extend(scope_conds.iter().cloned());
+        }
+        conds
+    }
+}
+
+/// Find soft-delete settings of the table
+pub(crate) fn soft_delete(from: &str) -> Option<SoftDelete> {
+    let registry = registry().read().unwrap_or_else(|e| e.into_inner());
+    let (table, _) = table_names(from).into_iter().next()?;
+    registry.get(&table)?.soft_delete.clone()
+}
+
+/// Make IS NULL condition qualified by table alias
+fn qualified_is_null(column: &str, alias: &Option<String>) -> String {
+    match alias {
+        Some(alias) => format!("{}.{} IS NULL", alias, column),
+        None => format!("{} IS NULL", column),
+    }
+}
+
+/// Split FROM part into unquoted table names with aliases.
+/// Subqueries and table functions like `unnest(...)` are skipped.
+pub(crate) fn table_names(from: &str) -> Vec<(String, Option<String>)> {
+    split_from(from).into_iter().filter_map(part_name).collect()
+}
+
+/// Split FROM part by commas outside of parentheses and brackets
+fn split_from(from: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, ch) in from.char_indices() {
+        match ch {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&from[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&from[start..]);
+    parts
+}
+
+/// Get unquoted table name and alias of one table source
+fn part_name(part: &str) -> Option<(String, Option<String>)> {
+    let words: Vec<&str> = part.split_whitespace().collect();
+    let table = words.first()?;
+    if table.contains('(') {
+        return None;
+    }
+    let table = table.replace(['`', '"', '[', ']'], "");
+    let alias = if words.len() > 1 {
+        words.last().map(|a| a.to_string())
+    } else {
+        None
+    };
+    Some((table, alias))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SqlBuilder;
+    use anyhow::Result;
+
+    #[test]
+    fn test_table_names() {
+        assert_eq!(
+            table_names("books AS b, `shops` s, (SELECT a, b FROM c) AS x"),
+            vec![
+                ("books".to_string(), Some("b".to_string())),
+                ("shops".to_string(), Some("s".to_string())),
+            ]
+        );
+        assert_eq!(
+            table_names("unnest(ARRAY[1, 2]) AS t(id), [my,table] m"),
+            vec![("my,table".to_string(), Some("m".to_string()))]
+        );
+    }
+
+    #[test]
+    fn test_scopes() -> Result<()> {
+        register_scope("scoped_books", "in_stock", ["stock > 0"]);
+        register_soft_delete("scoped_books", "deleted_at");
+
+        let sql = SqlBuilder::select_from("scoped_books AS b")
+            .field("title")
+            .and_where("price < 100")
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT title FROM scoped_books AS b WHERE (price < 100) AND (stock > 0) AND (b.deleted_at IS NULL);"
+        );
+
+        let sql = SqlBuilder::select_from("scoped_books")
+            .unscope(SOFT_DELETE_SCOPE)
+            .sql()?;
+        assert_eq!(&sql, "SELECT * FROM scoped_books WHERE stock > 0;");
+
+        let sql = SqlBuilder::select_from("scoped_books").unscoped().sql()?;
+        assert_eq!(&sql, "SELECT * FROM scoped_books;");
+
+        let sql = SqlBuilder::delete_from("scoped_books")
+            .and_where("id = 1")
+            .unscoped()
+            .sql()?;
+        assert_eq!(&sql, "DELETE FROM scoped_books WHERE id = 1;");
+
+        let sql = SqlBuilder::update_table("scoped_books")
+            .set("price", 10)
+            .and_where("id = 1")
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "UPDATE scoped_books SET price = 10 WHERE (id = 1) AND (stock > 0) AND (deleted_at IS NULL);"
+        );
+
+        let sql = SqlBuilder::select_from("scoped_books AS b, scoped_shops")
+            .left_join("scoped_books")
+            .using(["isbn"])
+            .unscope("in_stock")
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT * FROM (SELECT * FROM scoped_books WHERE deleted_at IS NULL) AS b, scoped_shops LEFT JOIN (SELECT * FROM scoped_books WHERE deleted_at IS NULL) AS scoped_books USING (isbn);"
+        );
+
+        remove_scopes("scoped_books");
+        let sql = SqlBuilder::select_from("scoped_books").sql()?;
+        assert_eq!(&sql, "SELECT * FROM scoped_books;");
+
+        Ok(())
+    }
+}
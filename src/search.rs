@@ -0,0 +1,316 @@
+//! Full-text search across several columns.
+//!
+//! The input is split into words, every word must be found in one of the
+//! columns. Conditions are rendered for the dialect:
+//!
+//! * Postgres: `to_tsvector(...) @@ plainto_tsquery(...)`
+//! * MySQL: `MATCH(...) AGAINST(... IN BOOLEAN MODE)`, needs FULLTEXT index
+//! * SQLite: FTS5 `MATCH` of the first table in FROM part or its alias
+//! * others: AND-ed `LIKE` conditions with escaped wildcards
+//!
+//! ```
+//! # use anyhow::Result;
+//! use sql_builder::{Dialect, SqlBuilder};
+//!
+//! # fn main() -> Result<()> {
+//! let sql = SqlBuilder::select_from("books")
+//!     .field("title")
+//!     .and_where_search(&["title", "summary"], "hobbit ring")
+//!     .sql()?;
+//!
+//! assert_eq!(r"SELECT title FROM books WHERE (title LIKE '%hobbit%' ESCAPE '\' OR summary LIKE '%hobbit%' ESCAPE '\') AND (title LIKE '%ring%' ESCAPE '\' OR summary LIKE '%ring%' ESCAPE '\');", &sql);
+//!
+//! let sql = SqlBuilder::select_from("books")
+//!     .dialect(Dialect::Postgres)
+//!     .field("title")
+//!     .field_search_rank(&["title", "summary"], "hobbit ring", "rank")
+//!     .and_where_search(&["title", "summary"], "hobbit ring")
+//!     .order_desc("rank")
+//!     .sql()?;
+//!
+//! assert_eq!("SELECT title, ts_rank(to_tsvector(coalesce(title, '') || ' ' || coalesce(summary, '')), plainto_tsquery('hobbit ring')) AS rank FROM books WHERE to_tsvector(coalesce(title, '') || ' ' || coalesce(summary, '')) @@ plainto_tsquery('hobbit ring') ORDER BY rank DESC;", &sql);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::dialect::Dialect;
+use crate::error::SqlBuilderError;
+use crate::scope::table_names;
+use crate::{quote, SqlBuilder};
+
+/// Boolean mode operators of MySQL
+const MYSQL_OPERATORS: &[char] = &['+', '-', '<', '>', '(', ')', '~', '*', '"', '@'];
+
+/// Split search input into words without quotes of phrases and without
+/// MySQL operators around words, other dialects keep words as is
+fn tokens(query: &str, dialect: Dialect) -> Vec<String> {
+    query
+        .split_whitespace()
+        .map(|word| match dialect {
+            Dialect::MySql => word.trim_matches(MYSQL_OPERATORS).replace('"', ""),
+            Dialect::Sqlite => word.replace('"', ""),
+            _ => word.to_string(),
+        })
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+impl SqlBuilder {
+    /// Add WHERE full-text search of the words in the columns.
+    /// Input without words adds no condition.
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use sql_builder::{Dialect, SqlBuilder};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .dialect(Dialect::MySql)
+    ///     .and_where_search(&["title", "summary"], "-hobbit+ ring*")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM books WHERE MATCH(title, summary) AGAINST('+hobbit +ring' IN BOOLEAN MODE);", &sql);
+    ///
+    /// let sql = SqlBuilder::select_from("books")
+    ///     .dialect(Dialect::MySql)
+    ///     .and_where_search(&["summary"], "co-op e-mail")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM books WHERE MATCH(summary) AGAINST('+\"co-op\" +\"e-mail\"' IN BOOLEAN MODE);", &sql);
+    ///
+    /// let sql = SqlBuilder::select_from("books_fts")
+    ///     .dialect(Dialect::Sqlite)
+    ///     .and_where_search(&["title", "summary"], "hobbit \"ring\"")
+    ///     .and_where_search(&["title"], "  ")
+    ///     .sql()?;
+    ///
+    /// assert_eq!("SELECT * FROM books_fts WHERE books_fts MATCH '{title summary} : (\"hobbit\" \"ring\")';", &sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and_where_search<S, T>(&mut self, columns: &[S], query: T) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+    {
+        let columns: Vec<String> = columns.iter().map(|c| c.to_string()).collect();
+        if columns.is_empty() || columns.iter().any(|c| c.is_empty()) {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+        let words = tokens(&query.to_string(), self.dialect);
+        if words.is_empty() {
+            return self;
+        }
+        match self.search_cond(&columns, &words) {
+            Some(cond) => self.and_where(cond),
+            None => self,
+        }
+    }
+
+    /// Add field with relevance of full-text search, greater is better.
+    /// Other dialects than Postgres, MySQL and SQLite count found words.
+    pub fn field_search_rank<S, T, A>(&mut self, columns: &[S], query: T, alias: A) -> &mut Self
+    where
+        S: ToString,
+        T: ToString,
+        A: ToString,
+    {
+        let columns: Vec<String> = columns.iter().map(|c| c.to_string()).collect();
+        if columns.is_empty() || columns.iter().any(|c| c.is_empty()) {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+        let words = tokens(&query.to_string(), self.dialect);
+        let rank = match self.dialect {
+            Dialect::Postgres => format!(
+                "ts_rank({}, plainto_tsquery({}))",
+                ts_vector(&columns),
+                quote(words.join(" "))
+            ),
+            Dialect::MySql => mysql_match(&columns, &words),
+            Dialect::Sqlite => match self.fts_table() {
+                Some(table) => format!("-bm25({})", table),
+                None => return self.set_error(&SqlBuilderError::NoTableName),
+            },
+            _ if words.is_empty() => "0".to_string(),
+            _ => words
+                .iter()
+                .map(|word| {
+                    format!(
+                        "CASE WHEN {} THEN 1 ELSE 0 END",
+                        self.like_any_column(&columns, word)
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join(" + "),
+        };
+        self.field(format!("{} AS {}", rank, alias.to_string()))
+    }
+
+    /// Make search condition for the dialect
+    fn search_cond(&mut self, columns: &[String], words: &[String]) -> Option<String> {
+        Some(match self.dialect {
+            Dialect::Postgres => format!(
+                "{} @@ plainto_tsquery({})",
+                ts_vector(columns),
+                quote(words.join(" "))
+            ),
+            Dialect::MySql => mysql_match(columns, words),
+            Dialect::Sqlite => {
+                let Some(table) = self.fts_table() else {
+                    self.set_error(&SqlBuilderError::NoTableName);
+                    return None;
+                };
+                let phrases = words
+                    .iter()
+                    .map(|word| format!("\"{}\"", word))
+                    .collect::<Vec<String>>();
+                format!(
+                    "{} MATCH {}",
+                    table,
+                    quote(format!(
+                        "{{{}}} : ({})",
+                        columns.join(" "),
+                        phrases.join(" ")
+                    ))
+                )
+            }
+            _ => {
+                let conds = words
+                    .iter()
+                    .map(|word| self.like_any_column(columns, word))
+                    .collect::<Vec<String>>();
+                if conds.len() == 1 {
+                    conds.into_iter().next().unwrap_or_default()
+                } else {
+                    conds
+                        .iter()
+                        .map(|cond| format!("({})", cond))
+                        .collect::<Vec<String>>()
+                        .join(" AND ")
+                }
+            }
+        })
+    }
+
+    /// Get alias or name of FTS5 table, the first table in FROM part
+    fn fts_table(&self) -> Option<String> {
+        let (table, alias) = table_names(&self.table).into_iter().next()?;
+        Some(alias.unwrap_or(table))
+    }
+
+    /// Make LIKE conditions of the word in any of the columns
+    fn like_any_column(&self, columns: &[String], word: &str) -> String {
+        columns
+            .iter()
+            .map(|column| format!("{} LIKE {}", column, self.like_mask("%", word, "%")))
+            .collect::<Vec<String>>()
+            .join(" OR ")
+    }
+}
+
+/// Make text search vector of the columns
+fn ts_vector(columns: &[String]) -> String {
+    let text = columns
+        .iter()
+        .map(|column| format!("coalesce({}, '')", column))
+        .collect::<Vec<String>>()
+        .join(" || ' ' || ");
+    format!("to_tsvector({})", text)
+}
+
+/// Make MySQL boolean mode search requiring every word,
+/// words with operators inside are searched as phrases
+fn mysql_match(columns: &[String], words: &[String]) -> String {
+    let query = words
+        .iter()
+        .map(|word| {
+            if word.contains(MYSQL_OPERATORS) {
+                format!("+\"{}\"", word)
+            } else {
+                format!("+{}", word)
+            }
+        })
+        .collect::<Vec<String>>();
+    format!(
+        "MATCH({}) AGAINST({} IN BOOLEAN MODE)",
+        columns.join(", "),
+        quote(query.join(" "))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    #[test]
+    fn test_search() -> Result<()> {
+        assert_eq!(
+            tokens(" +a  (b) \"c d\" -- e-mail", Dialect::MySql),
+            vec!["a", "b", "c", "d", "e-mail"]
+        );
+
+        let sql = SqlBuilder::select_from("users")
+            .and_where_search(&["email"], "co-op a@b.com 100%")
+            .sql()?;
+        assert_eq!(
+            &sql,
+            r"SELECT * FROM users WHERE (email LIKE '%co-op%' ESCAPE '\') AND (email LIKE '%a@b.com%' ESCAPE '\') AND (email LIKE '%100\%%' ESCAPE '\');"
+        );
+
+        let sql = SqlBuilder::select_from("users")
+            .dialect(Dialect::Postgres)
+            .and_where_search(&["email"], "co-op a@b.com")
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT * FROM users WHERE to_tsvector(coalesce(email, '')) @@ plainto_tsquery('co-op a@b.com');"
+        );
+
+        let sql = SqlBuilder::select_from("books")
+            .dialect(Dialect::MsSql)
+            .field_search_rank(&["title"], "50% off", "found")
+            .and_where_search(&["title"], "50%")
+            .sql()?;
+        assert_eq!(
+            &sql,
+            r"SELECT CASE WHEN title LIKE '%50\%%' ESCAPE '\' THEN 1 ELSE 0 END + CASE WHEN title LIKE '%off%' ESCAPE '\' THEN 1 ELSE 0 END AS found FROM books WHERE title LIKE '%50\%%' ESCAPE '\';"
+        );
+
+        let sql = SqlBuilder::select_from("posts_fts")
+            .dialect(Dialect::Sqlite)
+            .field_search_rank(&["body"], "it's", "rank")
+            .and_where_search(&["body"], "it's")
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT -bm25(posts_fts) AS rank FROM posts_fts WHERE posts_fts MATCH '{body} : (\"it''s\")';"
+        );
+
+        let sql = SqlBuilder::select_from("posts_fts AS p")
+            .dialect(Dialect::Sqlite)
+            .field_search_rank(&["body"], "rust", "rank")
+            .and_where_search(&["body"], "rust")
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT -bm25(p) AS rank FROM posts_fts AS p WHERE p MATCH '{body} : (\"rust\")';"
+        );
+
+        let sql = SqlBuilder::select_from("users")
+            .dialect(Dialect::MySql)
+            .and_where_search(&["email"], "+co-op* a@b.com")
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT * FROM users WHERE MATCH(email) AGAINST('+\"co-op\" +\"a@b.com\"' IN BOOLEAN MODE);"
+        );
+
+        let res = SqlBuilder::select_from("books")
+            .and_where_search(&[""], "x")
+            .sql();
+        assert_eq!(&res.unwrap_err().to_string(), "WHERE field not defined");
+
+        Ok(())
+    }
+}
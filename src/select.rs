@@ -1,21 +1,33 @@
+use crate::arg::SqlArg;
+use crate::dialect::Dialect;
 pub use crate::error::SqlBuilderError;
 pub use crate::name::SqlName;
+use crate::query::SqlQuery;
+use crate::scope;
+use crate::tenant;
+use crate::Join;
 //pub use crate::where::WhereBuilder;
+use crate::compound::CompoundBuilder;
 use anyhow::Result;
+use std::borrow::Cow;
 
 /// Main SQL builder
 #[derive(Clone)]
 pub struct SelectBuilder {
     table: String,
-    joins: Vec<String>,
+    joins: Vec<Join>,
     fields: Vec<String>,
     group_by: Vec<String>,
     having: Option<String>,
-    unions: String,
+    unions: Vec<(&'static str, String)>,
     wheres: Vec<String>,
     order_by: Vec<String>,
     limit: Option<String>,
     offset: Option<String>,
+    unscoped: bool,
+    unscopes: Vec<String>,
+    tenant: Option<String>,
+    pub(crate) dialect: Dialect,
     error: Option<SqlBuilderError>,
 }
 
@@ -28,11 +40,15 @@ impl SelectBuilder {
             fields: Vec::new(),
             group_by: Vec::new(),
             having: None,
-            unions: String::new(),
+            unions: Vec::new(),
             wheres: Vec::new(),
             order_by: Vec::new(),
             limit: None,
             offset: None,
+            unscoped: false,
+            unscopes: Vec::new(),
+            tenant: None,
+            dialect: Dialect::Generic,
             error: None::<SqlBuilderError>,
         }
     }
@@ -44,55 +60,45 @@ impl SelectBuilder {
         }
     }
 
+    /// Set target SQL dialect.
+    pub fn dialect(&mut self, dialect: Dialect) -> &mut Self {
+        self.dialect = dialect;
+        self
+    }
+
     pub fn and_table<S: ToString>(&mut self, table: S) -> &mut Self {
         self.table = format!("{}, {}", self.table, table.to_string());
         self
     }
 
     pub fn left_join<S: ToString>(&mut self, table: S) -> &mut Self {
-        let mut text = String::from("LEFT JOIN ");
-        text.push_str(&table.to_string());
-
-        self.joins.push(text);
+        self.joins.push(Join::new("LEFT JOIN", &table.to_string()));
         self
     }
 
     pub fn right_join<S: ToString>(&mut self, table: S) -> &mut Self {
-        let mut text = String::from("RIGHT JOIN ");
-        text.push_str(&table.to_string());
-
-        self.joins.push(text);
+        self.joins.push(Join::new("RIGHT JOIN", &table.to_string()));
         self
     }
 
     pub fn inner_join<S: ToString>(&mut self, table: S) -> &mut Self {
-        let mut text = String::from("INNER JOIN ");
-        text.push_str(&table.to_string());
-
-        self.joins.push(text);
+        self.joins.push(Join::new("INNER JOIN", &table.to_string()));
         self
     }
 
     pub fn cross_join<S: ToString>(&mut self, table: S) -> &mut Self {
-        let mut text = String::from("CROSS JOIN ");
-        text.push_str(&table.to_string());
-
-        self.joins.push(text);
+        self.joins.push(Join::new("CROSS JOIN", &table.to_string()));
         self
     }
 
     pub fn join<S: ToString>(&mut self, table: S) -> &mut Self {
-        let mut text = String::from("JOIN ");
-        text.push_str(&table.to_string());
-
-        self.joins.push(text);
+        self.joins.push(Join::new("JOIN", &table.to_string()));
         self
     }
 
     pub fn on<S: ToString>(&mut self, constraint: S) -> &mut Self {
         if let Some(last) = self.joins.last_mut() {
-            last.push_str(" ON ");
-            last.push_str(&constraint.to_string());
+            last.and_on(constraint.to_string());
         }
         self
     }
@@ -137,6 +143,26 @@ impl SelectBuilder {
         self
     }
 
+    /// Add WHERE condition if flag is set.
+    pub fn and_where_if<S: ToString>(&mut self, flag: bool, cond: S) -> &mut Self {
+        if flag {
+            self.and_where(cond)
+        } else {
+            self
+        }
+    }
+
+    /// Apply changes to the builder if flag is set.
+    pub fn apply_if<F>(&mut self, flag: bool, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut Self),
+    {
+        if flag {
+            f(self);
+        }
+        self
+    }
+
     pub fn or_where<S: ToString>(&mut self, cond: S) -> &mut Self {
         // Checks
         let cond = cond.to_string();
@@ -154,16 +180,24 @@ impl SelectBuilder {
         self
     }
 
-    pub fn union<S: ToString>(&mut self, query: S) -> &mut Self {
-        let append = format!(" UNION {}", &query.to_string());
-        self.unions.push_str(&append);
-        self
+    pub fn union<S: SqlQuery>(&mut self, query: S) -> &mut Self {
+        match query.sql_query_for(self.tenant.as_deref()) {
+            Ok(query) => {
+                self.unions.push(("UNION", query));
+                self
+            }
+            Err(err) => self.set_query_error(err),
+        }
     }
 
-    pub fn union_all<S: ToString>(&mut self, query: S) -> &mut Self {
-        self.unions.push_str(" UNION ALL ");
-        self.unions.push_str(&query.to_string());
-        self
+    pub fn union_all<S: SqlQuery>(&mut self, query: S) -> &mut Self {
+        match query.sql_query_for(self.tenant.as_deref()) {
+            Ok(query) => {
+                self.unions.push(("UNION ALL", query));
+                self
+            }
+            Err(err) => self.set_query_error(err),
+        }
     }
 
     pub fn order_by<S: ToString>(&mut self, field: S) -> &mut Self {
@@ -182,6 +216,36 @@ impl SelectBuilder {
         self
     }
 
+    /// Do not apply default scopes.
+    pub fn unscoped(&mut self) -> &mut Self {
+        self.unscoped = true;
+        self
+    }
+
+    /// Do not apply named default scope.
+    pub fn unscope<S: ToString>(&mut self, name: S) -> &mut Self {
+        self.unscopes.push(name.to_string());
+        self
+    }
+
+    /// Set tenant for tenant-scoped tables.
+    pub fn tenant<T: SqlArg>(&mut self, id: T) -> &mut Self {
+        self.tenant = Some(id.sql_arg());
+        self
+    }
+
+    /// Get builder with the outer tenant unless it has own one
+    pub(crate) fn inherit_tenant(&self, tenant: Option<&str>) -> Cow<'_, Self> {
+        match (&self.tenant, tenant) {
+            (None, Some(tenant)) => {
+                let mut builder = self.clone();
+                builder.tenant = Some(tenant.to_string());
+                Cow::Owned(builder)
+            }
+            _ => Cow::Borrowed(self),
+        }
+    }
+
     pub fn build(&self) -> Result<String> {
         if let Some(err) = &self.error {
             return Err(err.clone().into());
@@ -201,6 +265,25 @@ impl SelectBuilder {
             return Err(err.clone().into());
         }
 
+        // UNION parts with ORDER BY, LIMIT or OFFSET of the whole result
+        let outer = !self.order_by.is_empty() || self.limit.is_some() || self.offset.is_some();
+        if !self.unions.is_empty() && outer {
+            let mut first = self.clone();
+            first.unions.clear();
+            first.order_by.clear();
+            first.limit = None;
+            first.offset = None;
+            return CompoundBuilder::from_unions(
+                first.query()?,
+                &self.unions,
+                &self.order_by,
+                &self.limit,
+                &self.offset,
+                self.dialect,
+            )
+            .query();
+        }
+
         // Make fields
         let fields = if self.fields.is_empty() {
             "*".to_string()
@@ -208,11 +291,23 @@ impl SelectBuilder {
             self.fields.join(", ")
         };
 
+        // Make tenant filter
+        let (mut tenant_wheres, mut joins) =
+            tenant::tenant_filter(&self.table, &self.joins, self.tenant.as_deref())?;
+
+        // Make default scopes
+        let (table, mut scope_wheres) = if self.unscoped {
+            (self.table.clone(), Vec::new())
+        } else {
+            scope::scope_sources(&self.table, &mut joins, &self.unscopes)
+        };
+
         // Make JOIN parts
-        let joins = if self.joins.is_empty() {
+        let joins = if joins.is_empty() {
             String::new()
         } else {
-            format!(" {}", self.joins.join(" "))
+            let joins: Vec<String> = joins.iter().map(|join| join.to_string()).collect();
+            format!(" {}", joins.join(" "))
         };
 
         // Make GROUP BY part
@@ -228,10 +323,20 @@ impl SelectBuilder {
         };
 
         // Make WHERE part
-        let wheres = Self::make_wheres(&self.wheres);
+        let mut wheres = self.wheres.clone();
+        wheres.append(&mut scope_wheres);
+        wheres.append(&mut tenant_wheres);
+        let wheres = Self::make_wheres(&wheres);
+
+        // Make UNION parts
+        let unions: String = self
+            .unions
+            .iter()
+            .map(|(op, query)| format!(" {} {}", op, query))
+            .collect();
 
         // Make ORDER BY part
-        let order_by = if self.order_by.is_empty() || !self.unions.is_empty() {
+        let order_by = if self.order_by.is_empty() {
             String::new()
         } else {
             format!(" ORDER BY {}", self.order_by.join(", "))
@@ -252,11 +357,11 @@ impl SelectBuilder {
         // Make SQL
         let sql = format!("SELECT {fields} FROM {table}{joins}{wheres}{group_by}{unions}{order_by}{limit}{offset}",
                           fields = fields,
-                          table = &self.table,
+                          table = table,
                           joins = joins,
                           group_by = group_by,
                           wheres = wheres,
-                          unions = &self.unions,
+                          unions = unions,
                           order_by = order_by,
                           limit = limit,
                           offset = offset,
@@ -279,8 +384,17 @@ impl SelectBuilder {
         }
     }
     /// Set error during build.
-    fn set_error(&mut self, err: &SqlBuilderError) -> &mut Self {
+    pub(crate) fn set_error(&mut self, err: &SqlBuilderError) -> &mut Self {
         self.error = Some(err.clone());
         self
     }
+
+    /// Set error of nested query build.
+    fn set_query_error(&mut self, err: anyhow::Error) -> &mut Self {
+        let err = match err.downcast::<SqlBuilderError>() {
+            Ok(err) => err,
+            Err(err) => SqlBuilderError::InvalidQuery(err.to_string()),
+        };
+        self.set_error(&err)
+    }
 }
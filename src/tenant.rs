@@ -0,0 +1,280 @@
+//! Multi-tenant row scoping.
+//!
+//! Tables registered as tenant-scoped get the tenant filter in every
+//! statement built with a tenant context. Building a statement touching
+//! such a table without the tenant context fails. Builder subqueries
+//! added after the tenant is set take it, subqueries in FROM part and
+//! subqueries added before need their own tenant.
+//!
+//! ```
+//! # use anyhow::Result;
+//! use sql_builder::{tenant, SqlBuilder};
+//!
+//! # fn main() -> Result<()> {
+//! tenant::register_tenant_table("invoices", "tenant_id");
+//!
+//! let sql = SqlBuilder::select_from("invoices")
+//!     .field("total")
+//!     .tenant(7)
+//!     .sql()?;
+//!
+//! assert_eq!("SELECT total FROM invoices WHERE tenant_id = 7;", &sql);
+//!
+//! let res = SqlBuilder::select_from("invoices").sql();
+//!
+//! assert_eq!("Tenant is not set for table \"invoices\"", &res.unwrap_err().to_string());
+//!
+//! let sql = SqlBuilder::select_from("customers")
+//!     .field("name")
+//!     .tenant(7)
+//!     .and_where_in_query("id", SqlBuilder::select_from("invoices").field("customer_id"))
+//!     .sql()?;
+//!
+//! assert_eq!("SELECT name FROM customers WHERE id IN (SELECT customer_id FROM invoices WHERE tenant_id = 7);", &sql);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::SqlBuilderError;
+use crate::scope::table_names;
+use crate::Join;
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+/// Global registry of tenant-scoped tables with tenant columns
+fn registry() -> &'static RwLock<HashMap<String, String>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<String, String>>> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// Register tenant-scoped table with the tenant column.
+pub fn register_tenant_table<S, C>(table: S, column: C)
+where
+    S: ToString,
+    C: ToString,
+{
+    let mut registry = registry().write().unwrap_or_else(|e| e.into_inner());
+    registry.insert(table.to_string(), column.to_string());
+}
+
+/// Remove tenant scoping of the table.
+pub fn remove_tenant_table<S: ToString>(table: S) {
+    let mut registry = registry().write().unwrap_or_else(|e| e.into_inner());
+    registry.remove(&table.to_string());
+}
+
+/// Find tenant column of the table
+pub(crate) fn tenant_column(table: &str) -> Option<String> {
+    let registry = registry().read().unwrap_or_else(|e| e.into_inner());
+    if registry.is_empty() {
+        return None;
+    }
+    let (table, _) = table_names(table).into_iter().next()?;
+    registry.get(&table).cloned()
+}
+
+/// Make tenant conditions for FROM part and tenant-filtered JOIN parts.
+/// Conditions for inner joins without ON constraint, like CROSS JOIN or
+/// JOIN with USING, are returned with WHERE conditions, outer joins
+/// without ON constraint filter the joined table.
+pub(crate) fn tenant_filter(
+    from: &str,
+    joins: &[Join],
+    tenant: Option<&str>,
+) -> Result<(Vec<String>, Vec<Join>), SqlBuilderError> {
+    let registry = registry().read().unwrap_or_else(|e| e.into_inner());
+    if registry.is_empty() {
+        return Ok((Vec::new(), joins.to_vec()));
+    }
+
+    let tables = table_names(from);
+    let qualify = tables.len() > 1 || !joins.is_empty();
+
+    let mut wheres = Vec::new();
+    for (table, alias) in tables {
+        if let Some(column) = registry.get(&table) {
+            let tenant = tenant.ok_or_else(|| SqlBuilderError::NoTenant(table.clone()))?;
+            wheres.push(tenant_cond(&table, &alias, column, tenant, qualify));
+        }
+    }
+
+    let mut tenant_joins = joins.to_vec();
+    for join in &mut tenant_joins {
+        let found = join
+            .target()
+            .and_then(|(table, alias)| registry.get(&table).map(|c| (table, alias, c)));
+        let Some((table, alias, column)) = found else {
+            continue;
+        };
+        let tenant = tenant.ok_or_else(|| SqlBuilderError::NoTenant(table.clone()))?;
+        let outer = ["LEFT", "RIGHT", "FULL"]
+            .iter()
+            .any(|kind| join.kind.contains(kind));
+        if !join.on.is_empty() {
+            join.and_on(tenant_cond(&table, &alias, column, tenant, true));
+        } else if outer {
+            join.filters
+                .push(tenant_cond(&table, &None, column, tenant, false));
+        } else {
+            wheres.push(tenant_cond(&table, &alias, column, tenant, true));
+        }
+    }
+
+    Ok((wheres, tenant_joins))
+}
+
+/// Make tenant condition for the table
+fn tenant_cond(
+    table: &str,
+    alias: &Option<String>,
+    column: &str,
+    tenant: &str,
+    qualify: bool,
+) -> String {
+    match (alias, qualify) {
+        (Some(alias), _) => format!("{}.{} = {}", alias, column, tenant),
+        (None, true) => format!("{}.{} = {}", table, column, tenant),
+        (None, false) => format!("{} = {}", column, tenant),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array::SqlArray;
+    use crate::select::SelectBuilder;
+    use crate::{Dialect, SqlBuilder, SqlQuery};
+    use anyhow::Result;
+
+    #[test]
+    fn test_tenant_select() -> Result<()> {
+        register_tenant_table("tenant_books", "tenant_id");
+        register_tenant_table("tenant_shops", "tenant_id");
+
+        let sql = SqlBuilder::select_from("tenant_books AS b")
+            .field("b.title")
+            .left_join("tenant_shops AS s")
+            .on("b.id = s.book")
+            .cross_join("tenant_shops")
+            .and_where("b.price > 100")
+            .tenant(3)
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT b.title FROM tenant_books AS b LEFT JOIN tenant_shops AS s ON (b.id = s.book) AND (s.tenant_id = 3) CROSS JOIN tenant_shops WHERE (b.price > 100) AND (b.tenant_id = 3) AND (tenant_shops.tenant_id = 3);"
+        );
+
+        let sql = SqlBuilder::select_from("books")
+            .join("tenant_shops")
+            .using(["id"])
+            .join("tenant_books AS b ON b.id = books.id")
+            .tenant(3)
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT * FROM books JOIN tenant_shops USING (id) JOIN tenant_books AS b ON (b.id = books.id) AND (b.tenant_id = 3) WHERE tenant_shops.tenant_id = 3;"
+        );
+
+        let res = SqlBuilder::select_from("books")
+            .join("tenant_shops AS s ON s.book = books.id")
+            .sql();
+        assert_eq!(
+            &res.unwrap_err().to_string(),
+            "Tenant is not set for table \"tenant_shops\""
+        );
+
+        let sql = SqlBuilder::select_from("books")
+            .left_join("tenant_shops")
+            .using(["book_id"])
+            .tenant(3)
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT * FROM books LEFT JOIN (SELECT * FROM tenant_shops WHERE tenant_id = 3) AS tenant_shops USING (book_id);"
+        );
+
+        let shops = SelectBuilder::from_table("tenant_shops")
+            .field("book")
+            .clone();
+        let sql = SqlBuilder::select_from("books")
+            .tenant(3)
+            .and_where_in_query("id", &shops)
+            .union(
+                SqlBuilder::select_from("tenant_books")
+                    .field("id")
+                    .tenant(4),
+            )
+            .left_join((&shops).aliased("s"))
+            .on("s.book = books.id")
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT * FROM books LEFT JOIN (SELECT book FROM tenant_shops WHERE tenant_id = 3) AS s ON s.book = books.id WHERE id IN (SELECT book FROM tenant_shops WHERE tenant_id = 3) UNION SELECT id FROM tenant_books WHERE tenant_id = 4;"
+        );
+
+        let res = SqlBuilder::select_from("books")
+            .and_where_in_query("id", &shops)
+            .tenant(3)
+            .sql();
+        assert_eq!(
+            &res.unwrap_err().to_string(),
+            "Tenant is not set for table \"tenant_shops\""
+        );
+
+        let sql =
+            SqlBuilder::select_from(SqlArray::new(&[1, 2]).unnest("t(id)", Dialect::Postgres)?)
+                .join("tenant_books AS b")
+                .on("b.id = t.id")
+                .tenant(3)
+                .sql()?;
+        assert_eq!(
+            &sql,
+            "SELECT * FROM unnest(ARRAY[1, 2]) AS t(id) JOIN tenant_books AS b ON (b.id = t.id) AND (b.tenant_id = 3);"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_tenant_modify() -> Result<()> {
+        register_tenant_table("tenant_orders", "tenant_id");
+
+        let sql = SqlBuilder::insert_into("tenant_orders")
+            .fields(["title", "total"])
+            .values(&["'a'", "1"])
+            .values(&["'b'", "2"])
+            .tenant("acme")
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "INSERT INTO tenant_orders (title, total, tenant_id) VALUES ('a', 1, 'acme'), ('b', 2, 'acme');"
+        );
+
+        let sql = SqlBuilder::update_table("tenant_orders")
+            .set("total", 0)
+            .and_where("id = 1")
+            .tenant("acme")
+            .sql()?;
+        assert_eq!(
+            &sql,
+            "UPDATE tenant_orders SET total = 0 WHERE (id = 1) AND (tenant_id = 'acme');"
+        );
+
+        let sql = SqlBuilder::delete_from("tenant_orders")
+            .tenant("acme")
+            .sql()?;
+        assert_eq!(&sql, "DELETE FROM tenant_orders WHERE tenant_id = 'acme';");
+
+        let res = SqlBuilder::insert_into("tenant_orders")
+            .field("title")
+            .select("SELECT title FROM drafts")
+            .tenant("acme")
+            .sql();
+        assert_eq!(
+            &res.unwrap_err().to_string(),
+            "Tenant field \"tenant_id\" not defined"
+        );
+
+        Ok(())
+    }
+}
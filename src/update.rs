@@ -1,6 +1,8 @@
 use crate::arg::SqlArg;
 pub use crate::error::SqlBuilderError;
 pub use crate::name::SqlName;
+use crate::tenant;
+use crate::VersionLock;
 //pub use crate::where::WhereBuilder;
 use anyhow::Result;
 
@@ -11,6 +13,8 @@ pub struct UpdateBuilder {
     sets: Vec<String>,
     returning: Option<String>,
     wheres: Vec<String>,
+    version_lock: Option<VersionLock>,
+    tenant: Option<String>,
     error: Option<SqlBuilderError>,
 }
 
@@ -22,6 +26,8 @@ impl UpdateBuilder {
             sets: Vec::new(),
             returning: None,
             wheres: Vec::new(),
+            version_lock: None,
+            tenant: None,
             error: None::<SqlBuilderError>,
         }
     }
@@ -55,6 +61,26 @@ impl UpdateBuilder {
         self
     }
 
+    /// Add WHERE condition if flag is set.
+    pub fn and_where_if<S: ToString>(&mut self, flag: bool, cond: S) -> &mut Self {
+        if flag {
+            self.and_where(cond)
+        } else {
+            self
+        }
+    }
+
+    /// Apply changes to the builder if flag is set.
+    pub fn apply_if<F>(&mut self, flag: bool, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut Self),
+    {
+        if flag {
+            f(self);
+        }
+        self
+    }
+
     pub fn or_where<S: ToString>(&mut self, cond: S) -> &mut Self {
         // Checks
         let cond = cond.to_string();
@@ -72,6 +98,50 @@ impl UpdateBuilder {
         self
     }
 
+    /// Use optimistic locking by version column.
+    pub fn optimistic_lock<S, T>(&mut self, column: S, version: T) -> &mut Self
+    where
+        S: ToString,
+        T: SqlArg,
+    {
+        self.set_version_lock(column, version, false)
+    }
+
+    /// Use optimistic locking by version column and return the new version.
+    pub fn optimistic_lock_returning<S, T>(&mut self, column: S, version: T) -> &mut Self
+    where
+        S: ToString,
+        T: SqlArg,
+    {
+        self.set_version_lock(column, version, true)
+    }
+
+    fn set_version_lock<S, T>(&mut self, column: S, version: T, returning: bool) -> &mut Self
+    where
+        S: ToString,
+        T: SqlArg,
+    {
+        // Checks
+        let column = column.to_string();
+        if column.is_empty() {
+            return self.set_error(&SqlBuilderError::NoWhereField);
+        }
+
+        // Change
+        self.version_lock = Some(VersionLock {
+            column,
+            version: version.sql_arg(),
+            returning,
+        });
+        self
+    }
+
+    /// Set tenant for tenant-scoped tables.
+    pub fn tenant<T: SqlArg>(&mut self, id: T) -> &mut Self {
+        self.tenant = Some(id.sql_arg());
+        self
+    }
+
     /// Set error during build.
     fn set_error(&mut self, err: &SqlBuilderError) -> &mut Self {
         self.error = Some(err.clone());
@@ -94,13 +164,24 @@ impl UpdateBuilder {
         }
 
         // Make SET part
-        let sets = self.sets.join(", ");
+        let mut sets = self.sets.clone();
+        let mut wheres = self.wheres.clone();
+        let mut returning = self.returning.clone();
+        if let Some(lock) = &self.version_lock {
+            sets.push(lock.set_expr());
+            wheres.push(lock.where_cond());
+            returning = lock.returning(&returning);
+        }
+        let (mut tenant_wheres, _) =
+            tenant::tenant_filter(&self.table, &[], self.tenant.as_deref())?;
+        wheres.append(&mut tenant_wheres);
+        let sets = sets.join(", ");
 
         // Make WHERE part
-        let wheres = Self::make_wheres(&self.wheres);
+        let wheres = Self::make_wheres(&wheres);
 
         // Make RETURNING part
-        let returning = if let Some(ret) = &self.returning {
+        let returning = if let Some(ret) = &returning {
             format!(" RETURNING {}", ret)
         } else {
             "".to_string()
@@ -137,3 +218,25 @@ impl UpdateBuilder {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_optimistic_lock() -> Result<()> {
+        let sql = UpdateBuilder::update_table("books")
+            .set("price", 150)
+            .and_where("id = 42")
+            .or_where("id = 43")
+            .optimistic_lock_returning("version", 7)
+            .build()?;
+
+        assert_eq!(
+            &sql,
+            "UPDATE books SET price = 150, version = version + 1 WHERE (id = 42 OR id = 43) AND (version = 7) RETURNING version;"
+        );
+
+        Ok(())
+    }
+}